@@ -2,203 +2,126 @@
 use chromiumoxide::Page; 
 use std::time::Duration; 
 
+use tracing::{error, info, warn};
+
+use crate::confirmation::{self, ConfirmationStatus};
+use crate::contact::{self, ContactFields, ContactFillReport};
+use crate::control::Step;
+use crate::events::RunContext;
+use crate::locators::{self, Locator};
+use crate::pii::{Carrier, Email};
+use crate::retry::RetryPolicy;
+use crate::targets::{ActionWindow, TargetTracker};
+
+// 사용자 개입(PASS 인증) 기본 대기 시간
+pub const DEFAULT_USER_ACTION_TIMEOUT_SECS: u64 = 120;
+
 // 1. 쿠키 팝업 처리 함수 (정확한 셀렉터 사용)
-pub async fn handle_initial_popup(page: &Page) -> Result<(), String> {
-    println!("🍪 쿠키 팝업 처리 중...");
+pub async fn handle_initial_popup(page: &Page, locators: &[Locator], policy: &RetryPolicy) -> Result<(), String> {
+    info!("🍪 쿠키 팝업 처리 중...");
     tokio::time::sleep(Duration::from_secs(2)).await;
 
-    for attempt in 0..3 {
-        println!("🍪 쿠키 팝업 처리 시도 {}/3", attempt + 1);
+    let mut attempts = policy.start();
+    while let Some(attempt) = attempts.next().await {
+        info!("🍪 쿠키 팝업 처리 시도 {}/{}", attempt, attempts.total());
         
-        // 크로노디그마 전용 쿠키 수락 버튼 셀렉터
-        let popup_click_js = r#"
-            (() => {
-                const acceptBtn = document.querySelector('.cookies__button--accept');
-                if (acceptBtn && window.getComputedStyle(acceptBtn).display !== 'none') {
-                    console.log('쿠키 수락 버튼 찾음');
-                    acceptBtn.click();
-                    return true;
-                }
-                return false;
-            })()
-        "#;
-
-        match page.evaluate_expression(popup_click_js.to_string()).await {
-            Ok(result) => {
-                if let Ok(success) = result.into_value::<bool>() {
-                    if success {
-                        println!("✅ 쿠키 팝업 처리 완료");
-                        tokio::time::sleep(Duration::from_secs(2)).await;
-                        return Ok(());
-                    }
-                }
+        match attempts.timed(locators::find_and_click(page, Step::CookiePopup, locators)).await.and_then(|found| found) {
+            Ok(Some(_)) => {
+                info!("✅ 쿠키 팝업 처리 완료");
+                tokio::time::sleep(Duration::from_secs(2)).await;
+                return Ok(());
             }
-            Err(_) => {}
+            Ok(None) => {}
+            Err(e) => warn!("⚠️ 쿠키 수락 버튼 확인 중 오류: {}", e),
         }
-        
-        tokio::time::sleep(Duration::from_secs(1)).await;
     }
     
-    println!("ℹ️ 쿠키 팝업이 없거나 이미 처리됨");
+    info!("ℹ️ 쿠키 팝업이 없거나 이미 처리됨");
     Ok(())
 }
 
 // 2. 방문 예약하기 버튼 클릭 (정확한 셀렉터)
-pub async fn click_visit_reservation_button(page: &Page) -> Result<(), String> {
-    println!("📅 방문 예약하기 버튼 찾는 중...");
+pub async fn click_visit_reservation_button(page: &Page, locators: &[Locator], policy: &RetryPolicy) -> Result<(), String> {
+    info!("📅 방문 예약하기 버튼 찾는 중...");
     tokio::time::sleep(Duration::from_secs(2)).await;
 
-    for attempt in 0..5 {
-        println!("📅 방문 예약하기 버튼 클릭 시도 {}/5", attempt + 1);
+    let mut attempts = policy.start();
+    while let Some(attempt) = attempts.next().await {
+        info!("📅 방문 예약하기 버튼 클릭 시도 {}/{}", attempt, attempts.total());
         
-        let reservation_click_js = r#"
-            (() => {
-                const reservationBtn = document.querySelector('a[href="https://www.chronodigmwatch.co.kr/rolex/contact-seoul/appointment/"]');
-                if (reservationBtn && window.getComputedStyle(reservationBtn).display !== 'none') {
-                    console.log('방문 예약하기 버튼 찾음');
-                    reservationBtn.scrollIntoView({behavior: 'instant', block: 'center'});
-                    reservationBtn.click();
-                    return true;
-                }
-                return false;
-            })()
-        "#;
-
-        match page.evaluate_expression(reservation_click_js.to_string()).await {
-            Ok(result) => {
-                if let Ok(success) = result.into_value::<bool>() {
-                    if success {
-                        println!("✅ 방문 예약하기 버튼 클릭 완료");
-                        tokio::time::sleep(Duration::from_secs(1)).await; // 빠른 진행
-                        return Ok(());
-                    }
-                }
+        match attempts.timed(locators::find_and_click(page, Step::VisitReservation, locators)).await.and_then(|found| found) {
+            Ok(Some(_)) => {
+                info!("✅ 방문 예약하기 버튼 클릭 완료");
+                tokio::time::sleep(Duration::from_secs(1)).await; // 빠른 진행
+                return Ok(());
             }
-            Err(_) => {}
+            Ok(None) => {}
+            Err(e) => warn!("⚠️ 방문 예약하기 버튼 확인 중 오류: {}", e),
         }
-        
-        tokio::time::sleep(Duration::from_secs(2)).await;
     }
     
     Err("방문 예약하기 버튼을 찾을 수 없습니다.".to_string())
 }
 
 // 3. 롤렉스 컬렉션 버튼 클릭 (정확한 셀렉터)
-pub async fn click_rolex_collection_button(page: &Page) -> Result<(), String> {
-    println!("🛍️ 롤렉스 컬렉션 버튼 찾는 중...");
+pub async fn click_rolex_collection_button(page: &Page, locators: &[Locator], policy: &RetryPolicy) -> Result<(), String> {
+    info!("🛍️ 롤렉스 컬렉션 버튼 찾는 중...");
     tokio::time::sleep(Duration::from_secs(2)).await;
 
-    for attempt in 0..5 {
-        println!("🛍️ 롤렉스 컬렉션 버튼 클릭 시도 {}/5", attempt + 1);
+    let mut attempts = policy.start();
+    while let Some(attempt) = attempts.next().await {
+        info!("🛍️ 롤렉스 컬렉션 버튼 클릭 시도 {}/{}", attempt, attempts.total());
         
-        let rolex_click_js = r#"
-            (() => {
-                const collectionBtn = document.querySelector('a[onclick="select_type(\'collection\');"]');
-                if (collectionBtn && window.getComputedStyle(collectionBtn).display !== 'none') {
-                    console.log('롤렉스 컬렉션 버튼 찾음');
-                    collectionBtn.scrollIntoView({behavior: 'instant', block: 'center'});
-                    collectionBtn.click();
-                    return true;
-                }
-                return false;
-            })()
-        "#;
-
-        match page.evaluate_expression(rolex_click_js.to_string()).await {
-            Ok(result) => {
-                if let Ok(success) = result.into_value::<bool>() {
-                    if success {
-                        println!("✅ 롤렉스 컬렉션 버튼 클릭 완료");
-                        tokio::time::sleep(Duration::from_secs(1)).await; // 빠른 진행
-                        return Ok(());
-                    }
-                }
+        match attempts.timed(locators::find_and_click(page, Step::RolexCollection, locators)).await.and_then(|found| found) {
+            Ok(Some(_)) => {
+                info!("✅ 롤렉스 컬렉션 버튼 클릭 완료");
+                tokio::time::sleep(Duration::from_secs(1)).await; // 빠른 진행
+                return Ok(());
             }
-            Err(_) => {}
+            Ok(None) => {}
+            Err(e) => warn!("⚠️ 롤렉스 컬렉션 버튼 확인 중 오류: {}", e),
         }
-        
-        tokio::time::sleep(Duration::from_secs(2)).await;
     }
     
     Err("롤렉스 컬렉션 버튼을 찾을 수 없습니다.".to_string())
 }
 
 // 4. 동의합니다 버튼 클릭 (정확한 셀렉터)
-pub async fn click_agree_button(page: &Page) -> Result<(), String> {
-    println!("✅ 동의합니다 버튼 찾는 중...");
+pub async fn click_agree_button(page: &Page, locators: &[Locator], policy: &RetryPolicy) -> Result<(), String> {
+    info!("✅ 동의합니다 버튼 찾는 중...");
     tokio::time::sleep(Duration::from_secs(2)).await;
 
-    for attempt in 0..3 {
-        println!("✅ 동의합니다 버튼 클릭 시도 {}/3", attempt + 1);
+    let mut attempts = policy.start();
+    while let Some(attempt) = attempts.next().await {
+        info!("✅ 동의합니다 버튼 클릭 시도 {}/{}", attempt, attempts.total());
         
-        let agree_click_js = r#"
-            (() => {
-                const agreeBtn = document.querySelector('button.rolex-button');
-                if (agreeBtn && window.getComputedStyle(agreeBtn).display !== 'none') {
-                    const text = agreeBtn.textContent.trim();
-                    if (text.includes('동의합니다')) {
-                        console.log('동의합니다 버튼 찾음:', text);
-                        agreeBtn.scrollIntoView({behavior: 'instant', block: 'center'});
-                        agreeBtn.click();
-                        return true;
-                    }
-                }
-                return false;
-            })()
-        "#;
-
-        match page.evaluate_expression(agree_click_js.to_string()).await {
-            Ok(result) => {
-                if let Ok(success) = result.into_value::<bool>() {
-                    if success {
-                        println!("✅ 동의합니다 버튼 클릭 완료");
-                        tokio::time::sleep(Duration::from_secs(1)).await; // 빠른 진행
-                        return Ok(());
-                    }
-                }
+        match attempts.timed(locators::find_and_click(page, Step::Agree, locators)).await.and_then(|found| found) {
+            Ok(Some(_)) => {
+                info!("✅ 동의합니다 버튼 클릭 완료");
+                tokio::time::sleep(Duration::from_secs(1)).await; // 빠른 진행
+                return Ok(());
             }
-            Err(_) => {}
+            Ok(None) => {}
+            Err(e) => warn!("⚠️ 동의합니다 버튼 확인 중 오류: {}", e),
         }
-        
-        tokio::time::sleep(Duration::from_secs(1)).await;
     }
     
     Err("동의합니다 버튼을 찾을 수 없습니다.".to_string())
 }
 
 // 5. 방문 날짜 선택 (정확한 셀렉터)
-pub async fn select_visit_date(page: &Page, visit_date: &str) -> Result<(), String> {
-    println!("📅 방문 날짜 선택 중: {}", visit_date);
+pub async fn select_visit_date(page: &Page, visit_date: &str, policy: &RetryPolicy) -> Result<(), String> {
+    info!("📅 방문 날짜 선택 중: {}", visit_date);
     tokio::time::sleep(Duration::from_secs(2)).await;
 
-    // 먼저 예약 마감 메시지가 있는지 확인
-    let check_closed_js = r#"
-        (() => {
-            const body = document.body.textContent;
-            return body.includes('온라인 예약이 마감되었습니다') || 
-                   body.includes('예약이 마감') ||
-                   body.includes('마감');
-        })()
-    "#;
-
-    match page.evaluate_expression(check_closed_js.to_string()).await {
-        Ok(result) => {
-            if let Ok(is_closed) = result.into_value::<bool>() {
-                if is_closed {
-                    return Err("온라인 예약이 마감되었습니다. 다음 예약 오픈 시간을 확인해주세요.".to_string());
-                }
-            }
-        }
-        Err(_) => {}
-    }
-
-    for attempt in 0..3 {
-        println!("📅 방문 날짜 선택 시도 {}/3", attempt + 1);
+    let mut attempts = policy.start();
+    while let Some(attempt) = attempts.next().await {
+        info!("📅 방문 날짜 선택 시도 {}/{}", attempt, attempts.total());
         
         let date_select_js = format!(r#"
             (() => {{
-                const targetDate = '{}';
-                const dateBtn = document.querySelector('#appointment .datetime-form .date-list ul > li[data-date="' + targetDate + '"]');
+                const targetDate = {};
+                const dateBtn = document.querySelector('#appointment .datetime-form .date-list ul > li[data-date="' + CSS.escape(targetDate) + '"]');
                 
                 if (dateBtn && !dateBtn.classList.contains('off') && window.getComputedStyle(dateBtn).display !== 'none') {{
                     console.log('날짜 버튼 찾음:', targetDate);
@@ -208,42 +131,44 @@ pub async fn select_visit_date(page: &Page, visit_date: &str) -> Result<(), Stri
                 }}
                 return false;
             }})()
-        "#, visit_date);
+        "#, serde_json::to_string(visit_date).map_err(|e| e.to_string())?);
 
-        match page.evaluate_expression(date_select_js).await {
-            Ok(result) => {
-                if let Ok(success) = result.into_value::<bool>() {
-                    if success {
-                        println!("✅ 방문 날짜 선택 완료: {}", visit_date);
+        match attempts.timed(page.evaluate_expression(date_select_js)).await {
+            Ok(Ok(result)) => {
+                match result.into_value::<bool>() {
+                    Ok(true) => {
+                        info!("✅ 방문 날짜 선택 완료: {}", visit_date);
                         tokio::time::sleep(Duration::from_secs(1)).await; // 빠른 진행
                         return Ok(());
                     }
+                    Ok(false) => {}
+                    Err(e) => warn!("⚠️ 날짜 선택 결과 파싱 실패: {}", e),
                 }
             }
-            Err(_) => {}
+            Ok(Err(e)) => warn!("⚠️ 날짜 선택 스크립트 실행 오류: {}", e),
+            Err(e) => warn!("⚠️ 날짜 선택 시도 실패: {}", e),
         }
-        
-        tokio::time::sleep(Duration::from_secs(2)).await;
     }
     
     Err(format!("방문 날짜({})를 선택할 수 없습니다.", visit_date))
 }
 
 // 6. 방문 시간 선택 (정확한 셀렉터)
-pub async fn select_visit_time(page: &Page, visit_time: &str) -> Result<(), String> {
-    println!("🕐 방문 시간 선택 중: {}", visit_time);
+pub async fn select_visit_time(page: &Page, visit_time: &str, policy: &RetryPolicy) -> Result<(), String> {
+    info!("🕐 방문 시간 선택 중: {}", visit_time);
     tokio::time::sleep(Duration::from_secs(2)).await;
 
     // 시간을 분으로 변환 (17:30 -> 1050분)
     let time_in_minutes = convert_time_to_minutes(visit_time)?;
 
-    for attempt in 0..3 {
-        println!("🕐 방문 시간 선택 시도 {}/3", attempt + 1);
+    let mut attempts = policy.start();
+    while let Some(attempt) = attempts.next().await {
+        info!("🕐 방문 시간 선택 시도 {}/{}", attempt, attempts.total());
         
         let time_select_js = format!(r#"
             (() => {{
-                const timeValue = '{}';
-                const timeBtn = document.querySelector('#appointment .datetime-form .time-list ul > li[data-time="' + timeValue + '"]');
+                const timeValue = {};
+                const timeBtn = document.querySelector('#appointment .datetime-form .time-list ul > li[data-time="' + CSS.escape(timeValue) + '"]');
                 
                 if (timeBtn && !timeBtn.classList.contains('off') && window.getComputedStyle(timeBtn).display !== 'none') {{
                     console.log('시간 버튼 찾음:', timeValue);
@@ -253,76 +178,66 @@ pub async fn select_visit_time(page: &Page, visit_time: &str) -> Result<(), Stri
                 }}
                 return false;
             }})()
-        "#, time_in_minutes);
+        "#, serde_json::to_string(&time_in_minutes.to_string()).map_err(|e| e.to_string())?);
 
-        match page.evaluate_expression(time_select_js).await {
-            Ok(result) => {
-                if let Ok(success) = result.into_value::<bool>() {
-                    if success {
-                        println!("✅ 방문 시간 선택 완료: {} ({}분)", visit_time, time_in_minutes);
+        match attempts.timed(page.evaluate_expression(time_select_js)).await {
+            Ok(Ok(result)) => {
+                match result.into_value::<bool>() {
+                    Ok(true) => {
+                        info!("✅ 방문 시간 선택 완료: {} ({}분)", visit_time, time_in_minutes);
                         tokio::time::sleep(Duration::from_secs(1)).await; // 빠른 진행
                         return Ok(());
                     }
+                    Ok(false) => {}
+                    Err(e) => warn!("⚠️ 시간 선택 결과 파싱 실패: {}", e),
                 }
             }
-            Err(_) => {}
+            Ok(Err(e)) => warn!("⚠️ 시간 선택 스크립트 실행 오류: {}", e),
+            Err(e) => warn!("⚠️ 시간 선택 시도 실패: {}", e),
         }
-        
-        tokio::time::sleep(Duration::from_secs(2)).await;
     }
     
     Err(format!("방문 시간({})을 선택할 수 없습니다.", visit_time))
 }
 
 // 7. 다음 버튼 클릭 (날짜/시간 선택 후)
-pub async fn click_next_button(page: &Page) -> Result<(), String> {
-    println!("➡️ 다음 버튼 찾는 중...");
+pub async fn click_next_button(page: &Page, locators: &[Locator], policy: &RetryPolicy) -> Result<(), String> {
+    info!("➡️ 다음 버튼 찾는 중...");
     tokio::time::sleep(Duration::from_secs(2)).await;
 
-    for attempt in 0..3 {
-        println!("➡️ 다음 버튼 클릭 시도 {}/3", attempt + 1);
+    let mut attempts = policy.start();
+    while let Some(attempt) = attempts.next().await {
+        info!("➡️ 다음 버튼 클릭 시도 {}/{}", attempt, attempts.total());
         
-        let next_click_js = r#"
-            (() => {
-                const nextBtn = document.querySelector('button[name="verification"]');
-                if (nextBtn && window.getComputedStyle(nextBtn).display !== 'none') {
-                    console.log('다음 버튼 찾음');
-                    nextBtn.scrollIntoView({behavior: 'instant', block: 'center'});
-                    nextBtn.click();
-                    return true;
-                }
-                return false;
-            })()
-        "#;
-
-        match page.evaluate_expression(next_click_js.to_string()).await {
-            Ok(result) => {
-                if let Ok(success) = result.into_value::<bool>() {
-                    if success {
-                        println!("✅ 다음 버튼 클릭 완료");
-                        tokio::time::sleep(Duration::from_secs(1)).await; // 빠른 진행
-                        return Ok(());
-                    }
-                }
+        match attempts.timed(locators::find_and_click(page, Step::Next, locators)).await.and_then(|found| found) {
+            Ok(Some(_)) => {
+                info!("✅ 다음 버튼 클릭 완료");
+                tokio::time::sleep(Duration::from_secs(1)).await; // 빠른 진행
+                return Ok(());
             }
-            Err(_) => {}
+            Ok(None) => {}
+            Err(e) => warn!("⚠️ 다음 버튼 확인 중 오류: {}", e),
         }
-        
-        tokio::time::sleep(Duration::from_secs(1)).await;
     }
     
     Err("다음 버튼을 찾을 수 없습니다.".to_string())
 }
 
 // 8. PASS 인증 처리 (팝업창 대기)
-pub async fn handle_pass_authentication(page: &Page, carrier: &str) -> Result<(), String> {
-    println!("🔐 PASS 인증 처리 시작 - 통신사: {}", carrier);
+pub async fn handle_pass_authentication(
+    page: &Page,
+    carrier: &Carrier,
+    tracker: &TargetTracker,
+    policy: &RetryPolicy,
+    run: &RunContext,
+) -> Result<(), String> {
+    info!("🔐 PASS 인증 처리 시작 - 통신사: {}", carrier);
     tokio::time::sleep(Duration::from_secs(2)).await;
 
     // 통신사 선택 시도
     let carrier_select_js = format!(r#"
         (() => {{
-            const targetCarrier = '{}';
+            const targetCarrier = {};
             const elements = document.querySelectorAll('button, a, option');
             
             for (const el of elements) {{
@@ -343,114 +258,117 @@ pub async fn handle_pass_authentication(page: &Page, carrier: &str) -> Result<()
             }}
             return false;
         }})()
-    "#, carrier);
+    "#, serde_json::to_string(carrier.expose()).map_err(|e| e.to_string())?);
 
     match page.evaluate_expression(carrier_select_js).await {
-        Ok(result) => {
-            if let Ok(success) = result.into_value::<bool>() {
-                if success {
-                    println!("✅ 통신사 선택 완료: {}", carrier);
-                } else {
-                    println!("⚠️ 통신사 자동 선택 실패");
-                }
-            }
-        }
-        Err(_) => {
-            println!("⚠️ 통신사 자동 선택 실패");
+        Ok(result) => match result.into_value::<bool>() {
+            Ok(true) => info!("✅ 통신사 선택 완료: {}", carrier),
+            Ok(false) => warn!("⚠️ 통신사 자동 선택 실패"),
+            Err(e) => warn!("⚠️ 통신사 선택 결과 파싱 실패: {}", e),
+        },
+        Err(e) => {
+            warn!("⚠️ 통신사 자동 선택 실패 (스크립트 실행 오류: {})", e);
         }
     }
 
-    println!("📱 QR 코드 인증을 진행해주세요. 사용자 개입이 필요합니다.");
-    println!("⏳ 인증 완료까지 대기 중... (최대 120초)");
+    info!("📱 QR 코드 인증을 진행해주세요. 사용자 개입이 필요합니다.");
+    match policy.deadline() {
+        Some(deadline) => info!("⏳ 인증 완료까지 대기 중... (최대 {}초)", deadline.as_secs()),
+        None => info!("⏳ 인증 완료까지 대기 중... (최대 {}회 확인)", policy.attempts),
+    }
 
-    // PASS 인증 팝업창이 사라질 때까지 대기
-    for wait_attempt in 0..60 {
-        tokio::time::sleep(Duration::from_secs(2)).await;
-        
-        // 팝업창 또는 인증창이 사라졌는지 확인
-        let popup_check_js = r#"
-            (() => {
-                // 1. 새 창 체크
-                if (window.opener) {
-                    return false; // 팝업창에 있음
-                }
-                
-                // 2. iframe 체크
-                const authIframes = document.querySelectorAll('iframe[src*="pass"], iframe[src*="auth"], iframe[src*="okname"]');
-                const visibleIframes = Array.from(authIframes).filter(iframe => 
-                    window.getComputedStyle(iframe).display !== 'none'
-                );
-                
-                // 3. 모달/팝업 체크
-                const modals = document.querySelectorAll('.modal, .popup, .dialog');
-                const visibleModals = Array.from(modals).filter(modal => 
-                    window.getComputedStyle(modal).display !== 'none'
-                );
-                
-                // 4. 연락처 정보 입력 폼이 나타났는지 체크
-                const contactForm = document.querySelector('input[name="email"]');
-                const contactFormVisible = contactForm && window.getComputedStyle(contactForm).display !== 'none';
-                
-                return visibleIframes.length === 0 && visibleModals.length === 0 && contactFormVisible;
-            })()
-        "#;
-
-        match page.evaluate_expression(popup_check_js.to_string()).await {
-            Ok(result) => {
-                if let Ok(auth_complete) = result.into_value::<bool>() {
-                    if auth_complete {
-                        println!("✅ PASS 인증 완료 (연락처 정보 입력 단계로 진행)");
-                        return Ok(());
-                    }
-                }
-            }
-            Err(_) => {}
+    // 인증 팝업 창/iframe 이 모두 닫히고 연락처 입력 폼이 나타날 때까지 대기 (확인 간격은 정책의 backoff)
+    let mut attempts = policy.start();
+    let mut reported: Option<ActionWindow> = None;
+    let mut last_status = 0;
+    while attempts.next().await.is_some() {
+        // 사용자가 조작해야 하는 창이 바뀔 때마다 알림 (팝업 열림/닫힘, iframe 표시 등)
+        let window = tracker.action_window(page).await;
+        if reported.as_ref() != Some(&window) {
+            info!("🪟 사용자 인증 위치: {}", window);
+            run.emit_waiting(
+                &window,
+                format!("{} 브라우저의 {}에서 PASS 인증을 완료해주세요", run.store_name, window),
+            );
+            reported = Some(window);
         }
-        
-        if wait_attempt % 10 == 9 { // 20초마다 상태 출력
-            println!("⏳ PASS 인증 대기 중... ({}초 경과)", (wait_attempt + 1) * 2);
+
+        if !tracker.auth_open(page).await && is_contact_form_visible(page).await {
+            info!("✅ PASS 인증 완료 (연락처 정보 입력 단계로 진행)");
+            return Ok(());
+        }
+
+        let elapsed = attempts.elapsed().as_secs();
+        if elapsed / 20 > last_status { // 20초마다 상태 출력
+            last_status = elapsed / 20;
+            info!("⏳ PASS 인증 대기 중... ({}초 경과)", elapsed);
         }
     }
-    
-    Err("PASS 인증 시간 초과 (120초)".to_string())
+
+    Err(format!("PASS 인증 시간 초과 ({}초)", attempts.elapsed().as_secs()))
 }
 
-// 9. 이메일 입력 및 최종 예약 (정확한 셀렉터)
-pub async fn submit_final_reservation(page: &Page, email: &str) -> Result<(), String> {
-    println!("📧 이메일 입력 및 최종 예약 처리");
+// 인증 후 나타나는 연락처 정보 입력 폼 확인 (예약 페이지 안의 모달은 인증 중으로 봄)
+async fn is_contact_form_visible(page: &Page) -> bool {
+    let contact_form_js = r#"
+        (() => {
+            const modals = document.querySelectorAll('.modal, .popup, .dialog');
+            const visibleModals = Array.from(modals).filter(modal =>
+                window.getComputedStyle(modal).display !== 'none'
+            );
+
+            const contactForm = document.querySelector('input[name="email"]');
+            const contactFormVisible = contactForm && window.getComputedStyle(contactForm).display !== 'none';
+
+            return visibleModals.length === 0 && !!contactFormVisible;
+        })()
+    "#;
+
+    match page.evaluate_expression(contact_form_js.to_string()).await {
+        Ok(result) => result.into_value::<bool>().unwrap_or_else(|e| {
+            warn!("⚠️ 연락처 폼 확인 결과 파싱 실패: {}", e);
+            false
+        }),
+        Err(e) => {
+            // 인증 중 페이지 이동으로 자주 실패하므로 디버그 로그로만 남김
+            tracing::debug!("연락처 폼 확인 스크립트 실행 오류: {}", e);
+            false
+        }
+    }
+}
+
+// 9. 이메일/연락처 입력 및 최종 예약 (정확한 셀렉터)
+pub async fn submit_final_reservation(
+    page: &Page,
+    email: &Email,
+    contact_fields: &ContactFields,
+    locators: &[Locator],
+    policy: &RetryPolicy,
+) -> Result<ContactFillReport, String> {
+    info!("📧 이메일 입력 및 최종 예약 처리");
     tokio::time::sleep(Duration::from_secs(2)).await;
 
-    // 이메일 입력
-    let email_input_js = format!(r#"
-        (() => {{
-            const email = '{}';
-            const emailInput = document.querySelector('input[name="email"]');
-            
-            if (emailInput && window.getComputedStyle(emailInput).display !== 'none') {{
-                emailInput.value = email;
-                emailInput.dispatchEvent(new Event('input'));
-                emailInput.dispatchEvent(new Event('change'));
-                console.log('이메일 입력 완료:', email);
-                return true;
-            }}
-            return false;
-        }})()
-    "#, email);
-
-    match page.evaluate_expression(email_input_js).await {
-        Ok(result) => {
-            if let Ok(success) = result.into_value::<bool>() {
-                if success {
-                    println!("✅ 이메일 입력 완료: {}", email);
-                } else {
-                    println!("⚠️ 이메일 입력 실패");
-                }
+    // 이메일 및 추가 연락처 항목 입력
+    let report = match contact::fill_fields(page, email.expose(), contact_fields).await {
+        Ok(report) => {
+            if report.set.iter().any(|name| name == contact::EMAIL_FIELD) {
+                info!("✅ 이메일 입력 완료: {}", email);
+            } else {
+                warn!("⚠️ 이메일 입력 실패");
+            }
+            if !contact_fields.is_empty() {
+                info!("✅ 연락처 항목 입력: {:?}", report.set);
             }
+            if !report.missing.is_empty() {
+                warn!("⚠️ 폼에서 찾을 수 없는 항목: {:?}", report.missing);
+            }
+            report
         }
-        Err(_) => {
-            println!("⚠️ 이메일 입력 중 오류 발생");
+        Err(e) => {
+            warn!("⚠️ 이메일 입력 중 오류 발생: {}", e);
+            ContactFillReport::default()
         }
-    }
+    };
 
     tokio::time::sleep(Duration::from_secs(1)).await;
 
@@ -472,93 +390,67 @@ pub async fn submit_final_reservation(page: &Page, email: &str) -> Result<(), St
     "#;
 
     match page.evaluate_expression(checkbox_js.to_string()).await {
-        Ok(result) => {
-            if let Ok(success) = result.into_value::<bool>() {
-                if success {
-                    println!("✅ 마케팅 동의 체크박스 처리 완료");
-                } else {
-                    println!("⚠️ 마케팅 동의 체크박스 처리 실패");
-                }
-            }
-        }
-        Err(_) => {}
+        Ok(result) => match result.into_value::<bool>() {
+            Ok(true) => info!("✅ 마케팅 동의 체크박스 처리 완료"),
+            Ok(false) => warn!("⚠️ 마케팅 동의 체크박스 처리 실패"),
+            Err(e) => warn!("⚠️ 마케팅 동의 체크박스 결과 파싱 실패: {}", e),
+        },
+        Err(e) => warn!("⚠️ 마케팅 동의 체크박스 스크립트 실행 오류: {}", e),
     }
 
     tokio::time::sleep(Duration::from_secs(1)).await;
 
-    // 최종 제출 버튼 클릭
-    let submit_js = r#"
-        (() => {
-            const submitBtn = document.querySelector('button[type="submit"][name="submit_appointment"]');
-            
-            if (submitBtn && window.getComputedStyle(submitBtn).display !== 'none') {
-                console.log('최종 제출 버튼 찾음:', submitBtn.textContent.trim());
-                submitBtn.scrollIntoView({behavior: 'instant', block: 'center'});
-                submitBtn.click();
-                return true;
-            }
-            return false;
-        })()
-    "#;
-
-    match page.evaluate_expression(submit_js.to_string()).await {
-        Ok(result) => {
-            if let Ok(success) = result.into_value::<bool>() {
-                if success {
-                    println!("✅ 최종 제출 버튼 클릭 완료");
-                    tokio::time::sleep(Duration::from_secs(2)).await; // 제출 후 대기
-                    return Ok(());
-                }
+    // 최종 제출 버튼 클릭 (기본 1회 - 중복 제출 방지)
+    let mut attempts = policy.start();
+    while attempts.next().await.is_some() {
+        match attempts.timed(locators::find_and_click(page, Step::Submit, locators)).await.and_then(|found| found) {
+            Ok(Some(_)) => {
+                info!("✅ 최종 제출 버튼 클릭 완료");
+                tokio::time::sleep(Duration::from_secs(2)).await; // 제출 후 대기
+                return Ok(report);
             }
+            Ok(None) => {}
+            Err(e) => warn!("⚠️ 최종 제출 버튼 확인 중 오류: {}", e),
         }
-        Err(_) => {}
     }
     
     Err("최종 제출 버튼을 찾을 수 없습니다.".to_string())
 }
 
-// 10. 성공 페이지 확인
-pub async fn check_success_page(page: &Page) -> Result<bool, String> {
-    println!("🎉 예약 성공 여부 확인 중...");
+// 10. 성공 페이지 확인 - 예약번호를 찾지 못하면 Unverified 로 반환
+// 정책의 시도 횟수만큼 예약번호가 나타나기를 기다림 (기본 1회)
+pub async fn check_success_page(page: &Page, policy: &RetryPolicy) -> Result<ConfirmationStatus, String> {
+    info!("🎉 예약 성공 여부 확인 중...");
     tokio::time::sleep(Duration::from_secs(3)).await;
 
-    let success_check_js = r#"
-        (() => {
-            const url = window.location.href;
-            const body = document.body.textContent.toLowerCase();
-            
-            // 크로노디그마 성공 페이지 URL 패턴
-            const urlSuccess = url.includes('/success') || 
-                             url.includes('/complete') || 
-                             url.includes('/confirmation');
-            
-            // 성공 메시지 확인
-            const bodySuccess = body.includes('완료') ||
-                              body.includes('성공') ||
-                              body.includes('예약이 접수') ||
-                              body.includes('신청이 완료') ||
-                              body.includes('감사합니다');
-            
-            return urlSuccess || bodySuccess;
-        })()
-    "#;
-
-    match page.evaluate_expression(success_check_js.to_string()).await {
-        Ok(result) => {
-            if let Ok(is_success) = result.into_value::<bool>() {
-                if is_success {
-                    println!("🎉 예약 성공 확인!");
-                    return Ok(true);
-                } else {
-                    println!("❌ 예약 실패 또는 상태 불명");
-                    return Ok(false);
-                }
-            }
+    let mut attempts = policy.start();
+    let mut status = Err("예약 성공 여부를 확인하지 못했습니다.".to_string());
+    while let Some(attempt) = attempts.next().await {
+        status = attempts
+            .timed(confirmation::read_snapshot(page))
+            .await
+            .and_then(|snapshot| snapshot)
+            .map(|snapshot| confirmation::parse(&snapshot));
+        if let Ok(ConfirmationStatus::Confirmed(_)) = status {
+            break;
+        }
+        if attempt < attempts.total() {
+            info!("⏳ 예약번호 확인 재시도 {}/{}", attempt + 1, attempts.total());
         }
-        Err(_) => {}
     }
-    
-    Ok(false)
+    let status = status?;
+    match &status {
+        ConfirmationStatus::Confirmed(c) => {
+            info!(
+                "🎉 예약 성공 확인! 예약번호: {}, 날짜: {:?}, 시간: {:?}",
+                c.reference, c.date, c.time
+            );
+        }
+        ConfirmationStatus::Unverified { reason } => {
+            error!("❌ 예약 확인 불가: {}", reason);
+        }
+    }
+    Ok(status)
 }
 
 // 시간을 분으로 변환하는 유틸리티 함수
@@ -574,8 +466,7 @@ fn convert_time_to_minutes(time_str: &str) -> Result<i32, String> {
     Ok(hours * 60 + minutes)
 }
 
-// 유틸리티 함수
+// 유틸리티 함수 - 개인정보는 pii 타입의 Display 로 전달되어야 마스킹됨
 pub fn log_user_action(action: &str, details: &str) {
-    let timestamp = chrono::Utc::now().format("%H:%M:%S");
-    println!("🎯 [{}] {}: {}", timestamp, action, details);
+    tracing::info!(target: "user_action", action, "🎯 {}: {}", action, details);
 }
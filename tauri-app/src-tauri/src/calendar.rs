@@ -0,0 +1,777 @@
+// 확정된 예약을 iCalendar(.ics) 일정으로 내보내기 / 로컬 .ics 일정과의 충돌 확인
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::BufReader;
+
+use crate::confirmation::ConfirmationStatus;
+use crate::history::RunRecord;
+use crate::preferences::TimeWindow;
+
+// 매장 방문 기본 소요 시간
+const VISIT_DURATION_MINUTES: i64 = 60;
+// 방문 하루 전, 2시간 전 알림
+const REMINDERS: [&str; 2] = ["-P1D", "-PT2H"];
+
+// 매장은 모두 한국에 있으므로 Asia/Seoul (UTC+9, 서머타임 없음) 고정
+pub const TIME_ZONE_ID: &str = "Asia/Seoul";
+const VTIMEZONE: [&str; 9] = [
+    "BEGIN:VTIMEZONE",
+    "TZID:Asia/Seoul",
+    "BEGIN:STANDARD",
+    "DTSTART:19700101T000000",
+    "TZOFFSETFROM:+0900",
+    "TZOFFSETTO:+0900",
+    "TZNAME:KST",
+    "END:STANDARD",
+    "END:VTIMEZONE",
+];
+
+pub fn build_ics(record: &RunRecord) -> Result<String, String> {
+    let ConfirmationStatus::Confirmed(confirmation) = &record.confirmation else {
+        return Err("확정된 예약만 캘린더로 내보낼 수 있습니다".to_string());
+    };
+
+    // 완료 페이지에서 읽은 값이 있으면 우선 사용하고, 없으면 실행 설정값 사용
+    let date = confirmation
+        .date
+        .as_ref()
+        .or(record.visit_date.as_ref())
+        .ok_or("방문 날짜 정보가 없습니다")?;
+    let time = confirmation
+        .time
+        .as_ref()
+        .or(record.visit_time.as_ref())
+        .ok_or("방문 시간 정보가 없습니다")?;
+
+    let start = NaiveDateTime::new(
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|e| format!("잘못된 방문 날짜 ({}): {}", date, e))?,
+        NaiveTime::parse_from_str(time, "%H:%M")
+            .map_err(|e| format!("잘못된 방문 시간 ({}): {}", time, e))?,
+    );
+    let end = start + Duration::minutes(VISIT_DURATION_MINUTES);
+    let store = confirmation.store.as_deref().unwrap_or(&record.store_name);
+
+    let mut lines: Vec<String> = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//tauri-app//Rolex Automation//KO".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+    ];
+    lines.extend(VTIMEZONE.iter().map(|l| l.to_string()));
+
+    lines.extend([
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}@tauri-app", record.run_id),
+        format!("DTSTAMP:{}", Utc::now().format("%Y%m%dT%H%M%SZ")),
+        format!("DTSTART;TZID={}:{}", TIME_ZONE_ID, start.format("%Y%m%dT%H%M%S")),
+        format!("DTEND;TZID={}:{}", TIME_ZONE_ID, end.format("%Y%m%dT%H%M%S")),
+        format!("SUMMARY:{}", escape_text(&format!("{} 방문 예약", store))),
+        format!("LOCATION:{}", escape_text(store)),
+        format!(
+            "DESCRIPTION:{}",
+            escape_text(&format!("예약번호: {}\n{}", confirmation.reference, confirmation.url))
+        ),
+        "STATUS:CONFIRMED".to_string(),
+    ]);
+    if !confirmation.url.is_empty() {
+        lines.push(format!("URL:{}", confirmation.url));
+    }
+    for trigger in REMINDERS {
+        lines.extend([
+            "BEGIN:VALARM".to_string(),
+            "ACTION:DISPLAY".to_string(),
+            format!("DESCRIPTION:{}", escape_text(&format!("{} 방문 예약 알림", store))),
+            format!("TRIGGER:{}", trigger),
+            "END:VALARM".to_string(),
+        ]);
+    }
+    lines.push("END:VEVENT".to_string());
+    lines.push("END:VCALENDAR".to_string());
+
+    Ok(lines.iter().map(|l| fold_line(l)).collect::<Vec<_>>().join("\r\n") + "\r\n")
+}
+
+// RFC 5545 3.3.11 TEXT 이스케이프
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+// RFC 5545 3.1 - 75 옥텟마다 줄바꿈 후 공백으로 이어붙임 (UTF-8 문자 경계 유지)
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if width + len > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += len;
+    }
+    folded
+}
+
+// 예약 전 충돌 확인에 사용할 로컬 캘린더 설정
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CalendarCheck {
+    #[serde(rename = "icsPath")]
+    pub ics_path: String,
+    #[serde(default)]
+    pub mode: ConflictMode,
+    // 방문 전후로 비워둘 여유 시간 (분)
+    #[serde(rename = "bufferMinutes", default)]
+    pub buffer_minutes: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictMode {
+    #[default]
+    Warn,
+    Block,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CalendarConflict {
+    pub summary: String,
+    pub start: String,
+    pub end: String,
+}
+
+impl std::fmt::Display for CalendarConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' ({} ~ {})", self.summary, self.start, self.end)
+    }
+}
+
+// 선호 날짜 하나와 선호 시간대 하나의 조합에서 겹치는 일정
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreferenceConflicts {
+    pub date: String,
+    pub window: TimeWindow,
+    pub conflicts: Vec<CalendarConflict>,
+}
+
+impl PreferenceConflicts {
+    pub fn describe(&self) -> String {
+        let list: Vec<String> = self.conflicts.iter().map(|c| c.to_string()).collect();
+        format!("{} {} 방문이 다른 일정과 겹칩니다: {}", self.date, self.window, list.join(", "))
+    }
+}
+
+// 선호 날짜 × 선호 시간대의 모든 조합을 확인해 조합마다 결과를 돌려줌 (겹치는 일정이 없으면 conflicts 가 비어 있음)
+// 시간대는 시작~끝 사이 어느 시각에든 방문할 수 있으므로 시작 - 여유 시간 ~ 끝 + 소요 시간 + 여유 시간과 비교
+// 매일/매주 반복 일정(RRULE)은 회차마다 비교하고, 그 밖의 반복 규칙은 첫 회차만 비교함 (load_events 에서 경고)
+pub fn find_preference_conflicts(
+    events: &CalendarEvents,
+    check: &CalendarCheck,
+    dates: &[String],
+    windows: &[TimeWindow],
+) -> Result<Vec<PreferenceConflicts>, String> {
+    let buffer = Duration::minutes(check.buffer_minutes as i64);
+
+    let mut results = Vec::new();
+    for date in dates {
+        let day = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|e| format!("잘못된 방문 날짜 ({}): {}", date, e))?;
+        for window in windows {
+            let window_start = NaiveDateTime::new(day, window.start) - buffer;
+            let window_end =
+                NaiveDateTime::new(day, window.end) + Duration::minutes(VISIT_DURATION_MINUTES) + buffer;
+            let conflicts = events
+                .events
+                .iter()
+                .flat_map(|event| {
+                    event
+                        .occurrences_overlapping(window_start, window_end)
+                        .into_iter()
+                        .map(|(start, end)| CalendarConflict {
+                            summary: event.summary.clone(),
+                            start: start.format("%Y-%m-%d %H:%M").to_string(),
+                            end: end.format("%Y-%m-%d %H:%M").to_string(),
+                        })
+                })
+                .collect();
+            results.push(PreferenceConflicts {
+                date: date.clone(),
+                window: *window,
+                conflicts,
+            });
+        }
+    }
+    Ok(results)
+}
+
+// 한국 시간(UTC+9)으로 보는 TZID
+const SEOUL_TZIDS: [&str; 3] = [TIME_ZONE_ID, "Korea Standard Time", "ROK"];
+const UTC_TZIDS: [&str; 5] = ["UTC", "Etc/UTC", "GMT", "Etc/GMT", "Z"];
+
+// 충돌 비교용으로 읽은 일정 (시각은 모두 한국 시간)
+pub struct CalendarEvents {
+    events: Vec<CalendarEvent>,
+    // 해석하지 못해 한국 시간으로 간주한 시간대, 첫 회차만 확인한 반복 일정 등
+    pub warnings: Vec<String>,
+}
+
+struct CalendarEvent {
+    summary: String,
+    // 첫 회차
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    recurrence: Option<Recurrence>,
+}
+
+// 매일/매주 반복 규칙 (RFC 5545 RRULE 중 FREQ=DAILY|WEEKLY, INTERVAL, COUNT, UNTIL, BYDAY, WKST=MO)
+#[derive(Debug, Clone, PartialEq)]
+struct Recurrence {
+    weekly: bool,
+    interval: i64,
+    count: Option<u32>,
+    until: Option<NaiveDateTime>,
+    // 비어 있으면 매주 반복은 첫 회차의 요일, 매일 반복은 모든 요일
+    by_day: Vec<Weekday>,
+    // 제외된 회차의 시작 시각
+    exdates: Vec<NaiveDateTime>,
+}
+
+impl Recurrence {
+    fn matches(&self, day: NaiveDate, first_day: NaiveDate) -> bool {
+        let weekday_ok = if !self.by_day.is_empty() {
+            self.by_day.contains(&day.weekday())
+        } else {
+            !self.weekly || day.weekday() == first_day.weekday()
+        };
+        let period = if self.weekly {
+            (week_start(day) - week_start(first_day)).num_days() / 7
+        } else {
+            (day - first_day).num_days()
+        };
+        weekday_ok && period % self.interval == 0
+    }
+}
+
+fn week_start(day: NaiveDate) -> NaiveDate {
+    day - Duration::days(day.weekday().num_days_from_monday() as i64)
+}
+
+impl CalendarEvent {
+    // from ~ to 와 겹치는 회차의 (시작, 종료)
+    fn occurrences_overlapping(&self, from: NaiveDateTime, to: NaiveDateTime) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+        let overlaps = |start: NaiveDateTime, end: NaiveDateTime| start < to && end > from;
+        let Some(rule) = &self.recurrence else {
+            return if overlaps(self.start, self.end) {
+                vec![(self.start, self.end)]
+            } else {
+                Vec::new()
+            };
+        };
+
+        let length = self.end - self.start;
+        let first_day = self.start.date();
+        let mut found = Vec::new();
+        let mut generated = 0;
+        let mut day = first_day;
+        loop {
+            let start = NaiveDateTime::new(day, self.start.time());
+            if start >= to
+                || rule.until.is_some_and(|until| start > until)
+                || rule.count.is_some_and(|count| generated >= count)
+            {
+                break;
+            }
+            // 제외된 회차도 COUNT 에는 포함됨
+            if rule.matches(day, first_day) {
+                generated += 1;
+                if !rule.exdates.contains(&start) && overlaps(start, start + length) {
+                    found.push((start, start + length));
+                }
+            }
+            let Some(next) = day.succ_opt() else {
+                break;
+            };
+            day = next;
+        }
+        found
+    }
+}
+
+// 지원하지 않는 규칙이면 Err(규칙 문자열)
+fn parse_rrule(value: &str) -> Result<Recurrence, String> {
+    let unsupported = || value.to_string();
+    let mut recurrence = Recurrence {
+        weekly: false,
+        interval: 1,
+        count: None,
+        until: None,
+        by_day: Vec::new(),
+        exdates: Vec::new(),
+    };
+    let mut freq = None;
+    for part in value.split(';').filter(|p| !p.is_empty()) {
+        let (key, rule_value) = part.split_once('=').ok_or_else(unsupported)?;
+        match key.to_ascii_uppercase().as_str() {
+            "FREQ" => freq = Some(rule_value.to_ascii_uppercase()),
+            "INTERVAL" => {
+                recurrence.interval = rule_value.parse().ok().filter(|n| *n >= 1).ok_or_else(unsupported)?;
+            }
+            "COUNT" => recurrence.count = Some(rule_value.parse().map_err(|_| unsupported())?),
+            "UNTIL" => {
+                let (until, all_day) = parse_ical_datetime(rule_value, None).ok_or_else(unsupported)?;
+                // 날짜만 있으면 그날 하루 전체 포함
+                recurrence.until = Some(if all_day { until + Duration::days(1) - Duration::seconds(1) } else { until });
+            }
+            "BYDAY" => {
+                recurrence.by_day = rule_value
+                    .split(',')
+                    .map(|day| parse_weekday(day).ok_or_else(unsupported))
+                    .collect::<Result<_, _>>()?;
+            }
+            "WKST" if rule_value.eq_ignore_ascii_case("MO") => {}
+            _ => return Err(unsupported()),
+        }
+    }
+    match freq.as_deref() {
+        Some("DAILY") => {}
+        Some("WEEKLY") => recurrence.weekly = true,
+        _ => return Err(unsupported()),
+    }
+    Ok(recurrence)
+}
+
+// "MO" 등 요일 코드 ("1MO" 처럼 순번이 붙은 값은 월/년 단위 규칙이므로 지원하지 않음)
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value.trim().to_ascii_uppercase().as_str() {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+// 캘린더 파일을 한 번 읽어 두고 여러 매장/선호 조합에 사용
+pub fn load_events(check: &CalendarCheck) -> Result<CalendarEvents, String> {
+    let file = std::fs::File::open(&check.ics_path)
+        .map_err(|e| format!("캘린더 파일을 열 수 없습니다 ({}): {}", check.ics_path, e))?;
+
+    let mut events = Vec::new();
+    let mut unresolved = std::collections::BTreeSet::new();
+    let mut unchecked_rules = Vec::new();
+    for calendar in ical::IcalParser::new(BufReader::new(file)) {
+        let calendar = calendar.map_err(|e| format!("캘린더 파일 형식 오류: {}", e))?;
+        let zones = fixed_offsets(&calendar.timezones);
+        for event in &calendar.events {
+            if let Some(event) = parse_event(&event.properties, &zones, &mut unresolved, &mut unchecked_rules) {
+                events.push(event);
+            }
+        }
+    }
+    let mut warnings: Vec<String> = unresolved
+        .into_iter()
+        .map(|tzid| format!("캘린더 시간대 '{}' 를 해석할 수 없어 한국 시간으로 간주했습니다", tzid))
+        .collect();
+    warnings.extend(unchecked_rules.into_iter().map(|(summary, rule)| {
+        format!("반복 일정 '{}' 의 규칙({})은 확인할 수 없어 첫 회차만 비교했습니다", summary, rule)
+    }));
+    Ok(CalendarEvents { events, warnings })
+}
+
+// 캘린더에 정의된 VTIMEZONE 중 오프셋이 하나뿐인(서머타임 없는) 시간대의 UTC 오프셋
+fn fixed_offsets(timezones: &[ical::parser::ical::component::IcalTimeZone]) -> HashMap<String, Duration> {
+    let mut zones = HashMap::new();
+    for zone in timezones {
+        let Some(tzid) = zone.properties.iter().find(|p| p.name == "TZID").and_then(|p| p.value.clone()) else {
+            continue;
+        };
+        let offsets: Vec<Option<Duration>> = zone
+            .transitions
+            .iter()
+            .flat_map(|t| t.properties.iter().filter(|p| p.name == "TZOFFSETTO"))
+            .map(|p| p.value.as_deref().and_then(parse_utc_offset))
+            .collect();
+        if let Some(Some(first)) = offsets.first() {
+            if offsets.iter().all(|o| *o == Some(*first)) {
+                zones.insert(tzid, *first);
+            }
+        }
+    }
+    zones
+}
+
+// +0900, -0530, +090000
+fn parse_utc_offset(value: &str) -> Option<Duration> {
+    let (sign, digits) = match value.trim().split_at_checked(1)? {
+        ("+", digits) => (1, digits),
+        ("-", digits) => (-1, digits),
+        _ => return None,
+    };
+    if !(digits.len() == 4 || digits.len() == 6) || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i64 = digits[0..2].parse().ok()?;
+    let minutes: i64 = digits[2..4].parse().ok()?;
+    Some(Duration::minutes(sign * (hours * 60 + minutes)))
+}
+
+// TZID 의 UTC 오프셋 - 모르는 시간대는 None
+fn resolve_tzid(tzid: &str, zones: &HashMap<String, Duration>) -> Option<Duration> {
+    if SEOUL_TZIDS.contains(&tzid) {
+        Some(Duration::hours(9))
+    } else if UTC_TZIDS.contains(&tzid) {
+        Some(Duration::zero())
+    } else {
+        zones.get(tzid).copied()
+    }
+}
+
+// 일정의 제목, 첫 회차 시작/종료, 반복 규칙 - 취소되었거나 '한가함'으로 표시된 일정은 제외
+// 펼칠 수 없는 반복 규칙은 (제목, 규칙) 을 unchecked_rules 에 남기고 첫 회차만 사용
+fn parse_event(
+    properties: &[ical::property::Property],
+    zones: &HashMap<String, Duration>,
+    unresolved: &mut std::collections::BTreeSet<String>,
+    unchecked_rules: &mut Vec<(String, String)>,
+) -> Option<CalendarEvent> {
+    let find = |name: &str| properties.iter().find(|p| p.name == name);
+    let value = |name: &str| find(name).and_then(|p| p.value.clone());
+
+    if value("STATUS").as_deref() == Some("CANCELLED") || value("TRANSP").as_deref() == Some("TRANSPARENT") {
+        return None;
+    }
+
+    let mut parse_value = |property: &ical::property::Property, value: &str| -> Option<(NaiveDateTime, bool)> {
+        let tzid = property
+            .params
+            .iter()
+            .flatten()
+            .find(|(param, _)| param == "TZID")
+            .and_then(|(_, values)| values.first())
+            .map(|tzid| tzid.trim_matches('"').to_string());
+        let offset = match tzid {
+            Some(tzid) => {
+                let offset = resolve_tzid(&tzid, zones);
+                if offset.is_none() {
+                    unresolved.insert(tzid);
+                }
+                offset
+            }
+            None => None,
+        };
+        parse_ical_datetime(value, offset)
+    };
+    let mut datetime = |name: &str| -> Option<(NaiveDateTime, bool)> {
+        let property = find(name)?;
+        parse_value(property, property.value.as_deref()?)
+    };
+
+    let (start, all_day) = datetime("DTSTART")?;
+    let end = match (find("DTEND").is_some(), value("DURATION")) {
+        (true, _) => datetime("DTEND")?.0,
+        (false, Some(duration)) => start + parse_ical_duration(&duration)?,
+        (false, None) if all_day => start + Duration::days(1),
+        (false, None) => start,
+    };
+    let summary = value("SUMMARY").unwrap_or_else(|| "(제목 없음)".to_string());
+
+    let recurrence = match value("RRULE") {
+        Some(rule) => match parse_rrule(&rule) {
+            Ok(mut recurrence) => {
+                for property in properties.iter().filter(|p| p.name == "EXDATE") {
+                    for exdate in property.value.as_deref().unwrap_or("").split(',') {
+                        if let Some((exdate, _)) = parse_value(property, exdate.trim()) {
+                            recurrence.exdates.push(exdate);
+                        }
+                    }
+                }
+                Some(recurrence)
+            }
+            Err(rule) => {
+                unchecked_rules.push((summary.clone(), rule));
+                None
+            }
+        },
+        None => None,
+    };
+
+    Some(CalendarEvent {
+        summary,
+        start,
+        end,
+        recurrence,
+    })
+}
+
+// UTC(Z) 값과 오프셋을 아는 TZID 값은 한국 시간으로 바꾸고, 그 밖의 값(시간대 없음, 모르는 TZID)은 벽시계 시간 그대로 사용
+fn parse_ical_datetime(value: &str, utc_offset: Option<Duration>) -> Option<(NaiveDateTime, bool)> {
+    let seoul = Duration::hours(9);
+    if let Some(utc) = value.strip_suffix('Z') {
+        let dt = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some((dt + seoul, false));
+    }
+    if let Ok(dt) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S") {
+        return Some((utc_offset.map(|offset| dt - offset + seoul).unwrap_or(dt), false));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+    Some((date.and_hms_opt(0, 0, 0)?, true))
+}
+
+// RFC 5545 DURATION (예: PT1H30M, P1D, P2W)
+fn parse_ical_duration(value: &str) -> Option<Duration> {
+    let value = value.trim_start_matches('+').strip_prefix('P')?;
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in value.chars() {
+        match c {
+            'T' => in_time = true,
+            '0'..='9' => number.push(c),
+            unit => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                total += match (unit, in_time) {
+                    ('W', false) => Duration::weeks(n),
+                    ('D', false) => Duration::days(n),
+                    ('H', true) => Duration::hours(n),
+                    ('M', true) => Duration::minutes(n),
+                    ('S', true) => Duration::seconds(n),
+                    _ => return None,
+                };
+            }
+        }
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::confirmation::Confirmation;
+
+    // 75 옥텟을 넘겨 SUMMARY/LOCATION/DESCRIPTION 이 접히도록 긴 매장 이름 사용
+    const STORE: &str = "롯데백화점본점에비뉴엘롤렉스부티크청담플래그십스토어";
+
+    fn confirmed_record() -> RunRecord {
+        RunRecord {
+            run_id: "20250601-093000-0001".to_string(),
+            store_name: "설정 매장".to_string(),
+            visit_date: Some("2025-06-01".to_string()),
+            visit_time: Some("10:00".to_string()),
+            success: true,
+            message: "예약 완료".to_string(),
+            timestamp: "2025-06-01 09:30:00".to_string(),
+            confirmation: ConfirmationStatus::Confirmed(Confirmation {
+                reference: "R-12345".to_string(),
+                store: Some(STORE.to_string()),
+                date: Some("2025-06-02".to_string()),
+                time: Some("17:30".to_string()),
+                url: "https://www.rolex.com/ko/appointment/complete".to_string(),
+            }),
+            preflight: None,
+            slot_selection: None,
+            contact_fields: None,
+            network: None,
+            har_file: None,
+        }
+    }
+
+    fn property<'a>(properties: &'a [ical::property::Property], name: &str) -> &'a ical::property::Property {
+        properties.iter().find(|p| p.name == name).unwrap()
+    }
+
+    #[test]
+    fn exported_ics_parses_back() {
+        let ics = build_ics(&confirmed_record()).unwrap();
+        assert!(ics.lines().all(|line| line.trim_end_matches('\r').len() <= 75));
+        assert!(ics.contains("\r\n "), "긴 줄이 접혀야 함");
+
+        let calendars: Vec<_> = ical::IcalParser::new(BufReader::new(ics.as_bytes()))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(calendars.len(), 1);
+        let calendar = &calendars[0];
+
+        assert_eq!(calendar.timezones.len(), 1);
+        let timezone = &calendar.timezones[0];
+        assert_eq!(property(&timezone.properties, "TZID").value.as_deref(), Some(TIME_ZONE_ID));
+        assert_eq!(timezone.transitions.len(), 1);
+
+        assert_eq!(calendar.events.len(), 1);
+        let event = &calendar.events[0];
+        let dtstart = property(&event.properties, "DTSTART");
+        assert_eq!(dtstart.value.as_deref(), Some("20250602T173000"));
+        assert_eq!(
+            dtstart.params,
+            Some(vec![("TZID".to_string(), vec![TIME_ZONE_ID.to_string()])])
+        );
+        assert_eq!(
+            property(&event.properties, "DTEND").value.as_deref(),
+            Some("20250602T183000")
+        );
+        assert_eq!(
+            property(&event.properties, "SUMMARY").value,
+            Some(format!("{} 방문 예약", STORE))
+        );
+        assert_eq!(property(&event.properties, "LOCATION").value.as_deref(), Some(STORE));
+
+        let triggers: Vec<_> = event
+            .alarms
+            .iter()
+            .map(|alarm| property(&alarm.properties, "TRIGGER").value.clone().unwrap())
+            .collect();
+        assert_eq!(triggers, REMINDERS);
+    }
+
+    #[test]
+    fn every_preferred_date_and_window_is_checked() {
+        let path = std::env::temp_dir().join(format!("calendar-conflicts-{}.ics", std::process::id()));
+        std::fs::write(
+            &path,
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\n\
+             BEGIN:VEVENT\r\nSUMMARY:회의\r\nDTSTART:20250603T140000\r\nDTEND:20250603T150000\r\nEND:VEVENT\r\n\
+             BEGIN:VEVENT\r\nSUMMARY:취소된 일정\r\nSTATUS:CANCELLED\r\nDTSTART:20250602T100000\r\nDTEND:20250602T110000\r\nEND:VEVENT\r\n\
+             END:VCALENDAR\r\n",
+        )
+        .unwrap();
+        let check = CalendarCheck {
+            ics_path: path.display().to_string(),
+            mode: ConflictMode::Block,
+            buffer_minutes: 30,
+        };
+        let dates = vec!["2025-06-02".to_string(), "2025-06-03".to_string()];
+        let windows = vec![TimeWindow::parse("10:00").unwrap(), TimeWindow::parse("12:00-13:00").unwrap()];
+
+        let events = load_events(&check).unwrap();
+        let results = find_preference_conflicts(&events, &check, &dates, &windows).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // 2 날짜 × 2 시간대 - 6월 3일 12:00-13:00 시간대만 (13:00 방문 + 60분 + 여유 30분) 회의와 겹침
+        assert_eq!(results.len(), 4);
+        let conflicted: Vec<(&str, String)> = results
+            .iter()
+            .filter(|r| !r.conflicts.is_empty())
+            .map(|r| (r.date.as_str(), r.window.to_string()))
+            .collect();
+        assert_eq!(conflicted, vec![("2025-06-03", "12:00-13:00".to_string())]);
+    }
+
+    #[test]
+    fn event_time_zones_are_resolved_to_seoul_time() {
+        let path = std::env::temp_dir().join(format!("calendar-timezones-{}.ics", std::process::id()));
+        std::fs::write(
+            &path,
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\n\
+             BEGIN:VTIMEZONE\r\nTZID:Custom/Plus1\r\n\
+             BEGIN:STANDARD\r\nDTSTART:19700101T000000\r\nTZOFFSETFROM:+0100\r\nTZOFFSETTO:+0100\r\nEND:STANDARD\r\n\
+             END:VTIMEZONE\r\n\
+             BEGIN:VEVENT\r\nSUMMARY:유럽\r\nDTSTART;TZID=Custom/Plus1:20250603T060000\r\nDTEND;TZID=Custom/Plus1:20250603T070000\r\nEND:VEVENT\r\n\
+             BEGIN:VEVENT\r\nSUMMARY:UTC\r\nDTSTART;TZID=UTC:20250603T010000\r\nDTEND;TZID=UTC:20250603T020000\r\nEND:VEVENT\r\n\
+             BEGIN:VEVENT\r\nSUMMARY:모름\r\nDTSTART;TZID=America/New_York:20250603T160000\r\nDTEND;TZID=America/New_York:20250603T170000\r\nEND:VEVENT\r\n\
+             END:VCALENDAR\r\n",
+        )
+        .unwrap();
+        let check = CalendarCheck {
+            ics_path: path.display().to_string(),
+            mode: ConflictMode::Warn,
+            buffer_minutes: 0,
+        };
+
+        let events = load_events(&check).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let starts: Vec<(&str, String)> = events
+            .events
+            .iter()
+            .map(|event| (event.summary.as_str(), event.start.format("%H:%M").to_string()))
+            .collect();
+        // 오프셋을 아는 시간대는 한국 시간으로 바꾸고, 모르는 시간대는 벽시계 시간 그대로 두고 경고
+        assert_eq!(
+            starts,
+            vec![("유럽", "14:00".to_string()), ("UTC", "10:00".to_string()), ("모름", "16:00".to_string())]
+        );
+        assert_eq!(events.warnings.len(), 1);
+        assert!(events.warnings[0].contains("America/New_York"));
+    }
+
+    #[test]
+    fn daily_and_weekly_recurrences_are_expanded() {
+        let path = std::env::temp_dir().join(format!("calendar-recurrence-{}.ics", std::process::id()));
+        // 2025-06-02 는 월요일
+        std::fs::write(
+            &path,
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\n\
+             BEGIN:VEVENT\r\nSUMMARY:주간 회의\r\nDTSTART:20250602T100000\r\nDTEND:20250602T110000\r\n\
+             RRULE:FREQ=WEEKLY;BYDAY=MO,WE;COUNT=5\r\nEXDATE:20250609T100000\r\nEND:VEVENT\r\n\
+             BEGIN:VEVENT\r\nSUMMARY:격일 운동\r\nDTSTART:20250601T140000\r\nDTEND:20250601T150000\r\n\
+             RRULE:FREQ=DAILY;INTERVAL=2;UNTIL=20250610T000000Z\r\nEND:VEVENT\r\n\
+             BEGIN:VEVENT\r\nSUMMARY:월례 모임\r\nDTSTART:20250601T180000\r\nDTEND:20250601T190000\r\n\
+             RRULE:FREQ=MONTHLY;BYDAY=1SU\r\nEND:VEVENT\r\n\
+             END:VCALENDAR\r\n",
+        )
+        .unwrap();
+        let check = CalendarCheck {
+            ics_path: path.display().to_string(),
+            mode: ConflictMode::Warn,
+            buffer_minutes: 0,
+        };
+        let events = load_events(&check).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(events.warnings.len(), 1);
+        assert!(events.warnings[0].contains("월례 모임"), "{}", events.warnings[0]);
+
+        let conflicts = |date: &str, window: &str| -> Vec<String> {
+            let results = find_preference_conflicts(
+                &events,
+                &check,
+                &[date.to_string()],
+                &[TimeWindow::parse(window).unwrap()],
+            )
+            .unwrap();
+            results[0].conflicts.iter().map(|c| c.to_string()).collect()
+        };
+
+        // 주간 회의: 6/2(월), 6/4(수), 6/9(월, 제외), 6/11(수), 6/16(월) 까지 5회
+        assert_eq!(conflicts("2025-06-04", "10:30"), ["'주간 회의' (2025-06-04 10:00 ~ 2025-06-04 11:00)"]);
+        assert!(conflicts("2025-06-09", "10:30").is_empty());
+        assert_eq!(conflicts("2025-06-16", "10:30").len(), 1);
+        assert!(conflicts("2025-06-18", "10:30").is_empty());
+        assert!(conflicts("2025-06-05", "10:30").is_empty());
+
+        // 격일 운동: 6/1, 6/3, 6/5, 6/7, 6/9 (UNTIL 은 한국 시간 6/10 09:00)
+        assert_eq!(conflicts("2025-06-07", "14:00").len(), 1);
+        assert!(conflicts("2025-06-08", "14:00").is_empty());
+        assert!(conflicts("2025-06-11", "14:00").is_empty());
+
+        // 지원하지 않는 규칙은 첫 회차만 비교
+        assert_eq!(conflicts("2025-06-01", "18:00").len(), 1);
+        assert!(conflicts("2025-07-06", "18:00").is_empty());
+    }
+
+    #[test]
+    fn unsupported_rules_are_rejected() {
+        assert!(parse_rrule("FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH;WKST=MO").is_ok());
+        assert!(parse_rrule("FREQ=DAILY;COUNT=3").is_ok());
+        for rule in ["FREQ=MONTHLY", "FREQ=WEEKLY;BYDAY=1MO", "FREQ=DAILY;BYHOUR=9", "FREQ=DAILY;INTERVAL=0", "COUNT=3"] {
+            assert_eq!(parse_rrule(rule), Err(rule.to_string()));
+        }
+    }
+
+    #[test]
+    fn unconfirmed_record_is_not_exported() {
+        let mut record = confirmed_record();
+        record.confirmation = ConfirmationStatus::unverified("예약번호 없음");
+        assert!(build_ics(&record).is_err());
+    }
+}
@@ -0,0 +1,639 @@
+// 자동화 설정 파일 (automation_config_example.json 형식) 로더
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::calendar::{self, CalendarCheck, ConflictMode};
+use crate::contact::{self, ContactFields};
+use crate::har::HarConfig;
+use crate::locators::{self, LocatorOverrides};
+use crate::pii::{Carrier, Email};
+use crate::network::{self, NetworkMonitorConfig};
+use crate::pacing::{self, PacingConfig};
+use crate::preferences::{self, TimeWindow};
+use crate::recorder::{self, FlowDefinition};
+use crate::retry::{self, RetryPolicies};
+use crate::sinks::{self, SinkConfig};
+use crate::StoreConfig;
+
+// 현재 지원하는 설정 스키마 버전 (메이저 버전이 같으면 호환)
+pub const SCHEMA_VERSION: &str = "2.0.0";
+
+// 앱 데이터 디렉토리에 저장되는 설정 파일 이름
+pub const CONFIG_FILE_NAME: &str = "automation_config.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConfigFile {
+    pub automation_settings: AutomationSettings,
+    pub user_config: UserConfig,
+    #[serde(default)]
+    pub stores: Vec<StoreEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calendar_check: Option<CalendarCheck>,
+    // 이 설정(프로필)으로 실행되는 모든 매장의 이벤트 전달 대상
+    #[serde(rename = "notificationSinks", default, skip_serializing_if = "Vec::is_empty")]
+    pub notification_sinks: Vec<SinkConfig>,
+    // 단계별 대체 로케이터 (예약 사이트 공통, 없는 단계는 기본 목록 사용)
+    #[serde(default, skip_serializing_if = "LocatorOverrides::is_empty")]
+    pub locators: LocatorOverrides,
+    // 단계별 재시도 정책 (없는 단계는 기존 동작과 같은 기본 정책)
+    #[serde(rename = "retryPolicies", default, skip_serializing_if = "RetryPolicies::is_empty")]
+    pub retry_policies: RetryPolicies,
+    // 페이지 이동 간격 및 서버 오류(429/5xx) backoff (없으면 기본값)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pacing: Option<PacingConfig>,
+    // 페이지 로딩 지연/실패 감지 및 새로고침 (없으면 기본값)
+    #[serde(rename = "networkMonitor", default, skip_serializing_if = "Option::is_none")]
+    pub network_monitor: Option<NetworkMonitorConfig>,
+    // 설정 시 실행마다 네트워크 요청/응답을 HAR 로 저장
+    #[serde(rename = "recordHar", default, skip_serializing_if = "Option::is_none")]
+    pub record_har: Option<HarConfig>,
+    // 흐름 기록에서 가져온 동작 - 버튼/연락처 단계에서 기본 동작 대신 재생
+    #[serde(rename = "recordedFlow", default, skip_serializing_if = "Option::is_none")]
+    pub recorded_flow: Option<FlowDefinition>,
+    // 앱이 읽지 않는 섹션 (timing_strategy, error_handling 등) - 저장할 때 그대로 다시 씀
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AutomationSettings {
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<String>,
+}
+
+// secretId 가 있으면 email/carrier 는 암호화 저장소에서 읽으므로 비워둘 수 있음
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UserConfig {
+    #[serde(default)]
+    pub email: Email,
+    #[serde(default)]
+    pub carrier: Carrier,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    // 이메일 외 연락처 폼 입력란 (name 속성 -> 값), message 가 있으면 "message" 항목으로 입력
+    #[serde(rename = "contactFields", default, skip_serializing_if = "ContactFields::is_empty")]
+    pub contact_fields: ContactFields,
+    #[serde(rename = "secretId", default, skip_serializing_if = "Option::is_none")]
+    pub secret_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StoreEntry {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "authUrl")]
+    pub auth_url: String,
+    #[serde(rename = "reserveUrl")]
+    pub reserve_url: String,
+    #[serde(default)]
+    pub automation_config: StoreAutomationConfig,
+}
+
+// 매장별 설정 - email/carrier 가 있으면 user_config 값을 덮어씀
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct StoreAutomationConfig {
+    #[serde(rename = "startTime", default, skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<String>,
+    #[serde(rename = "visitDate", default, skip_serializing_if = "Option::is_none")]
+    pub visit_date: Option<String>,
+    #[serde(rename = "visitTime", default, skip_serializing_if = "Option::is_none")]
+    pub visit_time: Option<String>,
+    // 선호 순서대로의 날짜/시간(대) 목록 - 있으면 visitDate/visitTime 대신 사용
+    #[serde(rename = "visitDates", default, skip_serializing_if = "Vec::is_empty")]
+    pub visit_dates: Vec<String>,
+    #[serde(rename = "visitTimes", default, skip_serializing_if = "Vec::is_empty")]
+    pub visit_times: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<Email>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub carrier: Option<Carrier>,
+    #[serde(rename = "userActionTimeoutSecs", default, skip_serializing_if = "Option::is_none")]
+    pub user_action_timeout_secs: Option<u64>,
+    #[serde(rename = "chooseSlot", default, skip_serializing_if = "Option::is_none")]
+    pub choose_slot: Option<bool>,
+    // 같은 항목이 있으면 user_config.contactFields 값을 덮어씀
+    #[serde(rename = "contactFields", default, skip_serializing_if = "ContactFields::is_empty")]
+    pub contact_fields: ContactFields,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+// 스키마 오류 한 건 (path 예: "stores[1].automation_config.visitDate")
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SchemaError {
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+// 설정 검증 결과 - 스키마 오류와 일정 충돌 경고(block 모드면 오류)를 함께 보고
+#[derive(Serialize, Debug, Default)]
+pub struct ValidationReport {
+    pub errors: Vec<SchemaError>,
+    pub warnings: Vec<SchemaError>,
+}
+
+// 프론트엔드로 돌려주는 로드 결과 (원본 설정 + 병합된 매장별 StoreConfig)
+#[derive(Serialize, Debug)]
+pub struct LoadedConfig {
+    pub config: ConfigFile,
+    pub store_configs: Vec<StoreConfig>,
+}
+
+impl ConfigFile {
+    pub fn validate(&self) -> Vec<SchemaError> {
+        let mut errors = Vec::new();
+        let mut push = |path: String, message: String| errors.push(SchemaError { path, message });
+
+        match self.automation_settings.version.split('.').next().map(str::parse::<u32>) {
+            Some(Ok(major)) if Some(major) == schema_major() => {}
+            _ => push(
+                "automation_settings.version".to_string(),
+                format!(
+                    "지원하지 않는 스키마 버전입니다: {} (지원: {})",
+                    self.automation_settings.version, SCHEMA_VERSION
+                ),
+            ),
+        }
+
+        match &self.user_config.secret_id {
+            Some(secret_id) if secret_id.trim().is_empty() => {
+                push("user_config.secretId".to_string(), "secretId가 비어 있습니다".to_string());
+            }
+            Some(_) => {
+                if !self.user_config.email.is_empty() {
+                    if let Some(msg) = check_email(&self.user_config.email) {
+                        push("user_config.email".to_string(), msg);
+                    }
+                }
+            }
+            None => {
+                if let Some(msg) = check_email(&self.user_config.email) {
+                    push("user_config.email".to_string(), msg);
+                }
+                if self.user_config.carrier.is_empty() {
+                    push("user_config.carrier".to_string(), "통신사가 비어 있습니다".to_string());
+                }
+            }
+        }
+
+        if let Some(message) = &self.user_config.message {
+            if let Some(msg) = contact::validate_field("message", message) {
+                push("user_config.message".to_string(), msg);
+            }
+        }
+        for (field, message) in contact::validate(&self.user_config.contact_fields) {
+            push(format!("user_config.contactFields.{}", field), message);
+        }
+
+        if self.stores.is_empty() {
+            push("stores".to_string(), "매장이 하나 이상 필요합니다".to_string());
+        }
+
+        for (i, sink) in self.notification_sinks.iter().enumerate() {
+            for (field, message) in sinks::validate(sink) {
+                push(format!("notificationSinks[{}].{}", i, field), message);
+            }
+        }
+
+        for (field, message) in locators::validate(&self.locators) {
+            push(format!("locators.{}", field), message);
+        }
+
+        for (field, message) in retry::validate(&self.retry_policies) {
+            push(format!("retryPolicies.{}", field), message);
+        }
+
+        if let Some(pacing) = &self.pacing {
+            for (field, message) in pacing::validate(pacing) {
+                push(format!("pacing.{}", field), message);
+            }
+        }
+
+        if let Some(monitor) = &self.network_monitor {
+            for (field, message) in network::validate(monitor) {
+                push(format!("networkMonitor.{}", field), message);
+            }
+        }
+
+        if let Some(flow) = &self.recorded_flow {
+            if flow.version != recorder::FLOW_VERSION {
+                push(
+                    "recordedFlow.version".to_string(),
+                    format!("지원하지 않는 흐름 버전입니다: {} (지원: {})", flow.version, recorder::FLOW_VERSION),
+                );
+            }
+        }
+
+        let mut seen_ids = HashSet::new();
+        for (i, store) in self.stores.iter().enumerate() {
+            let base = format!("stores[{}]", i);
+
+            if store.id.trim().is_empty() {
+                push(format!("{}.id", base), "id가 비어 있습니다".to_string());
+            } else if !seen_ids.insert(store.id.as_str()) {
+                push(format!("{}.id", base), format!("중복된 매장 id: {}", store.id));
+            }
+            if store.name.trim().is_empty() {
+                push(format!("{}.name", base), "매장 이름이 비어 있습니다".to_string());
+            }
+            if url::Url::parse(&store.auth_url).is_err() {
+                push(format!("{}.authUrl", base), format!("잘못된 URL: {}", store.auth_url));
+            }
+            if url::Url::parse(&store.reserve_url).is_err() {
+                push(format!("{}.reserveUrl", base), format!("잘못된 URL: {}", store.reserve_url));
+            }
+
+            let cfg = &store.automation_config;
+            let cfg_base = format!("{}.automation_config", base);
+            if let Some(start_time) = &cfg.start_time {
+                if !is_valid_start_time(start_time) {
+                    push(
+                        format!("{}.startTime", cfg_base),
+                        format!("잘못된 시작 시간 형식: {} (HH:mm 또는 YYYY-MM-DD HH:mm[:ss])", start_time),
+                    );
+                }
+            }
+            if let Some(visit_date) = &cfg.visit_date {
+                if NaiveDate::parse_from_str(visit_date, "%Y-%m-%d").is_err() {
+                    push(
+                        format!("{}.visitDate", cfg_base),
+                        format!("잘못된 날짜 형식: {} (YYYY-MM-DD)", visit_date),
+                    );
+                }
+            }
+            if let Some(visit_time) = &cfg.visit_time {
+                if NaiveTime::parse_from_str(visit_time, "%H:%M").is_err() {
+                    push(
+                        format!("{}.visitTime", cfg_base),
+                        format!("잘못된 시간 형식: {} (HH:mm)", visit_time),
+                    );
+                }
+            }
+            for (j, message) in preferences::validate_dates(&cfg.visit_dates) {
+                push(format!("{}.visitDates[{}]", cfg_base, j), message);
+            }
+            for (j, window) in cfg.visit_times.iter().enumerate() {
+                if let Err(message) = TimeWindow::parse(window) {
+                    push(format!("{}.visitTimes[{}]", cfg_base, j), message);
+                }
+            }
+            for (field, message) in contact::validate(&cfg.contact_fields) {
+                push(format!("{}.contactFields.{}", cfg_base, field), message);
+            }
+            if cfg.user_action_timeout_secs == Some(0) {
+                push(
+                    format!("{}.userActionTimeoutSecs", cfg_base),
+                    "사용자 개입 대기 시간은 1초 이상이어야 합니다".to_string(),
+                );
+            }
+            if let Some(email) = &cfg.email {
+                if let Some(msg) = check_email(email) {
+                    push(format!("{}.email", cfg_base), msg);
+                }
+            }
+            if let Some(carrier) = &cfg.carrier {
+                if carrier.is_empty() {
+                    push(format!("{}.carrier", cfg_base), "통신사가 비어 있습니다".to_string());
+                }
+            }
+        }
+
+        errors
+    }
+
+    pub fn validation_report(&self) -> ValidationReport {
+        let mut report = ValidationReport {
+            errors: self.validate(),
+            warnings: Vec::new(),
+        };
+        if let Some(check) = &self.calendar_check {
+            self.check_calendar_conflicts(check, &mut report);
+        }
+        report
+    }
+
+    // 매장마다 선호 날짜 × 시간대 조합을 모두 확인 - 겹치는 조합은 경고,
+    // 모든 조합이 겹치면 block 모드에서 오류 (사전 점검과 같은 기준)
+    fn check_calendar_conflicts(&self, check: &CalendarCheck, report: &mut ValidationReport) {
+        // 캘린더 파일은 한 번만 읽고, 읽기 오류/시간대 경고도 한 번만 남김
+        let events = match calendar::load_events(check) {
+            Ok(events) => events,
+            Err(e) => {
+                report.warnings.push(SchemaError {
+                    path: "calendar_check.icsPath".to_string(),
+                    message: e,
+                });
+                return;
+            }
+        };
+        report.warnings.extend(events.warnings.iter().map(|warning| SchemaError {
+            path: "calendar_check.icsPath".to_string(),
+            message: warning.clone(),
+        }));
+
+        for (i, store) in self.to_store_configs().iter().enumerate() {
+            let cfg = &self.stores[i].automation_config;
+            let dates = store.date_preferences();
+            let windows = store.time_preferences();
+            if dates.is_empty() || windows.is_empty() {
+                continue;
+            }
+            let field = if cfg.visit_times.is_empty() { "visitTime" } else { "visitTimes" };
+            let path = format!("stores[{}].automation_config.{}", i, field);
+            match calendar::find_preference_conflicts(&events, check, &dates, &windows) {
+                Ok(results) => {
+                    let conflicted: Vec<String> = results
+                        .iter()
+                        .filter(|r| !r.conflicts.is_empty())
+                        .map(|r| r.describe())
+                        .collect();
+                    if conflicted.is_empty() {
+                        continue;
+                    }
+                    let all_conflict = conflicted.len() == results.len();
+                    let error = SchemaError {
+                        path,
+                        message: conflicted.join("; "),
+                    };
+                    match check.mode {
+                        ConflictMode::Block if all_conflict => report.errors.push(error),
+                        _ => report.warnings.push(error),
+                    }
+                }
+                Err(e) => report.warnings.push(SchemaError {
+                    path: "calendar_check.icsPath".to_string(),
+                    message: e,
+                }),
+            }
+        }
+    }
+
+    // user_config.message < user_config.contactFields < 매장별 contactFields 순으로 덮어씀
+    fn contact_fields(&self, cfg: &StoreAutomationConfig) -> ContactFields {
+        let mut fields = ContactFields::new();
+        if let Some(message) = &self.user_config.message {
+            fields.insert("message".to_string(), message.clone());
+        }
+        fields.extend(self.user_config.contact_fields.clone());
+        fields.extend(cfg.contact_fields.clone());
+        fields
+    }
+
+    // user_config 와 매장별 automation_config 를 병합해 실행용 StoreConfig 목록을 만듦
+    pub fn to_store_configs(&self) -> Vec<StoreConfig> {
+        self.store_configs_on(Local::now().date_naive())
+    }
+
+    // today: HH:mm 만 적힌 시작 시간에 붙일 날짜
+    fn store_configs_on(&self, today: NaiveDate) -> Vec<StoreConfig> {
+        self.stores
+            .iter()
+            .map(|store| {
+                let cfg = &store.automation_config;
+                StoreConfig {
+                    store_name: store.name.clone(),
+                    auth_url: store.auth_url.clone(),
+                    reserve_url: store.reserve_url.clone(),
+                    start_time: cfg.start_time.as_deref().map(|t| normalize_start_time(t, today)),
+                    visit_date: cfg.visit_date.clone(),
+                    visit_time: cfg.visit_time.clone(),
+                    visit_dates: cfg.visit_dates.clone(),
+                    visit_times: cfg
+                        .visit_times
+                        .iter()
+                        .filter_map(|w| TimeWindow::parse(w).ok())
+                        .collect(),
+                    carrier: cfg.carrier.clone().unwrap_or_else(|| self.user_config.carrier.clone()),
+                    email: cfg.email.clone().unwrap_or_else(|| self.user_config.email.clone()),
+                    client_time: None,
+                    secret_id: self.user_config.secret_id.clone(),
+                    calendar_check: self.calendar_check.clone(),
+                    notification_sinks: self.notification_sinks.clone(),
+                    user_action_timeout_secs: cfg.user_action_timeout_secs,
+                    choose_slot: cfg.choose_slot.unwrap_or(false),
+                    contact_fields: self.contact_fields(cfg),
+                    locators: self.locators.clone(),
+                    retry_policies: self.retry_policies.clone(),
+                    pacing: self.pacing.clone().unwrap_or_default(),
+                    network_monitor: self.network_monitor.clone().unwrap_or_default(),
+                    record_har: self.record_har.clone(),
+                    recorded_flow: self.recorded_flow.clone(),
+                }
+            })
+            .collect()
+    }
+}
+
+fn schema_major() -> Option<u32> {
+    SCHEMA_VERSION.split('.').next().and_then(|v| v.parse().ok())
+}
+
+fn check_email(email: &Email) -> Option<String> {
+    if email.is_empty() {
+        return Some("이메일이 비어 있습니다".to_string());
+    }
+    match email.expose().trim().split_once('@') {
+        Some((local, domain)) if !local.is_empty() && domain.contains('.') => None,
+        _ => Some(format!("잘못된 이메일 형식: {}", email)),
+    }
+}
+
+// run_rolex_automation 이 받아들이는 시작 시간 형식과 동일하게 검사 (HH:mm 단독 표기도 허용)
+fn is_valid_start_time(value: &str) -> bool {
+    let datetime_formats = ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"];
+    datetime_formats
+        .iter()
+        .any(|fmt| NaiveDateTime::parse_from_str(value, fmt).is_ok())
+        || NaiveTime::parse_from_str(value, "%H:%M").is_ok()
+        || NaiveTime::parse_from_str(value, "%H:%M:%S").is_ok()
+}
+
+// 실행 시에는 날짜가 포함된 시작 시간만 파싱하므로 HH:mm[:ss] 는 today 의 해당 시각으로 바꿈
+fn normalize_start_time(value: &str, today: NaiveDate) -> String {
+    ["%H:%M", "%H:%M:%S"]
+        .iter()
+        .find_map(|fmt| NaiveTime::parse_from_str(value, fmt).ok())
+        .map(|time| today.and_time(time).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| value.to_string())
+}
+
+fn format_schema_errors(errors: &[SchemaError]) -> String {
+    let lines: Vec<String> = errors.iter().map(|e| format!("- {}", e)).collect();
+    format!("설정 파일 스키마 오류 {}건:\n{}", errors.len(), lines.join("\n"))
+}
+
+// JSON 문자열을 파싱하고 스키마를 검사
+pub fn parse_config(json: &str) -> Result<ConfigFile, String> {
+    let config: ConfigFile = serde_json::from_str(json).map_err(|e| {
+        format_schema_errors(&[SchemaError {
+            path: format!("{}:{}", e.line(), e.column()),
+            message: e.to_string(),
+        }])
+    })?;
+
+    let errors = config.validate();
+    if !errors.is_empty() {
+        return Err(format_schema_errors(&errors));
+    }
+    Ok(config)
+}
+
+pub fn load_config_file(path: &Path) -> Result<ConfigFile, String> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| format!("설정 파일을 읽을 수 없습니다 ({}): {}", path.display(), e))?;
+    parse_config(&json)
+}
+
+pub fn save_config_file(path: &Path, config: &ConfigFile) -> Result<(), String> {
+    let errors = config.validate();
+    if !errors.is_empty() {
+        return Err(format_schema_errors(&errors));
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("설정 디렉토리를 만들 수 없습니다 ({}): {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+
+    // 임시 파일에 쓴 뒤 교체하여 저장 중 중단되어도 기존 파일이 깨지지 않도록 함
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, json)
+        .map_err(|e| format!("설정 파일을 저장할 수 없습니다 ({}): {}", tmp_path.display(), e))?;
+    std::fs::rename(&tmp_path, path)
+        .map_err(|e| format!("설정 파일을 저장할 수 없습니다 ({}): {}", path.display(), e))
+}
+
+pub fn config_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join(CONFIG_FILE_NAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"{
+        "automation_settings": { "version": "2.0.0" },
+        "user_config": { "email": "user@example.com", "carrier": "SKT" },
+        "stores": [{
+            "id": "store_1",
+            "name": "롯데",
+            "authUrl": "https://example.com/auth",
+            "reserveUrl": "https://example.com/reserve"
+        }],
+        "timing_strategy": { "recommended_times": ["09:00", "14:00"] },
+        "error_handling": { "retry_strategy": "3회 재시도" }
+    }"#;
+
+    #[test]
+    fn unknown_sections_survive_save_and_load() {
+        let path = std::env::temp_dir().join(format!("config-roundtrip-{}.json", std::process::id()));
+        let config = parse_config(CONFIG).unwrap();
+        save_config_file(&path, &config).unwrap();
+        let saved: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let reloaded = load_config_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let original: serde_json::Value = serde_json::from_str(CONFIG).unwrap();
+        assert_eq!(saved["timing_strategy"], original["timing_strategy"]);
+        assert_eq!(saved["error_handling"], original["error_handling"]);
+        assert_eq!(reloaded.extra.len(), 2);
+    }
+
+    fn config_with(stores: serde_json::Value) -> ConfigFile {
+        let mut value: serde_json::Value = serde_json::from_str(CONFIG).unwrap();
+        value["user_config"]["message"] = serde_json::json!("기본 메시지");
+        value["user_config"]["contactFields"] = serde_json::json!({ "name": "홍길동", "phone": "010-1234-5678" });
+        value["stores"] = stores;
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn start_time_without_date_is_scheduled_for_today() {
+        let config = config_with(serde_json::json!([
+            { "id": "a", "name": "A", "authUrl": "https://example.com/a", "reserveUrl": "https://example.com/a",
+              "automation_config": { "startTime": "10:00" } },
+            { "id": "b", "name": "B", "authUrl": "https://example.com/b", "reserveUrl": "https://example.com/b",
+              "automation_config": { "startTime": "2025-06-01 09:30" } }
+        ]));
+        let today = NaiveDate::from_ymd_opt(2025, 5, 20).unwrap();
+
+        let starts: Vec<Option<String>> = config.store_configs_on(today).into_iter().map(|c| c.start_time).collect();
+
+        assert_eq!(
+            starts,
+            vec![Some("2025-05-20 10:00:00".to_string()), Some("2025-06-01 09:30".to_string())]
+        );
+    }
+
+    #[test]
+    fn store_settings_override_user_config() {
+        let config = config_with(serde_json::json!([
+            { "id": "a", "name": "A", "authUrl": "https://example.com/a", "reserveUrl": "https://example.com/a" },
+            { "id": "b", "name": "B", "authUrl": "https://example.com/b", "reserveUrl": "https://example.com/b",
+              "automation_config": {
+                  "email": "store@example.com",
+                  "carrier": "KT",
+                  "chooseSlot": true,
+                  "contactFields": { "phone": "010-9999-0000", "message": "매장 메시지" }
+              } }
+        ]));
+
+        let stores = config.to_store_configs();
+
+        assert_eq!(stores[0].email.expose(), "user@example.com");
+        assert_eq!(stores[0].carrier.expose(), "SKT");
+        assert!(!stores[0].choose_slot);
+        assert_eq!(stores[0].contact_fields["message"], "기본 메시지");
+        assert_eq!(stores[0].contact_fields["phone"], "010-1234-5678");
+
+        assert_eq!(stores[1].email.expose(), "store@example.com");
+        assert_eq!(stores[1].carrier.expose(), "KT");
+        assert!(stores[1].choose_slot);
+        assert_eq!(stores[1].contact_fields["message"], "매장 메시지");
+        assert_eq!(stores[1].contact_fields["phone"], "010-9999-0000");
+        assert_eq!(stores[1].contact_fields["name"], "홍길동");
+    }
+
+    #[test]
+    fn schema_errors_name_each_invalid_field() {
+        let config = config_with(serde_json::json!([
+            { "id": "a", "name": "A", "authUrl": "not a url", "reserveUrl": "https://example.com/a",
+              "automation_config": { "startTime": "오전 10시", "visitDate": "2025/06/01", "visitTime": "25:00" } },
+            { "id": "a", "name": " ", "authUrl": "https://example.com/b", "reserveUrl": "https://example.com/b" }
+        ]));
+
+        let paths: Vec<String> = config.validate().into_iter().map(|e| e.path).collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                "stores[0].authUrl",
+                "stores[0].automation_config.startTime",
+                "stores[0].automation_config.visitDate",
+                "stores[0].automation_config.visitTime",
+                "stores[1].id",
+                "stores[1].name",
+            ]
+        );
+    }
+
+    #[test]
+    fn unparsable_json_reports_line_and_column() {
+        let error = parse_config("{\n  \"stores\": [,]\n}").unwrap_err();
+        assert!(error.starts_with("설정 파일 스키마 오류 1건:\n- 2:"), "{}", error);
+    }
+}
@@ -0,0 +1,270 @@
+// 예약 완료 페이지에서 예약번호/매장/날짜/시간을 추출
+use chromiumoxide::Page;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+// 완료 안내가 표시되는 영역 - 없으면 #appointment, 그래도 없으면 main 을 사용
+const CONFIRMATION_SELECTORS: [&str; 5] = [
+    "#appointment .appointment-complete",
+    "#appointment .complete",
+    ".reservation-complete",
+    "#appointment",
+    "main",
+];
+
+const REFERENCE_LABELS: [&str; 5] = ["예약번호", "예약 번호", "접수번호", "접수 번호", "reference"];
+const STORE_LABELS: [&str; 3] = ["매장", "부티크", "store"];
+const DATE_LABELS: [&str; 3] = ["방문 날짜", "예약 날짜", "날짜"];
+// "운영 시간", "소요 시간" 같은 안내 행과 섞이지 않도록 구체적인 라벨만 사용
+const TIME_LABELS: [&str; 4] = ["방문 시간", "예약 시간", "방문 일시", "예약 일시"];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Confirmation {
+    pub reference: String,
+    pub store: Option<String>,
+    pub date: Option<String>,
+    pub time: Option<String>,
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ConfirmationStatus {
+    Confirmed(Confirmation),
+    Unverified { reason: String },
+}
+
+impl ConfirmationStatus {
+    pub fn unverified(reason: impl Into<String>) -> Self {
+        ConfirmationStatus::Unverified {
+            reason: reason.into(),
+        }
+    }
+}
+
+// 페이지에서 읽어온 완료 영역 내용 (dt/dd, th/td 쌍과 텍스트)
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct ConfirmationSnapshot {
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub fields: Vec<(String, String)>,
+    #[serde(default)]
+    pub text: String,
+}
+
+pub fn parse(snapshot: &ConfirmationSnapshot) -> ConfirmationStatus {
+    let reference = find_field(snapshot, &REFERENCE_LABELS)
+        .and_then(|value| first_token(&value))
+        .or_else(|| find_reference_in_text(&snapshot.text));
+
+    let Some(reference) = reference else {
+        return ConfirmationStatus::unverified("완료 페이지에서 예약번호를 찾을 수 없습니다");
+    };
+
+    let date = find_field(snapshot, &DATE_LABELS)
+        .and_then(|value| find_date(&value))
+        .or_else(|| find_date(&snapshot.text));
+    let time = find_field(snapshot, &TIME_LABELS)
+        .and_then(|value| find_time(&value))
+        .or_else(|| find_time(&snapshot.text));
+
+    ConfirmationStatus::Confirmed(Confirmation {
+        reference,
+        store: find_field(snapshot, &STORE_LABELS),
+        date,
+        time,
+        url: snapshot.url.clone(),
+    })
+}
+
+fn find_field(snapshot: &ConfirmationSnapshot, labels: &[&str]) -> Option<String> {
+    snapshot.fields.iter().find_map(|(label, value)| {
+        let label = label.trim().to_lowercase();
+        let value = value.trim();
+        (!value.is_empty() && labels.iter().any(|l| label.contains(l))).then(|| value.to_string())
+    })
+}
+
+fn first_token(value: &str) -> Option<String> {
+    value
+        .split_whitespace()
+        .next()
+        .map(|token| token.trim_matches(|c: char| !c.is_alphanumeric() && c != '-').to_string())
+        .filter(|token| !token.is_empty())
+}
+
+fn find_reference_in_text(text: &str) -> Option<String> {
+    let re = Regex::new(r"(?i)(?:예약|접수)\s*번호\s*[:：]?\s*([A-Z0-9][A-Z0-9-]{3,})").ok()?;
+    re.captures(text).map(|c| c[1].to_string())
+}
+
+// 2025-06-01, 2025.06.01, 2025년 6월 1일 -> 2025-06-01
+fn find_date(text: &str) -> Option<String> {
+    let re = Regex::new(r"(\d{4})\s*(?:[-./]|년)\s*(\d{1,2})\s*(?:[-./]|월)\s*(\d{1,2})").ok()?;
+    let c = re.captures(text)?;
+    let (year, month, day): (i32, u32, u32) = (c[1].parse().ok()?, c[2].parse().ok()?, c[3].parse().ok()?);
+    chrono::NaiveDate::from_ymd_opt(year, month, day).map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+// 14:00, 오후 2:30, 오전 12:10, 오후 12:10 -> 14:00, 14:30, 00:10, 12:10
+fn find_time(text: &str) -> Option<String> {
+    let re = Regex::new(r"(오전|오후)?\s*(\d{1,2}):(\d{2})").ok()?;
+    let c = re.captures(text)?;
+    let mut hour: u32 = c[2].parse().ok()?;
+    let minute: u32 = c[3].parse().ok()?;
+    match (c.get(1).map(|m| m.as_str()), hour) {
+        (Some("오전"), 12) => hour = 0,
+        (Some("오후"), h) if h < 12 => hour += 12,
+        _ => {}
+    }
+    chrono::NaiveTime::from_hms_opt(hour, minute, 0).map(|t| t.format("%H:%M").to_string())
+}
+
+pub async fn read_snapshot(page: &Page) -> Result<ConfirmationSnapshot, String> {
+    let selectors = serde_json::to_string(&CONFIRMATION_SELECTORS).map_err(|e| e.to_string())?;
+    let snapshot_js = format!(
+        r#"
+        (() => {{
+            const selectors = {};
+            const root = selectors.map(s => document.querySelector(s)).find(el => el) || document.body;
+            const fields = [];
+            root.querySelectorAll('dl').forEach(dl => {{
+                dl.querySelectorAll('dt').forEach(dt => {{
+                    const dd = dt.nextElementSibling;
+                    if (dd && dd.tagName === 'DD') fields.push([dt.textContent.trim(), dd.textContent.trim()]);
+                }});
+            }});
+            root.querySelectorAll('tr').forEach(tr => {{
+                const th = tr.querySelector('th');
+                const td = tr.querySelector('td');
+                if (th && td) fields.push([th.textContent.trim(), td.textContent.trim()]);
+            }});
+            return {{ url: window.location.href, fields, text: root.innerText.slice(0, 5000) }};
+        }})()
+    "#,
+        selectors
+    );
+
+    page.evaluate_expression(snapshot_js)
+        .await
+        .map_err(|e| format!("완료 페이지 조회 실패: {}", e))?
+        .into_value::<ConfirmationSnapshot>()
+        .map_err(|e| format!("완료 페이지 파싱 실패: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const URL: &str = "https://www.rolex.com/ko/appointment/complete";
+
+    // read_snapshot 의 스크립트가 돌려주는 형태 그대로
+    fn snapshot(value: serde_json::Value) -> ConfirmationSnapshot {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn confirmed(status: ConfirmationStatus) -> Confirmation {
+        match status {
+            ConfirmationStatus::Confirmed(confirmation) => confirmation,
+            ConfirmationStatus::Unverified { reason } => panic!("확인되지 않음: {}", reason),
+        }
+    }
+
+    #[test]
+    fn definition_list_fields() {
+        // <dl><dt>예약번호</dt><dd>RX-20250602 (변경 불가)</dd>...</dl>
+        let confirmation = confirmed(parse(&snapshot(json!({
+            "url": URL,
+            "fields": [
+                ["예약번호", " RX-20250602 (변경 불가) "],
+                ["방문 매장", "롤렉스 부티크 강남"],
+                ["방문 날짜", "2025.06.02 (월)"],
+                ["방문 시간", "17:30"],
+            ],
+            "text": "예약이 완료되었습니다",
+        }))));
+        assert_eq!(
+            confirmation,
+            Confirmation {
+                reference: "RX-20250602".to_string(),
+                store: Some("롤렉스 부티크 강남".to_string()),
+                date: Some("2025-06-02".to_string()),
+                time: Some("17:30".to_string()),
+                url: URL.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn table_row_fields() {
+        // <tr><th>접수 번호</th><td>A1B2C3</td></tr>...
+        let confirmation = confirmed(parse(&snapshot(json!({
+            "url": URL,
+            "fields": [
+                ["접수 번호", "A1B2C3"],
+                ["Store", "Rolex Boutique Seoul"],
+                ["예약 날짜", "2025년 6월 2일"],
+                ["예약 시간", "오후 2:30"],
+            ],
+        }))));
+        assert_eq!(confirmation.reference, "A1B2C3");
+        assert_eq!(confirmation.store.as_deref(), Some("Rolex Boutique Seoul"));
+        assert_eq!(confirmation.date.as_deref(), Some("2025-06-02"));
+        assert_eq!(confirmation.time.as_deref(), Some("14:30"));
+    }
+
+    #[test]
+    fn operating_hours_row_is_not_the_visit_time() {
+        let confirmation = confirmed(parse(&snapshot(json!({
+            "url": URL,
+            "fields": [
+                ["예약번호", "RX-1001"],
+                ["운영 시간", "10:30 - 20:00"],
+                ["소요 시간", "약 0:30"],
+                ["방문 일시", "2025-06-02 15:00"],
+            ],
+        }))));
+        assert_eq!(confirmation.time.as_deref(), Some("15:00"));
+    }
+
+    #[test]
+    fn twelve_oclock_with_meridiem() {
+        for (value, expected) in [
+            ("오전 12:30", "00:30"),
+            ("오후 12:15", "12:15"),
+            ("오전 9:05", "09:05"),
+            ("오후 11:45", "23:45"),
+        ] {
+            let confirmation = confirmed(parse(&snapshot(json!({
+                "url": URL,
+                "fields": [["예약번호", "RX-1002"], ["예약 시간", value]],
+            }))));
+            assert_eq!(confirmation.time.as_deref(), Some(expected), "{}", value);
+        }
+    }
+
+    #[test]
+    fn reference_falls_back_to_page_text() {
+        let confirmation = confirmed(parse(&snapshot(json!({
+            "url": URL,
+            "fields": [["안내", "방문 10분 전까지 도착해주세요"]],
+            "text": "예약이 완료되었습니다.\n예약 번호: rx-7788\n2025-06-03 오전 11:00 방문",
+        }))));
+        assert_eq!(confirmation.reference, "rx-7788");
+        assert_eq!(confirmation.store, None);
+        assert_eq!(confirmation.date.as_deref(), Some("2025-06-03"));
+        assert_eq!(confirmation.time.as_deref(), Some("11:00"));
+    }
+
+    #[test]
+    fn missing_reference_is_unverified() {
+        let status = parse(&snapshot(json!({
+            "url": URL,
+            "fields": [["예약번호", "  "], ["방문 날짜", "2025-06-02"]],
+            "text": "예약 신청이 접수되었습니다. 방문 날짜: 2025-06-02",
+        })));
+        assert!(matches!(status, ConfirmationStatus::Unverified { .. }), "{:?}", status);
+    }
+}
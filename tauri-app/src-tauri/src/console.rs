@@ -0,0 +1,332 @@
+// 페이지 콘솔 출력과 JS 예외 수집 - CDP Runtime(console API, 예외)/Log(브라우저 로그) 이벤트를 모아 두었다가
+// 단계가 끝날 때 해당 단계 스팬 안에서 실행 로그에 기록 (항목마다 페이지에서 발생한 시각을 함께 남김)
+use chromiumoxide::cdp::browser_protocol::log::{
+    EnableParams as LogEnableParams, EventEntryAdded, LogEntryLevel,
+};
+use chromiumoxide::cdp::js_protocol::runtime::{
+    ConsoleApiCalledType, EnableParams as RuntimeEnableParams, EventConsoleApiCalled, EventExceptionThrown,
+    RemoteObject,
+};
+use chromiumoxide::Page;
+use futures::StreamExt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+// 단계 하나에서 보관할 최대 항목 수 (넘는 항목은 개수만 기록)
+const MAX_ENTRIES: usize = 200;
+// 항목 하나의 최대 길이
+const MAX_TEXT_CHARS: usize = 500;
+// 단계가 끝난 뒤 늦게 도착하는 이벤트를 기다리는 간격과 최대 시간
+const SETTLE_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_SETTLE: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConsoleSource {
+    // console.log 등 페이지 스크립트의 출력
+    Console,
+    // 처리되지 않은 JS 예외
+    Exception,
+    // 네트워크 오류, 보안 경고 등 브라우저가 남긴 로그
+    Browser,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConsoleEntry {
+    pub source: ConsoleSource,
+    pub level: String,
+    pub text: String,
+    pub location: Option<String>,
+    // 페이지에서 발생한 시각 (Unix epoch 밀리초)
+    pub timestamp_ms: f64,
+}
+
+impl ConsoleEntry {
+    fn is_error(&self) -> bool {
+        self.source == ConsoleSource::Exception || matches!(self.level.as_str(), "error" | "assert" | "warning")
+    }
+}
+
+#[derive(Default)]
+struct CaptureState {
+    entries: Vec<ConsoleEntry>,
+    dropped: usize,
+}
+
+impl CaptureState {
+    fn received(&self) -> usize {
+        self.entries.len() + self.dropped
+    }
+
+    // cutoff 까지 발생한 항목만 꺼냄 - 그 뒤에 발생한 항목은 다음 단계 몫으로 남김
+    fn take_until(&mut self, cutoff_ms: f64) -> (Vec<ConsoleEntry>, usize) {
+        let (taken, kept) = std::mem::take(&mut self.entries)
+            .into_iter()
+            .partition(|entry| entry.timestamp_ms <= cutoff_ms);
+        self.entries = kept;
+        (taken, std::mem::take(&mut self.dropped))
+    }
+}
+
+fn now_ms() -> f64 {
+    chrono::Utc::now().timestamp_millis() as f64
+}
+
+fn format_time(timestamp_ms: f64) -> String {
+    chrono::DateTime::from_timestamp_millis(timestamp_ms as i64)
+        .map(|at| at.with_timezone(&chrono::Local).format("%H:%M:%S%.3f").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+pub struct ConsoleCapture {
+    state: Arc<Mutex<CaptureState>>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= MAX_TEXT_CHARS {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(MAX_TEXT_CHARS).collect();
+    truncated.push('…');
+    truncated
+}
+
+fn remote_object_text(object: &RemoteObject) -> String {
+    match &object.value {
+        Some(serde_json::Value::String(text)) => text.clone(),
+        Some(value) => value.to_string(),
+        None => object
+            .description
+            .clone()
+            .unwrap_or_else(|| object.r#type.as_ref().to_string()),
+    }
+}
+
+fn push(state: &Mutex<CaptureState>, entry: ConsoleEntry) {
+    if let Ok(mut state) = state.lock() {
+        if state.entries.len() >= MAX_ENTRIES {
+            state.dropped += 1;
+        } else {
+            state.entries.push(entry);
+        }
+    }
+}
+
+impl ConsoleCapture {
+    // 구독에 실패한 이벤트는 건너뜀 (수집은 진단용)
+    pub async fn start(page: &Page) -> Self {
+        let state = Arc::new(Mutex::new(CaptureState::default()));
+        let mut tasks = Vec::new();
+
+        // 이미 활성화되어 있으면 무시됨
+        let _ = page.execute(RuntimeEnableParams::default()).await;
+        let _ = page.execute(LogEnableParams::default()).await;
+
+        match page.event_listener::<EventConsoleApiCalled>().await {
+            Ok(mut events) => {
+                let state = state.clone();
+                tasks.push(tokio::spawn(async move {
+                    while let Some(event) = events.next().await {
+                        let level = match event.r#type {
+                            ConsoleApiCalledType::Warning => "warning",
+                            ConsoleApiCalledType::Error | ConsoleApiCalledType::Assert => "error",
+                            ConsoleApiCalledType::Debug => "debug",
+                            _ => "log",
+                        };
+                        let text = event.args.iter().map(remote_object_text).collect::<Vec<_>>().join(" ");
+                        let location = event
+                            .stack_trace
+                            .as_ref()
+                            .and_then(|trace| trace.call_frames.first())
+                            .filter(|frame| !frame.url.is_empty())
+                            .map(|frame| format!("{}:{}", frame.url, frame.line_number + 1));
+                        push(
+                            &state,
+                            ConsoleEntry {
+                                source: ConsoleSource::Console,
+                                level: level.to_string(),
+                                text: truncate(&text),
+                                location,
+                                timestamp_ms: *event.timestamp.inner(),
+                            },
+                        );
+                    }
+                }));
+            }
+            Err(e) => warn!("⚠️ 콘솔 이벤트를 구독할 수 없습니다: {}", e),
+        }
+
+        match page.event_listener::<EventExceptionThrown>().await {
+            Ok(mut events) => {
+                let state = state.clone();
+                tasks.push(tokio::spawn(async move {
+                    while let Some(event) = events.next().await {
+                        let details = &event.exception_details;
+                        // 예외 객체의 설명(메시지 + 스택)이 있으면 그쪽이 더 자세함
+                        let text = details
+                            .exception
+                            .as_ref()
+                            .and_then(|e| e.description.clone())
+                            .unwrap_or_else(|| details.text.clone());
+                        let location = details
+                            .url
+                            .as_ref()
+                            .filter(|url| !url.is_empty())
+                            .map(|url| format!("{}:{}", url, details.line_number + 1));
+                        push(
+                            &state,
+                            ConsoleEntry {
+                                source: ConsoleSource::Exception,
+                                level: "error".to_string(),
+                                text: truncate(&text),
+                                location,
+                                timestamp_ms: *event.timestamp.inner(),
+                            },
+                        );
+                    }
+                }));
+            }
+            Err(e) => warn!("⚠️ 예외 이벤트를 구독할 수 없습니다: {}", e),
+        }
+
+        match page.event_listener::<EventEntryAdded>().await {
+            Ok(mut events) => {
+                let state = state.clone();
+                tasks.push(tokio::spawn(async move {
+                    while let Some(event) = events.next().await {
+                        let entry = &event.entry;
+                        if entry.level == LogEntryLevel::Verbose {
+                            continue;
+                        }
+                        push(
+                            &state,
+                            ConsoleEntry {
+                                source: ConsoleSource::Browser,
+                                level: entry.level.as_ref().to_string(),
+                                text: truncate(&format!("[{}] {}", entry.source.as_ref(), entry.text)),
+                                location: entry.url.clone().filter(|url| !url.is_empty()),
+                                timestamp_ms: *entry.timestamp.inner(),
+                            },
+                        );
+                    }
+                }));
+            }
+            Err(e) => warn!("⚠️ 브라우저 로그 이벤트를 구독할 수 없습니다: {}", e),
+        }
+
+        Self { state, tasks }
+    }
+
+    // 단계가 끝날 때까지 발생한 항목을 단계 스팬 안에서 실행 로그에 기록하고 비움
+    // 이벤트는 비동기로 도착하므로 새 항목이 더 오지 않을 때까지 잠깐 기다린 뒤 기록
+    pub async fn flush(&self, span: &tracing::Span) {
+        let cutoff_ms = now_ms();
+        let started = Instant::now();
+        let mut received = self.received();
+        while started.elapsed() < MAX_SETTLE {
+            tokio::time::sleep(SETTLE_INTERVAL).await;
+            let now = self.received();
+            if now == received {
+                break;
+            }
+            received = now;
+        }
+
+        let (entries, dropped) = match self.state.lock() {
+            Ok(mut state) => state.take_until(cutoff_ms),
+            Err(_) => return,
+        };
+
+        let _entered = span.enter();
+        for entry in &entries {
+            let location = entry.location.as_deref().unwrap_or("-");
+            let at = format_time(entry.timestamp_ms);
+            let at = at.as_str();
+            match entry.source {
+                ConsoleSource::Exception => {
+                    warn!(source = "exception", location, at, "💥 페이지 스크립트 예외: {}", entry.text)
+                }
+                ConsoleSource::Console if entry.is_error() => {
+                    warn!(source = "console", level = %entry.level, location, at, "🖥️ 콘솔 [{}] {}", entry.level, entry.text)
+                }
+                ConsoleSource::Console => {
+                    info!(source = "console", level = %entry.level, location, at, "🖥️ 콘솔 [{}] {}", entry.level, entry.text)
+                }
+                ConsoleSource::Browser if entry.is_error() => {
+                    warn!(source = "browser", level = %entry.level, location, at, "🌐 브라우저 로그 [{}] {}", entry.level, entry.text)
+                }
+                ConsoleSource::Browser => {
+                    info!(source = "browser", level = %entry.level, location, at, "🌐 브라우저 로그 [{}] {}", entry.level, entry.text)
+                }
+            }
+        }
+        if dropped > 0 {
+            warn!("⚠️ 콘솔 항목 {}개는 너무 많아 기록하지 않았습니다", dropped);
+        }
+    }
+
+    fn received(&self) -> usize {
+        self.state.lock().map(|state| state.received()).unwrap_or(0)
+    }
+}
+
+impl Drop for ConsoleCapture {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(text: &str, timestamp_ms: f64) -> ConsoleEntry {
+        ConsoleEntry {
+            source: ConsoleSource::Console,
+            level: "log".to_string(),
+            text: text.to_string(),
+            location: None,
+            timestamp_ms,
+        }
+    }
+
+    #[test]
+    fn entries_after_the_cutoff_stay_for_the_next_step() {
+        let mut state = CaptureState {
+            entries: vec![entry("이전 단계", 1000.0), entry("경계", 2000.0), entry("다음 단계", 2001.0)],
+            dropped: 3,
+        };
+
+        let (taken, dropped) = state.take_until(2000.0);
+        let texts: Vec<&str> = taken.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, ["이전 단계", "경계"]);
+        assert_eq!(dropped, 3);
+        assert_eq!(state.entries.len(), 1);
+        assert_eq!(state.received(), 1);
+
+        let (taken, _) = state.take_until(now_ms());
+        assert_eq!(taken[0].text, "다음 단계");
+        assert_eq!(state.received(), 0);
+    }
+
+    #[test]
+    fn long_text_is_truncated() {
+        let text = "가".repeat(MAX_TEXT_CHARS + 10);
+        let truncated = truncate(&text);
+        assert_eq!(truncated.chars().count(), MAX_TEXT_CHARS + 1);
+        assert!(truncated.ends_with('…'));
+        assert_eq!(truncate("짧은 로그"), "짧은 로그");
+    }
+
+    #[test]
+    fn timestamps_are_shown_as_local_time() {
+        let at = format_time(now_ms());
+        assert_eq!(at.len(), "12:34:56.789".len(), "{}", at);
+        assert_eq!(format_time(f64::MAX), "-");
+    }
+}
@@ -0,0 +1,193 @@
+// 연락처 입력 폼의 추가 항목 (문의 내용, 이름, 전화번호 또는 임의의 name 속성 입력란)
+// 값은 JSON 으로 직렬화해 스크립트에 전달하므로 따옴표 등이 들어가도 스크립트가 깨지지 않음
+use chromiumoxide::Page;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// 설정 키 = 폼 입력란의 name 속성
+pub type ContactFields = BTreeMap<String, String>;
+
+pub const EMAIL_FIELD: &str = "email";
+const MESSAGE_MAX_CHARS: usize = 1000;
+const NAME_MAX_CHARS: usize = 50;
+
+// 입력 결과 - 값은 개인정보일 수 있으므로 항목 이름만 남김
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ContactFillReport {
+    // 값을 채운 항목
+    pub set: Vec<String>,
+    // 폼에서 입력란을 찾지 못한 항목
+    pub missing: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct FieldArg<'a> {
+    name: &'a str,
+    value: &'a str,
+}
+
+// 항목별 검사 - 오류 메시지 (없으면 None)
+pub fn validate_field(name: &str, value: &str) -> Option<String> {
+    if name.trim().is_empty() {
+        return Some("항목 이름이 비어 있습니다".to_string());
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '[' | ']' | '.'))
+    {
+        return Some(format!("잘못된 항목 이름: {} (입력란의 name 속성)", name));
+    }
+    if name == EMAIL_FIELD {
+        return Some("이메일은 email 설정으로 입력합니다".to_string());
+    }
+    if value.trim().is_empty() {
+        return Some(format!("{} 값이 비어 있습니다", name));
+    }
+
+    match name {
+        "message" if value.chars().count() > MESSAGE_MAX_CHARS => {
+            Some(format!("문의 내용은 {}자 이하여야 합니다", MESSAGE_MAX_CHARS))
+        }
+        "name" if value.chars().count() > NAME_MAX_CHARS => {
+            Some(format!("이름은 {}자 이하여야 합니다", NAME_MAX_CHARS))
+        }
+        "phone" => {
+            let digits = value.chars().filter(|c| c.is_ascii_digit()).count();
+            let allowed = value
+                .chars()
+                .all(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | ' ' | '(' | ')'));
+            if !allowed || !(9..=15).contains(&digits) {
+                Some("잘못된 전화번호 형식입니다 (숫자 9~15자리)".to_string())
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+pub fn validate(fields: &ContactFields) -> Vec<(String, String)> {
+    fields
+        .iter()
+        .filter_map(|(name, value)| validate_field(name, value).map(|message| (name.clone(), message)))
+        .collect()
+}
+
+// 이메일과 추가 항목을 한 번에 입력 - 화면에 보이는 입력란(input/textarea/select)만 채움
+pub async fn fill_fields(page: &Page, email: &str, fields: &ContactFields) -> Result<ContactFillReport, String> {
+    let mut named = vec![(EMAIL_FIELD, email)];
+    named.extend(fields.iter().map(|(name, value)| (name.as_str(), value.as_str())));
+    fill(page, &named).await
+}
+
+// (name 속성, 값) 순서대로 입력
+pub async fn fill(page: &Page, fields: &[(&str, &str)]) -> Result<ContactFillReport, String> {
+    page.evaluate_expression(fill_script(fields)?)
+        .await
+        .map_err(|e| format!("연락처 입력 실패: {}", e))?
+        .into_value::<ContactFillReport>()
+        .map_err(|e| format!("연락처 입력 결과 파싱 실패: {}", e))
+}
+
+fn fill_script(fields: &[(&str, &str)]) -> Result<String, String> {
+    let args: Vec<FieldArg> = fields.iter().map(|&(name, value)| FieldArg { name, value }).collect();
+    let args = serde_json::to_string(&args).map_err(|e| e.to_string())?;
+
+    Ok(format!(
+        r#"
+        (() => {{
+            const fields = {};
+            const set = [];
+            const missing = [];
+            for (const field of fields) {{
+                const input = Array.from(document.getElementsByName(field.name))
+                    .filter(el => ['INPUT', 'TEXTAREA', 'SELECT'].includes(el.tagName))
+                    .find(el => window.getComputedStyle(el).display !== 'none');
+                if (!input) {{
+                    missing.push(field.name);
+                    continue;
+                }}
+                input.value = field.value;
+                input.dispatchEvent(new Event('input', {{ bubbles: true }}));
+                input.dispatchEvent(new Event('change', {{ bubbles: true }}));
+                set.push(field.name);
+            }}
+            return {{ set, missing }};
+        }})()
+    "#,
+        args
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(name: &str, value: &str) -> String {
+        validate_field(name, value).unwrap_or_else(|| panic!("{}={} 가 통과함", name, value))
+    }
+
+    #[test]
+    fn field_names_must_be_form_names() {
+        assert!(error("", "값").contains("항목 이름이 비어 있습니다"));
+        assert!(error("first name", "값").contains("잘못된 항목 이름"));
+        assert!(error("name\"]", "값").contains("잘못된 항목 이름"));
+        assert!(error(EMAIL_FIELD, "user@example.com").contains("email 설정"));
+        assert_eq!(validate_field("contact[phone_2].value-x", "값"), None);
+    }
+
+    #[test]
+    fn values_must_not_be_blank() {
+        assert!(error("company", "  ").contains("company 값이 비어 있습니다"));
+        assert_eq!(validate_field("company", "롤렉스"), None);
+    }
+
+    #[test]
+    fn message_and_name_have_length_limits() {
+        assert_eq!(validate_field("message", &"가".repeat(MESSAGE_MAX_CHARS)), None);
+        assert!(error("message", &"가".repeat(MESSAGE_MAX_CHARS + 1)).contains("1000자 이하"));
+        assert_eq!(validate_field("name", &"김".repeat(NAME_MAX_CHARS)), None);
+        assert!(error("name", &"김".repeat(NAME_MAX_CHARS + 1)).contains("50자 이하"));
+    }
+
+    #[test]
+    fn phone_numbers_need_nine_to_fifteen_digits() {
+        for phone in ["010-1234-5678", "+82 10 1234 5678", "(02) 123-4567", "021234567"] {
+            assert_eq!(validate_field("phone", phone), None, "{}", phone);
+        }
+        for phone in ["010-1234", "0101234567890123", "010.1234.5678", "010-abcd-5678"] {
+            assert!(error("phone", phone).contains("잘못된 전화번호 형식"), "{}", phone);
+        }
+    }
+
+    #[test]
+    fn validate_reports_each_invalid_field() {
+        let fields: ContactFields = [
+            ("message", "문의"),
+            ("phone", "123"),
+            ("email", "user@example.com"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        let names: Vec<String> = validate(&fields).into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["email", "phone"]);
+    }
+
+    #[test]
+    fn fill_script_embeds_values_as_json() {
+        let message = "그는 \"좋아요\"라고 했고 it's </script>\\n\n다음 줄";
+        let script = fill_script(&[("email", "user@example.com"), ("message", message)]).unwrap();
+
+        let literal = script
+            .split_once("const fields = ")
+            .and_then(|(_, rest)| rest.split_once(";\n"))
+            .map(|(literal, _)| literal)
+            .unwrap();
+        let fields: Vec<serde_json::Value> = serde_json::from_str(literal).unwrap();
+        assert_eq!(fields[0]["name"], "email");
+        assert_eq!(fields[1]["name"], "message");
+        assert_eq!(fields[1]["value"], message);
+        assert!(script.contains(r#"\"좋아요\""#));
+    }
+}
@@ -0,0 +1,360 @@
+// 실행 중인 자동화의 일시정지/재개/수동 조작(takeover) 제어
+// 단계 사이의 체크포인트에서만 멈추므로 진행 중인 단계(PASS 인증 대기 등)는 끝까지 실행됨
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::info;
+
+use crate::page_state::AvailableSlots;
+
+// run_rolex_automation 의 단계 순서 (이름은 실행 로그의 step 필드와 동일)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+    CookiePopup,
+    VisitReservation,
+    RolexCollection,
+    Agree,
+    VisitDate,
+    VisitTime,
+    Next,
+    PassAuthentication,
+    Submit,
+    CheckSuccess,
+}
+
+impl Step {
+    pub const ALL: [Step; 10] = [
+        Step::CookiePopup,
+        Step::VisitReservation,
+        Step::RolexCollection,
+        Step::Agree,
+        Step::VisitDate,
+        Step::VisitTime,
+        Step::Next,
+        Step::PassAuthentication,
+        Step::Submit,
+        Step::CheckSuccess,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Step::CookiePopup => "cookie_popup",
+            Step::VisitReservation => "visit_reservation",
+            Step::RolexCollection => "rolex_collection",
+            Step::Agree => "agree",
+            Step::VisitDate => "visit_date",
+            Step::VisitTime => "visit_time",
+            Step::Next => "next",
+            Step::PassAuthentication => "pass_authentication",
+            Step::Submit => "submit",
+            Step::CheckSuccess => "check_success",
+        }
+    }
+
+    // 마지막 단계면 None
+    pub fn next(&self) -> Option<Step> {
+        let index = Step::ALL.iter().position(|s| s == self)?;
+        Step::ALL.get(index + 1).copied()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlState {
+    Running,
+    // 다음 체크포인트에서 멈춤
+    Paused,
+    // 사용자가 브라우저를 직접 조작 중 - 재개할 때 이어갈 단계를 지정할 수 있음
+    TakenOver,
+    // 중지 요청 - 다음 체크포인트에서 실행을 끝냄 (되돌릴 수 없음)
+    Cancelled,
+}
+
+pub const CANCELLED_MESSAGE: &str = "사용자 요청으로 자동화를 중지했습니다";
+
+// 사용자가 예약 가능 목록에서 고른 날짜/시간 (choose_slot 명령)
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SlotChoice {
+    pub date: Option<String>,
+    pub time: Option<String>,
+}
+
+impl SlotChoice {
+    // 형식(YYYY-MM-DD / HH:mm)과 마지막으로 보여준 예약 가능 목록에 있는지 확인
+    pub fn validate(&self, offered: &AvailableSlots) -> Result<(), String> {
+        if self.date.is_none() && self.time.is_none() {
+            return Err("날짜 또는 시간을 선택해주세요".to_string());
+        }
+        if let Some(date) = &self.date {
+            let valid = date.len() == 10 && chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok();
+            if !valid {
+                return Err(format!("날짜 형식이 올바르지 않습니다 (YYYY-MM-DD): {}", date));
+            }
+            if !offered.dates.contains(date) {
+                return Err(format!("예약 가능한 날짜가 아닙니다: {}", date));
+            }
+        }
+        if let Some(time) = &self.time {
+            let valid = time.len() == 5 && chrono::NaiveTime::parse_from_str(time, "%H:%M").is_ok();
+            if !valid {
+                return Err(format!("시간 형식이 올바르지 않습니다 (HH:mm): {}", time));
+            }
+            if !offered.times.contains(time) {
+                return Err(format!("예약 가능한 시간이 아닙니다: {}", time));
+            }
+        }
+        Ok(())
+    }
+}
+
+struct ControlInner {
+    state: ControlState,
+    current_step: Option<Step>,
+    resume_from: Option<Step>,
+    slot_choice: Option<SlotChoice>,
+    // 사용자 선택을 기다리는 동안 보여준 예약 가능 목록 (기다리지 않을 때는 None)
+    offered_slots: Option<AvailableSlots>,
+}
+
+pub struct RunControl {
+    inner: Mutex<ControlInner>,
+}
+
+impl Default for RunControl {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(ControlInner {
+                state: ControlState::Running,
+                current_step: None,
+                resume_from: None,
+                slot_choice: None,
+                offered_slots: None,
+            }),
+        }
+    }
+}
+
+fn ensure_not_cancelled(inner: &ControlInner) -> Result<(), String> {
+    if inner.state == ControlState::Cancelled {
+        return Err("이미 중지된 실행입니다".to_string());
+    }
+    Ok(())
+}
+
+// 체크포인트에서 재개 여부를 확인하는 간격
+const CHECKPOINT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+impl RunControl {
+    pub fn state(&self) -> ControlState {
+        self.inner.lock().map(|i| i.state).unwrap_or(ControlState::Running)
+    }
+
+    pub fn current_step(&self) -> Option<Step> {
+        self.inner.lock().ok().and_then(|i| i.current_step)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state() == ControlState::Cancelled
+    }
+
+    pub fn pause(&self) -> Result<(), String> {
+        let mut inner = self.inner.lock().map_err(|e| e.to_string())?;
+        ensure_not_cancelled(&inner)?;
+        if inner.state == ControlState::Running {
+            inner.state = ControlState::Paused;
+        }
+        Ok(())
+    }
+
+    pub fn take_over(&self) -> Result<(), String> {
+        let mut inner = self.inner.lock().map_err(|e| e.to_string())?;
+        ensure_not_cancelled(&inner)?;
+        inner.state = ControlState::TakenOver;
+        Ok(())
+    }
+
+    pub fn resume(&self, from_step: Option<Step>) -> Result<(), String> {
+        let mut inner = self.inner.lock().map_err(|e| e.to_string())?;
+        ensure_not_cancelled(&inner)?;
+        if inner.state == ControlState::Running {
+            return Err("일시정지 상태가 아닙니다".to_string());
+        }
+        inner.state = ControlState::Running;
+        inner.resume_from = from_step;
+        Ok(())
+    }
+
+    // 다음 체크포인트에서 멈추고, 날짜/시간 선택 대기 중이면 바로 대기를 끝냄
+    pub fn cancel(&self) -> Result<(), String> {
+        let mut inner = self.inner.lock().map_err(|e| e.to_string())?;
+        inner.state = ControlState::Cancelled;
+        Ok(())
+    }
+
+    pub fn choose_slot(&self, choice: SlotChoice) -> Result<(), String> {
+        let mut inner = self.inner.lock().map_err(|e| e.to_string())?;
+        ensure_not_cancelled(&inner)?;
+        let offered = inner
+            .offered_slots
+            .as_ref()
+            .ok_or_else(|| "날짜/시간 선택을 기다리는 중이 아닙니다".to_string())?;
+        choice.validate(offered)?;
+        inner.slot_choice = Some(choice);
+        Ok(())
+    }
+
+    // 예약 가능 목록을 기록하고 사용자가 그 안에서 날짜/시간을 고를 때까지 대기 (시간 초과나 중지 시 None)
+    // 대기가 끝나면 목록을 지워 이후의 choose_slot 은 거부됨
+    pub async fn wait_for_slot_choice(&self, offered: AvailableSlots, timeout: Duration) -> Option<SlotChoice> {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.offered_slots = Some(offered);
+            inner.slot_choice = None;
+        }
+        let started = tokio::time::Instant::now();
+        let choice = loop {
+            if started.elapsed() >= timeout {
+                break None;
+            }
+            {
+                let Ok(mut inner) = self.inner.lock() else {
+                    break None;
+                };
+                if inner.state == ControlState::Cancelled {
+                    break None;
+                }
+                if let Some(choice) = inner.slot_choice.take() {
+                    break Some(choice);
+                }
+            }
+            tokio::time::sleep(CHECKPOINT_POLL_INTERVAL).await;
+        };
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.offered_slots = None;
+            inner.slot_choice = None;
+        }
+        choice
+    }
+
+    // 단계 시작 전 호출 - 일시정지/수동 조작 중이면 재개될 때까지 대기
+    // 재개 시 사용자가 단계를 지정했으면 그 단계를 돌려주고, 중지 요청이 있으면 Err
+    pub async fn checkpoint(&self, step: Step) -> Result<Option<Step>, String> {
+        let mut announced = false;
+        loop {
+            {
+                let mut inner = self.inner.lock().map_err(|e| e.to_string())?;
+                if inner.state == ControlState::Cancelled {
+                    info!("🛑 자동화 중지: {} 단계 전", step.as_str());
+                    return Err(CANCELLED_MESSAGE.to_string());
+                }
+                if inner.state == ControlState::Running {
+                    let resume_from = inner.resume_from.take();
+                    inner.current_step = Some(resume_from.unwrap_or(step));
+                    if announced {
+                        info!("▶️ 자동화 재개: {}", resume_from.unwrap_or(step).as_str());
+                    }
+                    return Ok(resume_from);
+                }
+                if !announced {
+                    match inner.state {
+                        ControlState::TakenOver => info!("🖐️ 수동 조작 중 - {} 단계 전에서 대기합니다", step.as_str()),
+                        _ => info!("⏸️ 일시정지 - {} 단계 전에서 대기합니다", step.as_str()),
+                    }
+                    inner.current_step = Some(step);
+                    announced = true;
+                }
+            }
+            tokio::time::sleep(CHECKPOINT_POLL_INTERVAL).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offered() -> AvailableSlots {
+        AvailableSlots {
+            dates: vec!["2025-03-04".to_string(), "2025-03-05".to_string()],
+            times: vec!["17:30".to_string()],
+            selected_date: Some("2025-03-04".to_string()),
+        }
+    }
+
+    fn choice(date: Option<&str>, time: Option<&str>) -> SlotChoice {
+        SlotChoice {
+            date: date.map(str::to_string),
+            time: time.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn offered_slot_is_accepted() {
+        assert!(choice(Some("2025-03-05"), None).validate(&offered()).is_ok());
+        assert!(choice(Some("2025-03-04"), Some("17:30")).validate(&offered()).is_ok());
+    }
+
+    #[test]
+    fn malformed_or_unoffered_slot_is_rejected() {
+        assert!(choice(None, None).validate(&offered()).is_err());
+        assert!(choice(Some("2025-3-4"), None).validate(&offered()).is_err());
+        assert!(choice(Some("2025-03-04'); alert(1); ('"), None).validate(&offered()).is_err());
+        assert!(choice(Some("2025-03-06"), None).validate(&offered()).is_err());
+        assert!(choice(None, Some("1050")).validate(&offered()).is_err());
+        assert!(choice(None, Some("18:00")).validate(&offered()).is_err());
+    }
+
+    #[test]
+    fn choose_slot_requires_offered_slots() {
+        let control = RunControl::default();
+        assert!(control.choose_slot(choice(Some("2025-03-04"), None)).is_err());
+    }
+
+    #[tokio::test]
+    async fn choice_is_accepted_only_while_waiting() {
+        let control = std::sync::Arc::new(RunControl::default());
+        let waiter = {
+            let control = control.clone();
+            tokio::spawn(async move { control.wait_for_slot_choice(offered(), Duration::from_secs(5)).await })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        control.choose_slot(choice(Some("2025-03-05"), None)).unwrap();
+        assert_eq!(waiter.await.unwrap(), Some(choice(Some("2025-03-05"), None)));
+
+        // 대기가 끝난 뒤의 선택은 다음 대기에 남지 않고 거부됨
+        assert!(control.choose_slot(choice(Some("2025-03-05"), None)).is_err());
+    }
+
+    #[tokio::test]
+    async fn cancel_ends_slot_wait() {
+        let control = std::sync::Arc::new(RunControl::default());
+        let waiter = {
+            let control = control.clone();
+            tokio::spawn(async move { control.wait_for_slot_choice(offered(), Duration::from_secs(30)).await })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        control.cancel().unwrap();
+        let result = tokio::time::timeout(Duration::from_secs(5), waiter).await.unwrap().unwrap();
+        assert_eq!(result, None);
+    }
+
+    #[tokio::test]
+    async fn cancel_stops_paused_checkpoint() {
+        let control = std::sync::Arc::new(RunControl::default());
+        assert_eq!(control.checkpoint(Step::Agree).await, Ok(None));
+
+        control.pause().unwrap();
+        let checkpoint = {
+            let control = control.clone();
+            tokio::spawn(async move { control.checkpoint(Step::VisitDate).await })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        control.cancel().unwrap();
+        let result = tokio::time::timeout(Duration::from_secs(5), checkpoint).await.unwrap().unwrap();
+        assert_eq!(result, Err(CANCELLED_MESSAGE.to_string()));
+
+        assert!(control.is_cancelled());
+        assert!(control.resume(None).is_err());
+        assert!(control.pause().is_err());
+    }
+}
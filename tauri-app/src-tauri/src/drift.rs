@@ -0,0 +1,485 @@
+// 예약 페이지 구조 변경(DOM drift) 점검
+// 주요 영역의 구조 지문(태그/클래스/name 등, 텍스트와 상태 클래스 제외)을 기준으로 저장해 두고 새로 수집한 지문과 비교
+use chromiumoxide::Page;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::info;
+
+use crate::selectors;
+
+const BASELINES_DIR_NAME: &str = "dom_baselines";
+
+// 보고서에 영역별로 나열할 최대 경로 수
+const MAX_LISTED_PATHS: usize = 20;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum RegionMode {
+    // 영역 아래 전체 구조
+    Subtree,
+    // 영역 안의 입력란/버튼만
+    Controls,
+}
+
+struct RegionSpec {
+    name: &'static str,
+    selector: &'static str,
+    mode: RegionMode,
+}
+
+const REGIONS: [RegionSpec; 3] = [
+    RegionSpec {
+        name: "datetime_form",
+        selector: "#appointment .datetime-form",
+        mode: RegionMode::Subtree,
+    },
+    RegionSpec {
+        name: "appointment_controls",
+        selector: "#appointment",
+        mode: RegionMode::Controls,
+    },
+    // PASS 인증 후 연락처 폼 - 저장된 HTML 로 수집할 때만 나타남
+    RegionSpec {
+        name: "contact_form",
+        selector: r#"form:has(input[name="email"])"#,
+        mode: RegionMode::Controls,
+    },
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct RegionFingerprint {
+    pub name: String,
+    pub found: bool,
+    // 구조 경로 -> 같은 경로의 요소 수
+    #[serde(default)]
+    pub nodes: BTreeMap<String, u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DomFingerprint {
+    pub store_name: String,
+    // 수집한 URL 또는 HTML 파일 경로
+    pub source: String,
+    pub captured_at: String,
+    pub regions: Vec<RegionFingerprint>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftStatus {
+    Unchanged,
+    Changed,
+    // 기준에는 있던 영역이 사라짐
+    Missing,
+    // 기준에는 없던 영역이 나타남
+    Appeared,
+    // 기준과 현재 모두 영역을 찾지 못함
+    NotFound,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CountChange {
+    pub path: String,
+    pub before: u32,
+    pub after: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RegionDrift {
+    pub name: String,
+    pub status: DriftStatus,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    // 날짜/시간 목록처럼 개수만 달라진 경우 (구조 변경으로 보지 않음)
+    pub count_changes: Vec<CountChange>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DriftReport {
+    pub store_name: String,
+    pub baseline_captured_at: String,
+    pub captured_at: String,
+    pub source: String,
+    pub drifted: bool,
+    pub regions: Vec<RegionDrift>,
+    // 사람이 읽는 요약
+    pub summary: String,
+}
+
+pub fn diff(baseline: &DomFingerprint, current: &DomFingerprint) -> DriftReport {
+    let empty = RegionFingerprint::default();
+    let regions: Vec<RegionDrift> = REGIONS
+        .iter()
+        .map(|spec| {
+            let before = baseline.regions.iter().find(|r| r.name == spec.name).unwrap_or(&empty);
+            let after = current.regions.iter().find(|r| r.name == spec.name).unwrap_or(&empty);
+            diff_region(spec.name, before, after)
+        })
+        .collect();
+
+    let drifted = regions
+        .iter()
+        .any(|r| matches!(r.status, DriftStatus::Changed | DriftStatus::Missing | DriftStatus::Appeared));
+    let mut report = DriftReport {
+        store_name: current.store_name.clone(),
+        baseline_captured_at: baseline.captured_at.clone(),
+        captured_at: current.captured_at.clone(),
+        source: current.source.clone(),
+        drifted,
+        regions,
+        summary: String::new(),
+    };
+    report.summary = describe(&report);
+    report
+}
+
+fn diff_region(name: &str, before: &RegionFingerprint, after: &RegionFingerprint) -> RegionDrift {
+    let added: Vec<String> = after
+        .nodes
+        .keys()
+        .filter(|path| !before.nodes.contains_key(*path))
+        .cloned()
+        .collect();
+    let removed: Vec<String> = before
+        .nodes
+        .keys()
+        .filter(|path| !after.nodes.contains_key(*path))
+        .cloned()
+        .collect();
+    let count_changes: Vec<CountChange> = before
+        .nodes
+        .iter()
+        .filter_map(|(path, &count)| match after.nodes.get(path) {
+            Some(&after_count) if after_count != count => Some(CountChange {
+                path: path.clone(),
+                before: count,
+                after: after_count,
+            }),
+            _ => None,
+        })
+        .collect();
+
+    let status = match (before.found, after.found) {
+        (false, false) => DriftStatus::NotFound,
+        (true, false) => DriftStatus::Missing,
+        (false, true) => DriftStatus::Appeared,
+        (true, true) if added.is_empty() && removed.is_empty() => DriftStatus::Unchanged,
+        (true, true) => DriftStatus::Changed,
+    };
+
+    RegionDrift {
+        name: name.to_string(),
+        status,
+        added,
+        removed,
+        count_changes,
+    }
+}
+
+fn describe(report: &DriftReport) -> String {
+    let mut lines = vec![format!(
+        "{} 페이지 구조 점검 (기준: {}, 현재: {})",
+        report.store_name, report.baseline_captured_at, report.captured_at
+    )];
+    if !report.drifted {
+        lines.push("구조 변경 없음".to_string());
+    }
+
+    for region in &report.regions {
+        let status = match region.status {
+            DriftStatus::Unchanged => "변경 없음".to_string(),
+            DriftStatus::Changed => format!("변경됨 (추가 {}, 삭제 {})", region.added.len(), region.removed.len()),
+            DriftStatus::Missing => "영역이 사라짐".to_string(),
+            DriftStatus::Appeared => "새 영역".to_string(),
+            DriftStatus::NotFound => "수집되지 않음".to_string(),
+        };
+        lines.push(format!("- {}: {}", region.name, status));
+        if region.status != DriftStatus::Changed {
+            continue;
+        }
+        push_paths(&mut lines, "+", &region.added);
+        push_paths(&mut lines, "-", &region.removed);
+        for change in region.count_changes.iter().take(MAX_LISTED_PATHS) {
+            lines.push(format!("  ~ {} ({} -> {}개)", change.path, change.before, change.after));
+        }
+    }
+    lines.join("\n")
+}
+
+fn push_paths(lines: &mut Vec<String>, marker: &str, paths: &[String]) {
+    for path in paths.iter().take(MAX_LISTED_PATHS) {
+        lines.push(format!("  {} {}", marker, path));
+    }
+    if paths.len() > MAX_LISTED_PATHS {
+        lines.push(format!("  {} ... 외 {}개", marker, paths.len() - MAX_LISTED_PATHS));
+    }
+}
+
+#[derive(Serialize)]
+struct RegionArg<'a> {
+    name: &'a str,
+    selector: &'a str,
+    mode: RegionMode,
+}
+
+async fn read_regions(page: &Page) -> Result<Vec<RegionFingerprint>, String> {
+    let args: Vec<RegionArg> = REGIONS
+        .iter()
+        .map(|spec| RegionArg {
+            name: spec.name,
+            selector: spec.selector,
+            mode: spec.mode,
+        })
+        .collect();
+    let args = serde_json::to_string(&args).map_err(|e| e.to_string())?;
+
+    // 상태 클래스(on/off 등)와 숫자가 붙은 생성 id(slot-20240315 등)는 실행할 때마다 달라지므로 구조에서 제외
+    let fingerprint_js = format!(
+        r#"
+        (() => {{
+            const regions = {};
+            const STATE_CLASSES = ['on', 'off', 'active', 'selected', 'disabled', 'hidden', 'show', 'open', 'checked', 'is-active'];
+            const SKIP_TAGS = ['SCRIPT', 'STYLE', 'NOSCRIPT', 'svg'];
+            const MAX_DEPTH = 15;
+            const MAX_NODES = 3000;
+            const sig = (el) => {{
+                let s = el.tagName.toLowerCase();
+                if (el.id && !/\d{{3,}}/.test(el.id)) s += '#' + el.id;
+                const classes = Array.from(el.classList).filter(c => !STATE_CLASSES.includes(c)).sort();
+                if (classes.length) s += '.' + classes.join('.');
+                for (const attr of ['name', 'type', 'role']) {{
+                    const value = el.getAttribute(attr);
+                    if (value) s += '[' + attr + '=' + value + ']';
+                }}
+                Array.from(el.attributes).map(a => a.name).filter(n => n.startsWith('data-')).sort()
+                    .forEach(n => {{ s += '[' + n + ']'; }});
+                return s;
+            }};
+            return regions.map(region => {{
+                let root = null;
+                try {{
+                    root = document.querySelector(region.selector);
+                }} catch (e) {{}}
+                if (!root) return {{ name: region.name, found: false, nodes: {{}} }};
+                const nodes = {{}};
+                const add = (path) => {{ nodes[path] = (nodes[path] || 0) + 1; }};
+                if (region.mode === 'controls') {{
+                    root.querySelectorAll('input, select, textarea, button').forEach(el => add(sig(el)));
+                }} else {{
+                    let total = 0;
+                    const walk = (el, path, depth) => {{
+                        if (depth > MAX_DEPTH) return;
+                        for (const child of el.children) {{
+                            if (SKIP_TAGS.includes(child.tagName) || total >= MAX_NODES) continue;
+                            const childPath = path + ' > ' + sig(child);
+                            add(childPath);
+                            total++;
+                            walk(child, childPath, depth + 1);
+                        }}
+                    }};
+                    add(sig(root));
+                    walk(root, sig(root), 0);
+                }}
+                return {{ name: region.name, found: true, nodes }};
+            }});
+        }})()
+    "#,
+        args
+    );
+
+    page.evaluate_expression(fingerprint_js)
+        .await
+        .map_err(|e| format!("페이지 구조 수집 실패: {}", e))?
+        .into_value::<Vec<RegionFingerprint>>()
+        .map_err(|e| format!("페이지 구조 파싱 실패: {}", e))
+}
+
+// snapshot_path 가 있으면 저장된 HTML, 없으면 reserveUrl 을 열어 수집
+pub async fn capture(store_name: &str, reserve_url: &str, snapshot_path: Option<&Path>) -> Result<DomFingerprint, String> {
+    let (source, html) = match snapshot_path {
+        Some(path) => {
+            let html = std::fs::read_to_string(path)
+                .map_err(|e| format!("HTML 파일을 읽을 수 없습니다 ({}): {}", path.display(), e))?;
+            (path.display().to_string(), Some(html))
+        }
+        None => (reserve_url.to_string(), None),
+    };
+    info!("🧬 페이지 구조 수집: {}", source);
+
+    let url = reserve_url.to_string();
+    let regions = selectors::with_browser(move |page| {
+        Box::pin(async move {
+            match html {
+                Some(html) => {
+                    page.set_content(html)
+                        .await
+                        .map_err(|e| format!("HTML 을 불러올 수 없습니다: {}", e))?;
+                }
+                None => {
+                    match tokio::time::timeout(Duration::from_secs(20), page.goto(url.as_str())).await {
+                        Ok(Ok(_)) => {}
+                        Ok(Err(e)) => return Err(format!("페이지를 열 수 없습니다: {}", e)),
+                        Err(_) => return Err("페이지 로딩 시간 초과".to_string()),
+                    }
+                    tokio::time::sleep(Duration::from_secs(3)).await;
+                }
+            }
+            read_regions(page).await
+        })
+    })
+    .await?;
+
+    Ok(DomFingerprint {
+        store_name: store_name.to_string(),
+        source,
+        captured_at: chrono::Local::now().to_rfc3339(),
+        regions,
+    })
+}
+
+// 매장 이름으로 파일 이름을 만듦 - 문자/숫자 외에는 _XX (UTF-8 바이트) 로 바꿔 이름이 달라도 같은 파일이 되지 않도록 함
+pub fn baseline_path(app_data_dir: &Path, store_name: &str) -> PathBuf {
+    let mut store = String::new();
+    for c in store_name.chars() {
+        if c.is_alphanumeric() {
+            store.push(c);
+        } else {
+            let mut buf = [0u8; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                store.push_str(&format!("_{:02X}", byte));
+            }
+        }
+    }
+    app_data_dir.join(BASELINES_DIR_NAME).join(format!("{}.json", store))
+}
+
+pub fn save_baseline(app_data_dir: &Path, fingerprint: &DomFingerprint) -> Result<(), String> {
+    let path = baseline_path(app_data_dir, &fingerprint.store_name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("기준 구조 디렉토리를 만들 수 없습니다 ({}): {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(fingerprint).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json)
+        .map_err(|e| format!("기준 구조를 저장할 수 없습니다 ({}): {}", path.display(), e))
+}
+
+pub fn load_baseline(app_data_dir: &Path, store_name: &str) -> Result<DomFingerprint, String> {
+    let path = baseline_path(app_data_dir, store_name);
+    let json = std::fs::read_to_string(&path).map_err(|_| {
+        format!("{} 매장의 기준 구조가 없습니다. 먼저 기준 구조를 저장해주세요", store_name)
+    })?;
+    serde_json::from_str(&json).map_err(|e| format!("기준 구조 파싱 실패 ({}): {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(name: &str, nodes: &[(&str, u32)]) -> RegionFingerprint {
+        RegionFingerprint {
+            name: name.to_string(),
+            found: true,
+            nodes: nodes.iter().map(|(path, count)| (path.to_string(), *count)).collect(),
+        }
+    }
+
+    fn fingerprint(regions: Vec<RegionFingerprint>) -> DomFingerprint {
+        DomFingerprint {
+            store_name: "롯데".to_string(),
+            source: "https://example.com/reserve".to_string(),
+            captured_at: "2026-10-19T10:00:00+09:00".to_string(),
+            regions,
+        }
+    }
+
+    const DATE_LIST: &str = "div.datetime-form > div.date-list > ul > li[data-date]";
+
+    fn baseline() -> DomFingerprint {
+        fingerprint(vec![
+            region("datetime_form", &[("div.datetime-form", 1), (DATE_LIST, 14)]),
+            region("appointment_controls", &[("button[type=button]", 2)]),
+        ])
+    }
+
+    fn status(report: &DriftReport, name: &str) -> DriftStatus {
+        report.regions.iter().find(|r| r.name == name).unwrap().status
+    }
+
+    #[test]
+    fn identical_structure_is_unchanged() {
+        let report = diff(&baseline(), &baseline());
+        assert!(!report.drifted);
+        assert_eq!(status(&report, "datetime_form"), DriftStatus::Unchanged);
+        assert_eq!(status(&report, "appointment_controls"), DriftStatus::Unchanged);
+        assert_eq!(status(&report, "contact_form"), DriftStatus::NotFound);
+        assert!(report.summary.contains("구조 변경 없음"));
+    }
+
+    #[test]
+    fn count_only_changes_do_not_drift() {
+        let current = fingerprint(vec![
+            region("datetime_form", &[("div.datetime-form", 1), (DATE_LIST, 9)]),
+            region("appointment_controls", &[("button[type=button]", 2)]),
+        ]);
+        let report = diff(&baseline(), &current);
+
+        assert!(!report.drifted);
+        let datetime = &report.regions[0];
+        assert_eq!(datetime.status, DriftStatus::Unchanged);
+        assert_eq!(
+            datetime.count_changes,
+            [CountChange { path: DATE_LIST.to_string(), before: 14, after: 9 }]
+        );
+    }
+
+    #[test]
+    fn added_and_removed_paths_are_changes() {
+        let before = region("datetime_form", &[("div.datetime-form", 1), (DATE_LIST, 14)]);
+        let after = region("datetime_form", &[("div.datetime-form", 1), ("div.datetime-form > div.calendar", 1)]);
+        let drift = diff_region("datetime_form", &before, &after);
+
+        assert_eq!(drift.status, DriftStatus::Changed);
+        assert_eq!(drift.added, ["div.datetime-form > div.calendar"]);
+        assert_eq!(drift.removed, [DATE_LIST]);
+
+        let current = fingerprint(vec![after, region("appointment_controls", &[("button[type=button]", 2)])]);
+        let report = diff(&baseline(), &current);
+        assert!(report.drifted);
+        assert!(report.summary.contains("변경됨 (추가 1, 삭제 1)"));
+    }
+
+    #[test]
+    fn missing_and_appeared_regions_drift() {
+        let not_found = RegionFingerprint {
+            name: "appointment_controls".to_string(),
+            ..RegionFingerprint::default()
+        };
+        let current = fingerprint(vec![
+            region("datetime_form", &[("div.datetime-form", 1), (DATE_LIST, 14)]),
+            not_found,
+            region("contact_form", &[("input[name=email][type=email]", 1)]),
+        ]);
+        let report = diff(&baseline(), &current);
+
+        assert!(report.drifted);
+        assert_eq!(status(&report, "datetime_form"), DriftStatus::Unchanged);
+        assert_eq!(status(&report, "appointment_controls"), DriftStatus::Missing);
+        assert_eq!(status(&report, "contact_form"), DriftStatus::Appeared);
+    }
+
+    #[test]
+    fn similar_store_names_get_different_baselines() {
+        let dir = Path::new("/data");
+        let a = baseline_path(dir, "Rolex A");
+        let b = baseline_path(dir, "Rolex-A");
+        let c = baseline_path(dir, "Rolex_A");
+        assert_ne!(a, b);
+        assert_ne!(a, c);
+        assert_ne!(b, c);
+        assert_eq!(a, dir.join(BASELINES_DIR_NAME).join("Rolex_20A.json"));
+        assert_eq!(baseline_path(dir, "롯데잠실"), dir.join(BASELINES_DIR_NAME).join("롯데잠실.json"));
+    }
+}
@@ -0,0 +1,335 @@
+// 실행 이벤트 (사용자 개입 필요, 성공, 실패, 취소) 와 실행 중인 자동화 목록
+// 이벤트는 실행 로그, 프론트엔드("run-event"), 데스크톱 알림으로 함께 전달됨
+use chromiumoxide::cdp::browser_protocol::target::ActivateTargetParams;
+use chromiumoxide::Page;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+use crate::contact::ContactFillReport;
+use crate::control::{ControlState, RunControl, SlotChoice, Step};
+use crate::har::HarEntry;
+use crate::network::NetworkStats;
+use crate::page_state::AvailableSlots;
+use crate::notify;
+use crate::preferences::SlotSelection;
+use crate::sinks::{self, NotificationSink, SinkConfig};
+use crate::targets::ActionWindow;
+
+pub const RUN_EVENT_NAME: &str = "run-event";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum RunEventKind {
+    WaitingForUser,
+    Succeeded,
+    Failed,
+    Cancelled,
+    // 일시정지 또는 수동 조작(takeover) 시작
+    Paused,
+    Resumed,
+    // 예약 가능한 날짜/시간 중 하나를 앱에서 골라야 함
+    ChoiceRequired,
+}
+
+impl RunEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunEventKind::WaitingForUser => "waiting_for_user",
+            RunEventKind::Succeeded => "succeeded",
+            RunEventKind::Failed => "failed",
+            RunEventKind::Cancelled => "cancelled",
+            RunEventKind::Paused => "paused",
+            RunEventKind::Resumed => "resumed",
+            RunEventKind::ChoiceRequired => "choice_required",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RunEvent {
+    pub run_id: String,
+    pub store_name: String,
+    pub kind: RunEventKind,
+    pub message: String,
+    pub timestamp: String,
+    // WaitingForUser 일 때 사용자가 조작해야 하는 창
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<ActionWindow>,
+}
+
+// 실행 하나에 대한 핸들 - run_rolex_automation 에 전달되어 이벤트 발행과 페이지 등록에 사용
+#[derive(Clone)]
+pub struct RunContext {
+    pub app: AppHandle,
+    pub run_id: String,
+    pub store_name: String,
+    control: Arc<RunControl>,
+    selection: Arc<Mutex<Option<SlotSelection>>>,
+    contact: Arc<Mutex<Option<ContactFillReport>>>,
+    network: Arc<Mutex<NetworkStats>>,
+    har: Arc<Mutex<Vec<HarEntry>>>,
+}
+
+impl RunContext {
+    pub fn new(app: AppHandle, run_id: String, store_name: String, sink_configs: &[SinkConfig]) -> Self {
+        let sinks = Arc::new(sinks::build_all(sink_configs));
+        let control = Arc::new(RunControl::default());
+        app.state::<ActiveRuns>().register(&run_id, &store_name, sinks, control.clone());
+        Self {
+            app,
+            run_id,
+            store_name,
+            control,
+            selection: Arc::new(Mutex::new(None)),
+            contact: Arc::new(Mutex::new(None)),
+            network: Arc::new(Mutex::new(NetworkStats::default())),
+            har: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    // 선택한 날짜/시간 기록 (실행 결과와 기록에 포함됨)
+    pub fn update_selection(&self, update: impl FnOnce(&mut SlotSelection)) {
+        if let Ok(mut selection) = self.selection.lock() {
+            update(selection.get_or_insert_with(SlotSelection::default));
+        }
+    }
+
+    pub fn selection(&self) -> Option<SlotSelection> {
+        self.selection.lock().ok().and_then(|s| s.clone())
+    }
+
+    // 연락처 폼에 입력한 항목 기록
+    pub fn set_contact_report(&self, report: ContactFillReport) {
+        if let Ok(mut contact) = self.contact.lock() {
+            *contact = Some(report);
+        }
+    }
+
+    pub fn contact_report(&self) -> Option<ContactFillReport> {
+        self.contact.lock().ok().and_then(|c| c.clone())
+    }
+
+    // 네트워크 모니터가 갱신하는 통계 (브라우저를 열지 않은 실행은 None)
+    pub fn network_recorder(&self) -> Arc<Mutex<NetworkStats>> {
+        self.network.clone()
+    }
+
+    pub fn network_stats(&self) -> Option<NetworkStats> {
+        self.network.lock().ok().map(|n| n.clone()).filter(|n| !n.is_empty())
+    }
+
+    // HAR 기록 항목 (recordHar 설정 시에만 채워짐)
+    pub fn har_recorder(&self) -> Arc<Mutex<Vec<HarEntry>>> {
+        self.har.clone()
+    }
+
+    pub fn har_entries(&self) -> Vec<HarEntry> {
+        self.har.lock().map(|h| h.clone()).unwrap_or_default()
+    }
+
+    pub async fn wait_for_slot_choice(
+        &self,
+        offered: AvailableSlots,
+        timeout: std::time::Duration,
+    ) -> Option<SlotChoice> {
+        self.control.wait_for_slot_choice(offered, timeout).await
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.control.is_cancelled()
+    }
+
+    // 단계 사이 체크포인트 - 일시정지/수동 조작 중이면 재개될 때까지 대기, 중지 요청이 있으면 Err
+    pub async fn checkpoint(&self, step: Step) -> Result<Option<Step>, String> {
+        self.control.checkpoint(step).await
+    }
+
+    pub fn emit(&self, kind: RunEventKind, message: impl Into<String>) {
+        let event = RunEvent {
+            run_id: self.run_id.clone(),
+            store_name: self.store_name.clone(),
+            kind,
+            message: message.into(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            window: None,
+        };
+        publish(&self.app, &event);
+    }
+
+    // 사용자 개입 요청 - 알림에서 창을 앞으로 가져올 때 해당 창을 사용
+    pub fn emit_waiting(&self, window: &ActionWindow, message: impl Into<String>) {
+        self.app
+            .state::<ActiveRuns>()
+            .set_action_target(&self.run_id, window.target_id().map(str::to_string));
+        let event = RunEvent {
+            run_id: self.run_id.clone(),
+            store_name: self.store_name.clone(),
+            kind: RunEventKind::WaitingForUser,
+            message: message.into(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            window: Some(window.clone()),
+        };
+        publish(&self.app, &event);
+    }
+
+    pub fn set_page(&self, page: &Page) {
+        self.app.state::<ActiveRuns>().set_page(&self.run_id, page.clone());
+    }
+
+    pub fn finish(&self) {
+        self.app.state::<ActiveRuns>().unregister(&self.run_id);
+    }
+}
+
+pub fn publish(app: &AppHandle, event: &RunEvent) {
+    info!("📣 [{:?}] {}: {}", event.kind, event.store_name, event.message);
+    if let Err(e) = app.emit(RUN_EVENT_NAME, event.clone()) {
+        warn!("⚠️ 실행 이벤트 전달 실패: {}", e);
+    }
+    notify::notify_desktop(app, event);
+
+    let sinks = app.state::<ActiveRuns>().sinks(&event.run_id);
+    if !sinks.is_empty() {
+        let event = event.clone();
+        tokio::spawn(async move {
+            sinks::deliver_all(&sinks, &event).await;
+        });
+    }
+}
+
+struct ActiveRun {
+    store_name: String,
+    page: Option<Page>,
+    // 사용자가 조작해야 하는 팝업 창 (없으면 예약 페이지)
+    action_target: Option<String>,
+    sinks: Arc<Vec<Box<dyn NotificationSink>>>,
+    control: Arc<RunControl>,
+}
+
+// 프론트엔드에 보여줄 실행 중인 자동화 정보
+#[derive(Serialize, Debug, Clone)]
+pub struct ActiveRunInfo {
+    pub run_id: String,
+    pub store_name: String,
+    pub state: ControlState,
+    pub current_step: Option<Step>,
+}
+
+// Tauri 관리 상태 - 현재 실행 중인 자동화 (run_id 기준)
+#[derive(Default)]
+pub struct ActiveRuns {
+    runs: Mutex<HashMap<String, ActiveRun>>,
+}
+
+impl ActiveRuns {
+    fn register(
+        &self,
+        run_id: &str,
+        store_name: &str,
+        sinks: Arc<Vec<Box<dyn NotificationSink>>>,
+        control: Arc<RunControl>,
+    ) {
+        if let Ok(mut runs) = self.runs.lock() {
+            runs.insert(
+                run_id.to_string(),
+                ActiveRun {
+                    store_name: store_name.to_string(),
+                    page: None,
+                    action_target: None,
+                    sinks,
+                    control,
+                },
+            );
+        }
+    }
+
+    fn unregister(&self, run_id: &str) {
+        if let Ok(mut runs) = self.runs.lock() {
+            runs.remove(run_id);
+        }
+    }
+
+    fn set_page(&self, run_id: &str, page: Page) {
+        if let Ok(mut runs) = self.runs.lock() {
+            if let Some(run) = runs.get_mut(run_id) {
+                run.page = Some(page);
+            }
+        }
+    }
+
+    fn set_action_target(&self, run_id: &str, target_id: Option<String>) {
+        if let Ok(mut runs) = self.runs.lock() {
+            if let Some(run) = runs.get_mut(run_id) {
+                run.action_target = target_id;
+            }
+        }
+    }
+
+    pub fn page(&self, run_id: &str) -> Option<Page> {
+        self.runs.lock().ok()?.get(run_id)?.page.clone()
+    }
+
+    fn action_target(&self, run_id: &str) -> Option<String> {
+        self.runs.lock().ok()?.get(run_id)?.action_target.clone()
+    }
+
+    fn sinks(&self, run_id: &str) -> Arc<Vec<Box<dyn NotificationSink>>> {
+        self.runs
+            .lock()
+            .ok()
+            .and_then(|runs| runs.get(run_id).map(|run| run.sinks.clone()))
+            .unwrap_or_default()
+    }
+
+    pub fn control(&self, run_id: &str) -> Option<(String, Arc<RunControl>)> {
+        let runs = self.runs.lock().ok()?;
+        let run = runs.get(run_id)?;
+        Some((run.store_name.clone(), run.control.clone()))
+    }
+
+    pub fn info(&self) -> Vec<ActiveRunInfo> {
+        let Ok(runs) = self.runs.lock() else {
+            return Vec::new();
+        };
+        runs.iter()
+            .map(|(id, run)| ActiveRunInfo {
+                run_id: id.clone(),
+                store_name: run.store_name.clone(),
+                state: run.control.state(),
+                current_step: run.control.current_step(),
+            })
+            .collect()
+    }
+
+    // (run_id, store_name) 목록 - store_name 이 있으면 해당 매장만
+    pub fn list(&self, store_name: Option<&str>) -> Vec<(String, String)> {
+        let Ok(runs) = self.runs.lock() else {
+            return Vec::new();
+        };
+        runs.iter()
+            .filter(|(_, run)| store_name.map(|s| run.store_name == s).unwrap_or(true))
+            .map(|(id, run)| (id.clone(), run.store_name.clone()))
+            .collect()
+    }
+}
+
+// 실행 중인 브라우저 창을 앞으로 가져옴 (인증 팝업이 열려 있으면 팝업 창)
+pub async fn focus_browser(app: &AppHandle, run_id: &str) -> Result<(), String> {
+    let runs = app.state::<ActiveRuns>();
+    let page = runs
+        .page(run_id)
+        .ok_or_else(|| format!("실행 중인 브라우저가 없습니다: {}", run_id))?;
+    if let Some(target_id) = runs.action_target(run_id) {
+        page.execute(ActivateTargetParams::new(target_id))
+            .await
+            .map_err(|e| format!("팝업 창을 앞으로 가져올 수 없습니다: {}", e))?;
+        return Ok(());
+    }
+    page.bring_to_front()
+        .await
+        .map_err(|e| format!("브라우저 창을 앞으로 가져올 수 없습니다: {}", e))?;
+    Ok(())
+}
@@ -0,0 +1,648 @@
+// 실행 중 네트워크 요청/응답 기록 - CDP Network 이벤트를 HAR 1.2 항목으로 모아 실행 산출물 디렉토리에 저장
+// 쿠키(Cookie/Set-Cookie), 인증 헤더, 요청 본문과 URL 쿼리 값(폼 입력값)은 기본적으로 값을 가리고 이름/크기만 남김
+use base64::Engine;
+use chromiumoxide::cdp::browser_protocol::network::{
+    EnableParams, EventLoadingFailed, EventLoadingFinished, EventRequestWillBeSent, EventResponseReceived,
+    Headers, Request, Response,
+};
+use chromiumoxide::Page;
+use futures::stream::{self, BoxStream};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+pub const HAR_FILE_NAME: &str = "network.har";
+
+const REDACTED: &str = "[REDACTED]";
+// 쿠키 설정과 관계없이 값을 가리는 인증 헤더
+const AUTH_HEADERS: [&str; 2] = ["authorization", "proxy-authorization"];
+const COOKIE_HEADERS: [&str; 2] = ["cookie", "set-cookie"];
+// 값이 URL 인 헤더 - GET 폼 제출/리다이렉트의 쿼리 값은 본문과 같이 취급
+const URL_HEADERS: [&str; 2] = ["location", "referer"];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct HarConfig {
+    // 쿠키 값을 그대로 기록 (기본: 가림)
+    #[serde(rename = "includeCookies", default)]
+    pub include_cookies: bool,
+    // 요청 본문과 URL 쿼리 값(폼 입력값)을 그대로 기록 (기본: 가림)
+    #[serde(rename = "includeBodies", default)]
+    pub include_bodies: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct HarNameValue {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HarPostData {
+    pub mime_type: String,
+    pub text: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    pub http_version: String,
+    pub cookies: Vec<HarNameValue>,
+    pub headers: Vec<HarNameValue>,
+    pub query_string: Vec<HarNameValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<HarPostData>,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    pub size: i64,
+    pub mime_type: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: i64,
+    pub status_text: String,
+    pub http_version: String,
+    pub cookies: Vec<HarNameValue>,
+    pub headers: Vec<HarNameValue>,
+    pub content: HarContent,
+    #[serde(rename = "redirectURL")]
+    pub redirect_url: String,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+// 해당 없는 구간은 -1 (send/wait/receive 는 0 이상)
+#[derive(Serialize, Debug, Clone)]
+pub struct HarTimings {
+    pub blocked: f64,
+    pub dns: f64,
+    pub connect: f64,
+    pub ssl: f64,
+    pub send: f64,
+    pub wait: f64,
+    pub receive: f64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct HarCache {}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    pub started_date_time: String,
+    pub time: f64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    pub cache: HarCache,
+    pub timings: HarTimings,
+    #[serde(rename = "serverIPAddress", skip_serializing_if = "Option::is_none")]
+    pub server_ip_address: Option<String>,
+    #[serde(rename = "_resourceType")]
+    pub resource_type: String,
+    // 응답을 받지 못한 요청의 오류
+    #[serde(rename = "_error", skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// 응답 전에 실행이 끝난 요청은 status 0 으로 남음
+fn pending_response() -> HarResponse {
+    HarResponse {
+        status: 0,
+        status_text: String::new(),
+        http_version: String::new(),
+        cookies: Vec::new(),
+        headers: Vec::new(),
+        content: HarContent {
+            size: 0,
+            mime_type: String::new(),
+        },
+        redirect_url: String::new(),
+        headers_size: -1,
+        body_size: -1,
+    }
+}
+
+fn empty_timings() -> HarTimings {
+    HarTimings {
+        blocked: -1.0,
+        dns: -1.0,
+        connect: -1.0,
+        ssl: -1.0,
+        send: 0.0,
+        wait: 0.0,
+        receive: 0.0,
+    }
+}
+
+fn header_list(headers: &Headers) -> Vec<(String, String)> {
+    let Some(object) = headers.inner().as_object() else {
+        return Vec::new();
+    };
+    object
+        .iter()
+        .map(|(name, value)| {
+            let value = match value {
+                serde_json::Value::String(value) => value.clone(),
+                other => other.to_string(),
+            };
+            (name.clone(), value)
+        })
+        .collect()
+}
+
+fn redact_headers(headers: Vec<(String, String)>, options: &HarConfig) -> Vec<HarNameValue> {
+    headers
+        .into_iter()
+        .map(|(name, value)| {
+            let lower = name.to_lowercase();
+            let hidden = AUTH_HEADERS.contains(&lower.as_str())
+                || (!options.include_cookies && COOKIE_HEADERS.contains(&lower.as_str()));
+            let value = if hidden {
+                REDACTED.to_string()
+            } else if URL_HEADERS.contains(&lower.as_str()) {
+                redact_url(&value, options)
+            } else {
+                value
+            };
+            HarNameValue { name, value }
+        })
+        .collect()
+}
+
+// Cookie: "a=1; b=2" / Set-Cookie: 줄마다 "a=1; Path=/"
+fn cookies(headers: &[(String, String)], header: &str, options: &HarConfig) -> Vec<HarNameValue> {
+    let set_cookie = header == "set-cookie";
+    headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case(header))
+        .flat_map(|(_, value)| {
+            let pairs: Vec<&str> = if set_cookie {
+                value.lines().filter_map(|line| line.split(';').next()).collect()
+            } else {
+                value.split(';').collect()
+            };
+            pairs
+                .into_iter()
+                .filter_map(|pair| {
+                    let (name, value) = pair.trim().split_once('=')?;
+                    Some(HarNameValue {
+                        name: name.trim().to_string(),
+                        value: if options.include_cookies {
+                            value.trim().to_string()
+                        } else {
+                            REDACTED.to_string()
+                        },
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn query_string(url: &str, options: &HarConfig) -> Vec<HarNameValue> {
+    url::Url::parse(url)
+        .map(|url| {
+            url.query_pairs()
+                .map(|(name, value)| HarNameValue {
+                    name: name.into_owned(),
+                    value: if options.include_bodies {
+                        value.into_owned()
+                    } else {
+                        REDACTED.to_string()
+                    },
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// 쿼리 값만 가리고 이름은 남김 (a=1&b -> a=[REDACTED]&b) - 상대 경로나 해석할 수 없는 URL 은 ? 뒤를 모두 가림
+fn redact_url(url: &str, options: &HarConfig) -> String {
+    if options.include_bodies {
+        return url.to_string();
+    }
+    let Some((base, rest)) = url.split_once('?') else {
+        return url.to_string();
+    };
+    let (query, fragment) = match rest.split_once('#') {
+        Some((query, fragment)) => (query, Some(fragment)),
+        None => (rest, None),
+    };
+    let redacted: Vec<String> = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _)) => format!("{}={}", name, REDACTED),
+            None => pair.to_string(),
+        })
+        .collect();
+    match fragment {
+        Some(fragment) => format!("{}?{}#{}", base, redacted.join("&"), fragment),
+        None => format!("{}?{}", base, redacted.join("&")),
+    }
+}
+
+fn post_data(request: &Request, headers: &[(String, String)], options: &HarConfig) -> Option<HarPostData> {
+    if !request.has_post_data.unwrap_or(false) {
+        return None;
+    }
+    let mime_type = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        .map(|(_, value)| value.clone())
+        .unwrap_or_default();
+    let text = if options.include_bodies {
+        let bytes: Vec<u8> = request
+            .post_data_entries
+            .iter()
+            .flatten()
+            .filter_map(|entry| entry.bytes.as_ref())
+            .filter_map(|bytes| base64::engine::general_purpose::STANDARD.decode(AsRef::<str>::as_ref(bytes)).ok())
+            .flatten()
+            .collect();
+        String::from_utf8_lossy(&bytes).into_owned()
+    } else {
+        REDACTED.to_string()
+    };
+    Some(HarPostData { mime_type, text })
+}
+
+fn body_size(request: &Request) -> i64 {
+    if !request.has_post_data.unwrap_or(false) {
+        return 0;
+    }
+    request
+        .post_data_entries
+        .iter()
+        .flatten()
+        .filter_map(|entry| entry.bytes.as_ref())
+        .filter_map(|bytes| base64::engine::general_purpose::STANDARD.decode(AsRef::<str>::as_ref(bytes)).ok())
+        .map(|bytes| bytes.len() as i64)
+        .sum()
+}
+
+fn http_version(protocol: Option<&str>) -> String {
+    match protocol {
+        Some("h2") => "HTTP/2".to_string(),
+        Some("h3") => "HTTP/3".to_string(),
+        Some(protocol) if protocol.starts_with("http/") => protocol.to_uppercase(),
+        Some(protocol) => protocol.to_string(),
+        None => String::new(),
+    }
+}
+
+fn build_request(request: &Request, options: &HarConfig) -> HarRequest {
+    let headers = header_list(&request.headers);
+    HarRequest {
+        method: request.method.clone(),
+        url: redact_url(&request.url, options),
+        http_version: String::new(),
+        cookies: cookies(&headers, "cookie", options),
+        query_string: query_string(&request.url, options),
+        post_data: post_data(request, &headers, options),
+        headers: redact_headers(headers, options),
+        headers_size: -1,
+        body_size: body_size(request),
+    }
+}
+
+fn apply_response(entry: &mut HarEntry, response: &Response, options: &HarConfig) {
+    let headers = header_list(&response.headers);
+    let version = http_version(response.protocol.as_deref());
+    if entry.request.http_version.is_empty() {
+        entry.request.http_version = version.clone();
+    }
+    let redirect_url = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("location"))
+        .map(|(_, value)| redact_url(value, options))
+        .unwrap_or_default();
+    entry.response = HarResponse {
+        status: response.status,
+        status_text: response.status_text.clone(),
+        http_version: version,
+        cookies: cookies(&headers, "set-cookie", options),
+        headers: redact_headers(headers, options),
+        content: HarContent {
+            size: 0,
+            mime_type: response.mime_type.clone(),
+        },
+        redirect_url,
+        headers_size: -1,
+        body_size: -1,
+    };
+    entry.server_ip_address = response.remote_ip_address.clone().filter(|ip| !ip.is_empty());
+
+    if let Some(timing) = &response.timing {
+        let span = |start: f64, end: f64| if start >= 0.0 && end >= start { end - start } else { -1.0 };
+        entry.timings.dns = span(timing.dns_start, timing.dns_end);
+        entry.timings.connect = span(timing.connect_start, timing.connect_end);
+        entry.timings.ssl = span(timing.ssl_start, timing.ssl_end);
+        entry.timings.send = span(timing.send_start, timing.send_end).max(0.0);
+        entry.timings.wait = span(timing.send_end, timing.receive_headers_end).max(0.0);
+    }
+}
+
+// 요청 하나의 진행 상태 (request id 는 리다이렉트 동안 유지됨)
+struct Pending {
+    index: usize,
+    started: f64,
+    responded: Option<f64>,
+}
+
+enum NetworkEvent {
+    Request(Arc<EventRequestWillBeSent>),
+    Response(Arc<EventResponseReceived>),
+    Finished(Arc<EventLoadingFinished>),
+    Failed(Arc<EventLoadingFailed>),
+}
+
+pub struct HarRecorder {
+    task: Option<JoinHandle<()>>,
+}
+
+impl HarRecorder {
+    // 기록한 항목은 실행 컨텍스트와 공유 (실행이 어디서 끝나든 저장되도록)
+    pub async fn start(page: &Page, options: HarConfig, entries: Arc<Mutex<Vec<HarEntry>>>) -> Self {
+        // 이미 활성화되어 있으면 무시됨
+        let _ = page.execute(EnableParams::default()).await;
+
+        // 요청/응답 순서를 지키기 위해 하나의 스트림으로 합쳐 처리
+        let listeners = async {
+            let requests = page.event_listener::<EventRequestWillBeSent>().await?;
+            let responses = page.event_listener::<EventResponseReceived>().await?;
+            let finished = page.event_listener::<EventLoadingFinished>().await?;
+            let failed = page.event_listener::<EventLoadingFailed>().await?;
+            let streams: Vec<BoxStream<'static, NetworkEvent>> = vec![
+                requests.map(NetworkEvent::Request).boxed(),
+                responses.map(NetworkEvent::Response).boxed(),
+                finished.map(NetworkEvent::Finished).boxed(),
+                failed.map(NetworkEvent::Failed).boxed(),
+            ];
+            Ok::<_, chromiumoxide::error::CdpError>(stream::select_all(streams))
+        };
+        let mut events = match listeners.await {
+            Ok(events) => events,
+            Err(e) => {
+                warn!("⚠️ HAR 기록을 위한 네트워크 이벤트를 구독할 수 없습니다: {}", e);
+                return Self { task: None };
+            }
+        };
+
+        let task = tokio::spawn(async move {
+            let mut pending: HashMap<String, Pending> = HashMap::new();
+            while let Some(event) = events.next().await {
+                let Ok(mut entries) = entries.lock() else {
+                    continue;
+                };
+                match event {
+                    NetworkEvent::Request(event) => {
+                        let request_id = event.request_id.as_ref().to_string();
+                        let timestamp = *event.timestamp.inner();
+                        // 리다이렉트 - 이전 항목을 리다이렉트 응답으로 마무리하고 새 항목 시작
+                        if let (Some(redirect), Some(previous)) =
+                            (&event.redirect_response, pending.remove(&request_id))
+                        {
+                            if let Some(entry) = entries.get_mut(previous.index) {
+                                apply_response(entry, redirect, &options);
+                                entry.time = ((timestamp - previous.started) * 1000.0).max(0.0);
+                            }
+                        }
+                        let started_date_time =
+                            chrono::DateTime::from_timestamp_millis((*event.wall_time.inner() * 1000.0) as i64)
+                                .unwrap_or_else(chrono::Utc::now)
+                                .to_rfc3339();
+                        entries.push(HarEntry {
+                            started_date_time,
+                            time: 0.0,
+                            request: build_request(&event.request, &options),
+                            response: pending_response(),
+                            cache: HarCache {},
+                            timings: empty_timings(),
+                            server_ip_address: None,
+                            resource_type: event
+                                .r#type
+                                .as_ref()
+                                .map(|t| t.as_ref().to_lowercase())
+                                .unwrap_or_default(),
+                            error: None,
+                        });
+                        pending.insert(
+                            request_id,
+                            Pending {
+                                index: entries.len() - 1,
+                                started: timestamp,
+                                responded: None,
+                            },
+                        );
+                    }
+                    NetworkEvent::Response(event) => {
+                        let Some(request) = pending.get_mut(event.request_id.as_ref()) else {
+                            continue;
+                        };
+                        request.responded = Some(*event.timestamp.inner());
+                        if let Some(entry) = entries.get_mut(request.index) {
+                            apply_response(entry, &event.response, &options);
+                        }
+                    }
+                    NetworkEvent::Finished(event) => {
+                        let Some(request) = pending.remove(event.request_id.as_ref()) else {
+                            continue;
+                        };
+                        let finished = *event.timestamp.inner();
+                        if let Some(entry) = entries.get_mut(request.index) {
+                            entry.time = ((finished - request.started) * 1000.0).max(0.0);
+                            entry.timings.receive = request
+                                .responded
+                                .map(|responded| ((finished - responded) * 1000.0).max(0.0))
+                                .unwrap_or(0.0);
+                            entry.response.body_size = event.encoded_data_length as i64;
+                            entry.response.content.size = event.encoded_data_length as i64;
+                        }
+                    }
+                    NetworkEvent::Failed(event) => {
+                        let Some(request) = pending.remove(event.request_id.as_ref()) else {
+                            continue;
+                        };
+                        if let Some(entry) = entries.get_mut(request.index) {
+                            entry.time = ((*event.timestamp.inner() - request.started) * 1000.0).max(0.0);
+                            entry.error = Some(event.error_text.clone());
+                        }
+                    }
+                }
+            }
+        });
+
+        Self { task: Some(task) }
+    }
+}
+
+impl Drop for HarRecorder {
+    fn drop(&mut self) {
+        if let Some(task) = &self.task {
+            task.abort();
+        }
+    }
+}
+
+// <실행 산출물 디렉토리>/network.har 로 저장
+pub fn save(run_dir: &Path, entries: Vec<HarEntry>) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(run_dir)
+        .map_err(|e| format!("실행 기록 디렉토리를 만들 수 없습니다 ({}): {}", run_dir.display(), e))?;
+
+    let har = serde_json::json!({
+        "log": {
+            "version": "1.2",
+            "creator": {
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "pages": [],
+            "entries": entries,
+        }
+    });
+    let json = serde_json::to_string_pretty(&har).map_err(|e| e.to_string())?;
+    let path = run_dir.join(HAR_FILE_NAME);
+    std::fs::write(&path, json).map_err(|e| format!("HAR 파일을 저장할 수 없습니다 ({}): {}", path.display(), e))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFAULT: HarConfig = HarConfig {
+        include_cookies: false,
+        include_bodies: false,
+    };
+    const INCLUDE_ALL: HarConfig = HarConfig {
+        include_cookies: true,
+        include_bodies: true,
+    };
+
+    fn headers(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(n, v)| (n.to_string(), v.to_string())).collect()
+    }
+
+    fn pairs(values: &[HarNameValue]) -> Vec<(&str, &str)> {
+        values.iter().map(|v| (v.name.as_str(), v.value.as_str())).collect()
+    }
+
+    fn post_request(body: &str) -> Request {
+        serde_json::from_value(serde_json::json!({
+            "url": "https://www.rolex.com/ko/store/appointment?step=4&email=user%40example.com",
+            "method": "POST",
+            "headers": { "Content-Type": "application/x-www-form-urlencoded" },
+            "hasPostData": true,
+            "postDataEntries": [{ "bytes": base64::engine::general_purpose::STANDARD.encode(body) }],
+            "initialPriority": "High",
+            "referrerPolicy": "strict-origin-when-cross-origin",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn headers_hide_auth_always_and_cookies_unless_included() {
+        let raw = headers(&[
+            ("Authorization", "Bearer token"),
+            ("Cookie", "session=abc"),
+            ("Referer", "https://www.rolex.com/ko/store?phone=01012345678"),
+            ("Accept", "text/html"),
+        ]);
+
+        assert_eq!(
+            pairs(&redact_headers(raw.clone(), &DEFAULT)),
+            vec![
+                ("Authorization", REDACTED),
+                ("Cookie", REDACTED),
+                ("Referer", "https://www.rolex.com/ko/store?phone=[REDACTED]"),
+                ("Accept", "text/html"),
+            ]
+        );
+        assert_eq!(
+            pairs(&redact_headers(raw, &INCLUDE_ALL)),
+            vec![
+                ("Authorization", REDACTED),
+                ("Cookie", "session=abc"),
+                ("Referer", "https://www.rolex.com/ko/store?phone=01012345678"),
+                ("Accept", "text/html"),
+            ]
+        );
+    }
+
+    #[test]
+    fn cookie_and_multi_line_set_cookie_values_are_split() {
+        let request = headers(&[("cookie", "session=abc; theme=dark")]);
+        let response = headers(&[("Set-Cookie", "session=abc; Path=/; HttpOnly\nlang=ko; Max-Age=60")]);
+
+        assert_eq!(
+            pairs(&cookies(&request, "cookie", &DEFAULT)),
+            vec![("session", REDACTED), ("theme", REDACTED)]
+        );
+        assert_eq!(
+            pairs(&cookies(&request, "cookie", &INCLUDE_ALL)),
+            vec![("session", "abc"), ("theme", "dark")]
+        );
+        assert_eq!(
+            pairs(&cookies(&response, "set-cookie", &DEFAULT)),
+            vec![("session", REDACTED), ("lang", REDACTED)]
+        );
+        assert_eq!(
+            pairs(&cookies(&response, "set-cookie", &INCLUDE_ALL)),
+            vec![("session", "abc"), ("lang", "ko")]
+        );
+    }
+
+    #[test]
+    fn post_body_is_hidden_unless_included() {
+        let request = post_request("email=user%40example.com&phone=01012345678");
+        let headers = header_list(&request.headers);
+
+        let hidden = post_data(&request, &headers, &DEFAULT).unwrap();
+        assert_eq!(hidden.mime_type, "application/x-www-form-urlencoded");
+        assert_eq!(hidden.text, REDACTED);
+        assert_eq!(
+            post_data(&request, &headers, &INCLUDE_ALL).unwrap().text,
+            "email=user%40example.com&phone=01012345678"
+        );
+        assert_eq!(body_size(&request), 42);
+    }
+
+    #[test]
+    fn query_values_are_hidden_in_query_string_and_url() {
+        let url = "https://www.rolex.com/ko/store/appointment?email=user%40example.com&phone=010&flag#top";
+
+        assert_eq!(
+            pairs(&query_string(url, &DEFAULT)),
+            vec![("email", REDACTED), ("phone", REDACTED), ("flag", REDACTED)]
+        );
+        assert_eq!(
+            pairs(&query_string(url, &INCLUDE_ALL)),
+            vec![("email", "user@example.com"), ("phone", "010"), ("flag", "")]
+        );
+        assert_eq!(
+            redact_url(url, &DEFAULT),
+            "https://www.rolex.com/ko/store/appointment?email=[REDACTED]&phone=[REDACTED]&flag#top"
+        );
+        assert_eq!(redact_url(url, &INCLUDE_ALL), url);
+        assert_eq!(redact_url("/ko/store/done?ref=ABC123", &DEFAULT), "/ko/store/done?ref=[REDACTED]");
+
+        let request = build_request(&post_request("a=1"), &DEFAULT);
+        assert_eq!(
+            request.url,
+            "https://www.rolex.com/ko/store/appointment?step=[REDACTED]&email=[REDACTED]"
+        );
+    }
+}
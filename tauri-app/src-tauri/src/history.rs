@@ -0,0 +1,60 @@
+// 실행 기록 - <앱 데이터 디렉토리>/runs/<run_id>/run.json 에 실행 결과를 저장
+// 같은 디렉토리는 해당 실행의 산출물(.ics 등) 저장 위치로도 사용됨
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::confirmation::ConfirmationStatus;
+use crate::contact::ContactFillReport;
+use crate::logging;
+use crate::network::NetworkStats;
+use crate::preferences::SlotSelection;
+use crate::preflight::PreflightReport;
+
+const RUNS_DIR_NAME: &str = "runs";
+const RECORD_FILE_NAME: &str = "run.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RunRecord {
+    pub run_id: String,
+    pub store_name: String,
+    pub visit_date: Option<String>,
+    pub visit_time: Option<String>,
+    pub success: bool,
+    pub message: String,
+    pub timestamp: String,
+    pub confirmation: ConfirmationStatus,
+    #[serde(default)]
+    pub preflight: Option<PreflightReport>,
+    #[serde(default)]
+    pub slot_selection: Option<SlotSelection>,
+    #[serde(default)]
+    pub contact_fields: Option<ContactFillReport>,
+    #[serde(default)]
+    pub network: Option<NetworkStats>,
+    // recordHar 설정 시 저장한 HAR 파일 경로
+    #[serde(default)]
+    pub har_file: Option<String>,
+}
+
+pub fn run_dir(app_data_dir: &Path, run_id: &str) -> Result<PathBuf, String> {
+    logging::validate_run_id(run_id)?;
+    Ok(app_data_dir.join(RUNS_DIR_NAME).join(run_id))
+}
+
+pub fn save_record(app_data_dir: &Path, record: &RunRecord) -> Result<(), String> {
+    let dir = run_dir(app_data_dir, &record.run_id)?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("실행 기록 디렉토리를 만들 수 없습니다 ({}): {}", dir.display(), e))?;
+
+    let json = serde_json::to_string_pretty(record).map_err(|e| e.to_string())?;
+    let path = dir.join(RECORD_FILE_NAME);
+    std::fs::write(&path, json)
+        .map_err(|e| format!("실행 기록을 저장할 수 없습니다 ({}): {}", path.display(), e))
+}
+
+pub fn load_record(app_data_dir: &Path, run_id: &str) -> Result<RunRecord, String> {
+    let path = run_dir(app_data_dir, run_id)?.join(RECORD_FILE_NAME);
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| format!("실행 기록을 찾을 수 없습니다 ({}): {}", path.display(), e))?;
+    serde_json::from_str(&json).map_err(|e| format!("실행 기록 형식 오류: {}", e))
+}
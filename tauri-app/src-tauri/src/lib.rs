@@ -4,8 +4,36 @@ use futures::StreamExt; // chromiumoxide 이벤트 처리에 필요할 수 있
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use chrono::{Local, NaiveTime, NaiveDateTime};
+use tauri::Manager;
+use tracing::{error, info, info_span, warn, Instrument};
 
 mod automation;
+mod calendar;
+mod config;
+mod confirmation;
+mod console;
+mod contact;
+mod control;
+mod drift;
+mod events;
+mod har;
+mod history;
+mod locators;
+mod logging;
+mod network;
+mod notify;
+mod pacing;
+mod page_state;
+mod pii;
+mod preferences;
+mod preflight;
+mod recorder;
+mod replay;
+mod retry;
+mod secrets;
+mod selectors;
+mod sinks;
+mod targets;
 use automation::{
     log_user_action,
     handle_initial_popup,
@@ -19,6 +47,10 @@ use automation::{
     submit_final_reservation,
     check_success_page
 };
+use confirmation::{Confirmation, ConfirmationStatus};
+use control::Step;
+use events::{RunContext, RunEventKind};
+use page_state::PageState;
 // handle_auth_page는 이 파일(lib.rs)에 정의되어 있으므로 use하지 않습니다.
 
 #[tauri::command]
@@ -40,10 +72,96 @@ pub struct StoreConfig {
     visit_date: Option<String>,
     #[serde(rename = "visitTime")]
     visit_time: Option<String>,
-    carrier: String,
-    email: String,
+    #[serde(rename = "visitDates", default)]
+    visit_dates: Vec<String>, // 선호 순서대로의 방문 날짜 (있으면 visitDate 대신 사용)
+    #[serde(rename = "visitTimes", default)]
+    visit_times: Vec<preferences::TimeWindow>, // 선호 순서대로의 시간 "HH:mm" 또는 시간대 "HH:mm-HH:mm"
+    #[serde(default)]
+    carrier: pii::Carrier,
+    #[serde(default)]
+    email: pii::Email,
     #[serde(rename = "clientTime")]
     client_time: Option<String>, // 클라이언트 현재 시간 추가
+    #[serde(rename = "secretId", default)]
+    secret_id: Option<String>, // 암호화 저장소의 개인정보 id (설정 시 email/carrier 대신 사용)
+    #[serde(rename = "calendarCheck", default)]
+    calendar_check: Option<calendar::CalendarCheck>, // 예약 전 로컬 일정 충돌 확인
+    #[serde(rename = "notificationSinks", default)]
+    notification_sinks: Vec<sinks::SinkConfig>, // 실행 이벤트를 전달할 명령/파일/웹훅
+    #[serde(rename = "userActionTimeoutSecs", default)]
+    user_action_timeout_secs: Option<u64>, // PASS 인증 등 사용자 개입 대기 시간 (기본 120초)
+    #[serde(rename = "chooseSlot", default)]
+    choose_slot: bool, // 실행 중 예약 가능 목록에서 날짜/시간을 직접 선택
+    #[serde(rename = "contactFields", default)]
+    contact_fields: contact::ContactFields, // 이메일 외 연락처 폼 입력란 (name 속성 -> 값)
+    #[serde(default)]
+    locators: locators::LocatorOverrides, // 단계별 대체 로케이터 (없는 단계는 기본 목록)
+    #[serde(rename = "retryPolicies", default)]
+    retry_policies: retry::RetryPolicies, // 단계별 재시도 횟수/대기/제한 시간 (없는 단계는 기본 정책)
+    #[serde(default)]
+    pacing: pacing::PacingConfig, // 페이지 이동 최소 간격 및 429/5xx backoff
+    #[serde(rename = "networkMonitor", default)]
+    network_monitor: network::NetworkMonitorConfig, // 로딩 지연/실패 감지 및 새로고침 횟수
+    #[serde(rename = "recordHar", default)]
+    record_har: Option<har::HarConfig>, // 설정 시 네트워크 요청/응답을 HAR 로 기록 (쿠키/본문은 기본적으로 가림)
+    #[serde(rename = "recordedFlow", default)]
+    recorded_flow: Option<recorder::FlowDefinition>, // 흐름 기록에서 가져온 동작 (버튼/연락처 단계에서 기본 동작 대신 재생)
+}
+
+impl StoreConfig {
+    fn date_preferences(&self) -> Vec<String> {
+        if self.visit_dates.is_empty() {
+            self.visit_date.iter().cloned().collect()
+        } else {
+            self.visit_dates.clone()
+        }
+    }
+
+    fn time_preferences(&self) -> Vec<preferences::TimeWindow> {
+        if self.visit_times.is_empty() {
+            self.visit_time
+                .iter()
+                .filter_map(|t| preferences::TimeWindow::parse(t).ok())
+                .collect()
+        } else {
+            self.visit_times.clone()
+        }
+    }
+
+    fn locator_chain(&self, step: Step) -> Vec<locators::Locator> {
+        locators::chain(&self.locators, step)
+    }
+
+    // PASS 인증의 기본 전체 제한 시간은 userActionTimeoutSecs
+    fn retry_policy(&self, step: Step) -> retry::RetryPolicy {
+        if let Some(policy) = self.retry_policies.get(&step) {
+            return policy.clone();
+        }
+        match step {
+            Step::PassAuthentication => retry::RetryPolicy::pass_authentication(Duration::from_secs(
+                self.user_action_timeout_secs
+                    .unwrap_or(automation::DEFAULT_USER_ACTION_TIMEOUT_SECS),
+            )),
+            _ => retry::RetryPolicy::default_for(step),
+        }
+    }
+
+    // secret_id 가 있으면 암호화 저장소에서 이메일/통신사를 채움
+    fn resolve_personal_details(&mut self, store: &secrets::SecretStore) -> Result<(), String> {
+        if let Some(secret_id) = &self.secret_id {
+            let details = store.resolve(secret_id)?;
+            self.email = details.email;
+            self.carrier = details.carrier;
+            // 저장된 문의 내용은 설정 파일에 message 항목이 없을 때만 사용
+            if let Some(message) = details.message.filter(|m| !m.trim().is_empty()) {
+                self.contact_fields.entry("message".to_string()).or_insert(message);
+            }
+        }
+        if self.email.is_empty() || self.carrier.is_empty() {
+            return Err("이메일 또는 통신사 정보가 없습니다".to_string());
+        }
+        Ok(())
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -52,11 +170,16 @@ pub struct AutomationResult {
     message: String,
     timestamp: String,
     store_name: String,
+    run_id: Option<String>, // read_run_log 로 실행 로그를 조회할 때 사용
+    confirmation: ConfirmationStatus, // 예약번호 등을 찾지 못하면 unverified
+    preflight: Option<preflight::PreflightReport>, // 브라우저 실행 전 점검 결과
+    slot_selection: Option<preferences::SlotSelection>, // 실제로 선택한 날짜/시간과 선호 순위
+    contact_fields: Option<contact::ContactFillReport>, // 연락처 폼에 입력한 항목 이름
 }
 
 #[tauri::command]
 async fn setup_automation() -> Result<String, String> {
-    println!("🚀 자동화 환경 설정 시작 (chromiumoxide)...");
+    info!("🚀 자동화 환경 설정 시작 (chromiumoxide)...");
 
     // BrowserConfig를 빌드합니다.
     let _browser_config = BrowserConfig::builder()
@@ -69,7 +192,7 @@ async fn setup_automation() -> Result<String, String> {
 
     match Browser::launch(_browser_config).await {
         Ok((browser, mut handler)) => {
-            println!("✅ 브라우저 환경 확인 완료!");
+            info!("✅ 브라우저 환경 확인 완료!");
             
             // 핸들러를 백그라운드에서 실행
             let handler_task = tokio::spawn(async move {
@@ -81,7 +204,7 @@ async fn setup_automation() -> Result<String, String> {
             });
             
             // 브라우저 안정화 대기
-            println!("⏳ 브라우저 안정화 중...");
+            info!("⏳ 브라우저 안정화 중...");
             tokio::time::sleep(Duration::from_secs(3)).await;
             
             // 테스트 페이지 생성
@@ -90,16 +213,16 @@ async fn setup_automation() -> Result<String, String> {
                 browser.new_page("https://www.google.com")
             ).await {
                 Ok(Ok(_page)) => {
-                    println!("✅ 테스트 페이지 생성 성공");
+                    info!("✅ 테스트 페이지 생성 성공");
                     tokio::time::sleep(Duration::from_secs(5)).await;
                 }
                 _ => {
-                    println!("⚠️ 테스트 페이지 생성 실패, 하지만 브라우저는 정상");
+                    warn!("⚠️ 테스트 페이지 생성 실패, 하지만 브라우저는 정상");
                 }
             }
             
             // 브라우저를 5초 더 유지한 후 종료
-            println!("🕐 5초 후 브라우저가 종료됩니다...");
+            info!("🕐 5초 후 브라우저가 종료됩니다...");
             tokio::time::sleep(Duration::from_secs(5)).await;
             
             // 핸들러 태스크 종료 대기 (브라우저는 자동으로 닫힘)
@@ -110,7 +233,7 @@ async fn setup_automation() -> Result<String, String> {
         }
         Err(e) => {
             let error_msg = format!("❌ 브라우저 환경 설정 실패: {}", e);
-            println!("{}", error_msg);
+            error!("{}", error_msg);
             log_user_action("환경 설정", &format!("실패: {}", e));
             Err(error_msg)
         }
@@ -118,44 +241,165 @@ async fn setup_automation() -> Result<String, String> {
 }
 
 #[tauri::command]
-async fn run_single_automation(store_config: StoreConfig) -> Result<AutomationResult, String> {
-    println!("🚀 개별 자동화 실행 시작: {}", store_config.store_name);
-    log_user_action("자동화 시작", &format!("매장: {}", store_config.store_name));
+async fn run_single_automation(
+    app: tauri::AppHandle,
+    mut store_config: StoreConfig,
+) -> Result<AutomationResult, String> {
+    let run_id = logging::new_run_id(&store_config.store_name);
+    let run_span = info_span!(
+        logging::RUN_SPAN_NAME,
+        run_id = %run_id,
+        store = %store_config.store_name
+    );
+
+    async move {
+        info!("🚀 개별 자동화 실행 시작: {}", store_config.store_name);
+        let run = RunContext::new(
+            app.clone(),
+            run_id.clone(),
+            store_config.store_name.clone(),
+            &store_config.notification_sinks,
+        );
+        log_user_action("자동화 시작", &format!("매장: {}", store_config.store_name));
+
+        let mut preflight_report = None;
+        let result = match store_config.resolve_personal_details(&app.state::<secrets::SecretStore>()) {
+            Ok(()) => {
+                let report = preflight::run(&store_config);
+                let blocked = report.is_blocked();
+                let blocking = report.blocking_messages();
+                preflight_report = Some(report);
+                if blocked {
+                    Err(format!("사전 점검 실패: {}", blocking.join("; ")))
+                } else {
+                    run_rolex_automation(&store_config, &run).await
+                }
+            }
+            Err(e) => Err(e),
+        };
 
-    let result = run_rolex_automation(&store_config).await;
+        let slot_selection = run.selection();
+        let contact_fields = run.contact_report();
+        let automation_result = match result {
+            Ok((success_msg, confirmation)) => {
+                let timestamp = chrono::Utc::now().to_rfc3339();
+                log_user_action("자동화 성공", &format!("매장: {}", store_config.store_name));
+                AutomationResult {
+                    success: true,
+                    message: success_msg,
+                    timestamp,
+                    store_name: store_config.store_name.clone(),
+                    run_id: Some(run_id.clone()),
+                    confirmation: ConfirmationStatus::Confirmed(confirmation),
+                    preflight: preflight_report,
+                    slot_selection: slot_selection.clone(),
+                    contact_fields: contact_fields.clone(),
+                }
+            }
+            Err(error_msg) => {
+                let timestamp = chrono::Utc::now().to_rfc3339();
+                log_user_action(
+                    "자동화 실패",
+                    &format!("매장: {}, 오류: {}", store_config.store_name, error_msg),
+                );
+                AutomationResult {
+                    success: false,
+                    confirmation: ConfirmationStatus::unverified(error_msg.clone()),
+                    message: error_msg,
+                    timestamp,
+                    store_name: store_config.store_name.clone(),
+                    run_id: Some(run_id.clone()),
+                    preflight: preflight_report,
+                    slot_selection: slot_selection.clone(),
+                    contact_fields: contact_fields.clone(),
+                }
+            }
+        };
 
-    match result {
-        Ok(success_msg) => {
-            let timestamp = chrono::Utc::now().to_rfc3339();
-            log_user_action("자동화 성공", &format!("매장: {}", store_config.store_name));
-            Ok(AutomationResult {
-                success: true,
-                message: success_msg,
-                timestamp,
-                store_name: store_config.store_name,
-            })
+        if automation_result.success {
+            run.emit(RunEventKind::Succeeded, automation_result.message.clone());
+        } else if run.is_cancelled() {
+            run.emit(RunEventKind::Cancelled, automation_result.message.clone());
+        } else {
+            run.emit(RunEventKind::Failed, automation_result.message.clone());
         }
-        Err(error_msg) => {
-            let timestamp = chrono::Utc::now().to_rfc3339();
-            log_user_action(
-                "자동화 실패",
-                &format!("매장: {}, 오류: {}", store_config.store_name, error_msg),
-            );
-            Ok(AutomationResult {
-                success: false,
-                message: error_msg,
-                timestamp,
-                store_name: store_config.store_name,
-            })
+        run.finish();
+
+        // 선호 목록에서 실제로 고른 값이 있으면 그 값을 기록
+        let visit_date = slot_selection
+            .as_ref()
+            .and_then(|s| s.date.clone())
+            .or_else(|| store_config.date_preferences().first().cloned());
+        let visit_time = slot_selection
+            .as_ref()
+            .and_then(|s| s.time.clone())
+            .or_else(|| {
+                store_config
+                    .time_preferences()
+                    .first()
+                    .map(|w| w.start.format("%H:%M").to_string())
+            });
+
+        // HAR 기록이 켜져 있으면 실행 산출물 디렉토리에 저장
+        let har_entries = run.har_entries();
+        let har_file = if store_config.record_har.is_some() && !har_entries.is_empty() {
+            let saved = app
+                .path()
+                .app_data_dir()
+                .map_err(|e| e.to_string())
+                .and_then(|dir| history::run_dir(&dir, &run_id))
+                .and_then(|dir| har::save(&dir, har_entries));
+            match saved {
+                Ok(path) => {
+                    info!("📼 네트워크 기록(HAR) 저장: {}", path.display());
+                    Some(path.display().to_string())
+                }
+                Err(e) => {
+                    warn!("⚠️ HAR 저장 실패: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // 실행 기록 저장 (캘린더 내보내기 등에서 run_id 로 조회)
+        let record = history::RunRecord {
+            run_id,
+            store_name: store_config.store_name,
+            visit_date,
+            visit_time,
+            success: automation_result.success,
+            message: automation_result.message.clone(),
+            timestamp: automation_result.timestamp.clone(),
+            confirmation: automation_result.confirmation.clone(),
+            preflight: automation_result.preflight.clone(),
+            slot_selection: automation_result.slot_selection.clone(),
+            contact_fields: automation_result.contact_fields.clone(),
+            network: run.network_stats(),
+            har_file,
+        };
+        match app.path().app_data_dir() {
+            Ok(dir) => {
+                if let Err(e) = history::save_record(&dir, &record) {
+                    warn!("⚠️ {}", e);
+                }
+            }
+            Err(e) => warn!("⚠️ 앱 데이터 디렉토리를 찾을 수 없습니다: {}", e),
         }
+
+        Ok(automation_result)
     }
+    .instrument(run_span)
+    .await
 }
 
 #[tauri::command]
 async fn run_multiple_automation(
+    app: tauri::AppHandle,
     store_configs: Vec<StoreConfig>,
 ) -> Result<Vec<AutomationResult>, String> {
-    println!("🚀 다중 자동화 실행 시작: {} 개 매장", store_configs.len());
+    info!("🚀 다중 자동화 실행 시작: {} 개 매장", store_configs.len());
     log_user_action(
         "다중 자동화 시작",
         &format!("{} 개 매장", store_configs.len()),
@@ -167,8 +411,9 @@ async fn run_multiple_automation(
         // 각 매장마다 2초씩 간격을 두고 시작 (브라우저 안정성 확보)
         tokio::time::sleep(Duration::from_millis(2000 * index as u64)).await;
         
+        let app = app.clone();
         let task = tokio::spawn(async move { 
-            run_single_automation(store_config).await 
+            run_single_automation(app, store_config).await 
         });
         tasks.push(task);
     }
@@ -181,26 +426,38 @@ async fn run_multiple_automation(
                 results.push(automation_result);
             }
             Ok(Err(error)) => {
+                let message = format!("실행 실패: {}", error);
                 results.push(AutomationResult {
                     success: false,
-                    message: format!("실행 실패: {}", error),
+                    confirmation: ConfirmationStatus::unverified(message.clone()),
+                    message,
                     timestamp: chrono::Utc::now().to_rfc3339(),
                     store_name: "Unknown".to_string(),
+                    run_id: None,
+                    preflight: None,
+                    slot_selection: None,
+                    contact_fields: None,
                 });
             }
             Err(join_error) => {
+                let message = format!("작업 실패: {}", join_error);
                 results.push(AutomationResult {
                     success: false,
-                    message: format!("작업 실패: {}", join_error),
+                    confirmation: ConfirmationStatus::unverified(message.clone()),
+                    message,
                     timestamp: chrono::Utc::now().to_rfc3339(),
                     store_name: "Unknown".to_string(),
+                    run_id: None,
+                    preflight: None,
+                    slot_selection: None,
+                    contact_fields: None,
                 });
             }
         }
     }
 
     let success_count = results.iter().filter(|r| r.success).count();
-    println!(
+    info!(
         "✅ 다중 자동화 완료: {} 개 매장 (성공: {}, 실패: {})",
         results.len(),
         success_count,
@@ -221,27 +478,520 @@ async fn run_multiple_automation(
 }
 
 #[tauri::command]
-async fn stop_automation(store_name: String) -> Result<String, String> {
-    println!("🛑 {} 자동화 중지 요청", store_name);
+async fn stop_automation(app: tauri::AppHandle, store_name: String) -> Result<String, String> {
+    info!("🛑 {} 자동화 중지 요청", store_name);
     log_user_action("자동화 중지", &format!("매장: {}", store_name));
+    cancel_runs(&app, Some(&store_name));
 
-    // 현재는 브라우저가 사용자에 의해 수동으로 닫히도록 함
-    // 실제 구현에서는 프로세스 ID를 추적하여 종료할 수 있음
-
-    println!("✅ {} 자동화 중지 신호 전송", store_name);
+    info!("✅ {} 자동화 중지 신호 전송", store_name);
     Ok(format!(
-        "{} 자동화 중지 신호를 전송했습니다. 브라우저를 수동으로 닫아주세요.",
+        "{} 자동화 중지 신호를 전송했습니다. 진행 중인 단계가 끝나면 브라우저를 닫고 중지합니다.",
         store_name
     ))
 }
 
 #[tauri::command]
-async fn stop_all_automation() -> Result<String, String> {
-    println!("🛑 모든 자동화 중지 요청");
+async fn stop_all_automation(app: tauri::AppHandle) -> Result<String, String> {
+    info!("🛑 모든 자동화 중지 요청");
     log_user_action("전체 자동화 중지", "사용자 요청");
+    cancel_runs(&app, None);
+
+    info!("✅ 모든 자동화 중지 신호 전송");
+    Ok("모든 자동화 중지 신호를 전송했습니다. 진행 중인 단계가 끝나면 브라우저를 닫고 중지합니다.".to_string())
+}
+
+// 실행 중인 자동화에 중지 요청 (store_name 이 없으면 전체)
+// 각 실행은 다음 체크포인트에서 끝나며, 중지 이벤트는 실행이 실제로 끝날 때 발행됨
+fn cancel_runs(app: &tauri::AppHandle, store_name: Option<&str>) {
+    let runs = app.state::<events::ActiveRuns>();
+    for (run_id, _) in runs.list(store_name) {
+        if let Some((_, control)) = runs.control(&run_id) {
+            if let Err(e) = control.cancel() {
+                warn!("⚠️ 자동화 중지 실패 ({}): {}", run_id, e);
+            }
+        }
+    }
+}
+
+fn app_notification_preferences_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("앱 데이터 디렉토리를 찾을 수 없습니다: {}", e))?;
+    Ok(notify::preferences_path(&dir))
+}
+
+#[tauri::command]
+async fn get_notification_preferences(
+    app: tauri::AppHandle,
+) -> Result<notify::NotificationPreferences, String> {
+    let settings = app.state::<notify::NotificationSettings>();
+    let preferences = settings.0.lock().map_err(|e| e.to_string())?;
+    Ok(preferences.clone())
+}
+
+#[tauri::command]
+async fn set_notification_preferences(
+    app: tauri::AppHandle,
+    preferences: notify::NotificationPreferences,
+) -> Result<(), String> {
+    notify::save_preferences(&app_notification_preferences_path(&app)?, &preferences)?;
+    let settings = app.state::<notify::NotificationSettings>();
+    *settings.0.lock().map_err(|e| e.to_string())? = preferences;
+    Ok(())
+}
+
+#[derive(Serialize, Debug)]
+struct SinkTestResult {
+    sink: String,
+    success: bool,
+    error: Option<String>,
+}
+
+// 설정한 전달 대상에 테스트 이벤트를 보내 연결을 확인
+#[tauri::command]
+async fn test_notification_sinks(sinks: Vec<sinks::SinkConfig>) -> Result<Vec<SinkTestResult>, String> {
+    let event = events::RunEvent {
+        run_id: logging::new_run_id("test"),
+        store_name: "테스트".to_string(),
+        kind: RunEventKind::Succeeded,
+        message: "알림 전달 테스트".to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        window: None,
+    };
+    // 이벤트 필터와 상관없이 모든 대상에 전달
+    let sinks: Vec<sinks::SinkConfig> = sinks
+        .into_iter()
+        .map(|sink| sinks::SinkConfig { events: Vec::new(), ..sink })
+        .collect();
+    let results = sinks::deliver_all(&sinks::build_all(&sinks), &event).await;
+    Ok(results
+        .into_iter()
+        .map(|(sink, result)| SinkTestResult {
+            sink,
+            success: result.is_ok(),
+            error: result.err(),
+        })
+        .collect())
+}
+
+fn run_control(
+    app: &tauri::AppHandle,
+    run_id: &str,
+) -> Result<(String, std::sync::Arc<control::RunControl>), String> {
+    app.state::<events::ActiveRuns>()
+        .control(run_id)
+        .ok_or_else(|| format!("실행 중인 자동화가 없습니다: {}", run_id))
+}
+
+fn publish_control_event(app: &tauri::AppHandle, run_id: String, store_name: String, kind: RunEventKind, message: &str) {
+    events::publish(
+        app,
+        &events::RunEvent {
+            run_id,
+            store_name,
+            kind,
+            message: message.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            window: None,
+        },
+    );
+}
+
+#[tauri::command]
+async fn list_active_runs(app: tauri::AppHandle) -> Result<Vec<events::ActiveRunInfo>, String> {
+    Ok(app.state::<events::ActiveRuns>().info())
+}
+
+// 다음 단계로 넘어가기 전에 멈춤
+#[tauri::command]
+async fn pause_automation(app: tauri::AppHandle, run_id: String) -> Result<(), String> {
+    let (store_name, control) = run_control(&app, &run_id)?;
+    control.pause()?;
+    log_user_action("자동화 일시정지", &format!("매장: {}", store_name));
+    publish_control_event(&app, run_id, store_name, RunEventKind::Paused, "다음 단계 전에서 일시정지합니다");
+    Ok(())
+}
+
+// 사용자가 브라우저를 직접 조작 - resume_automation 으로 이어갈 단계를 지정
+#[tauri::command]
+async fn take_over_automation(app: tauri::AppHandle, run_id: String) -> Result<(), String> {
+    let (store_name, control) = run_control(&app, &run_id)?;
+    control.take_over()?;
+    log_user_action("수동 조작 전환", &format!("매장: {}", store_name));
+    publish_control_event(
+        &app,
+        run_id.clone(),
+        store_name,
+        RunEventKind::Paused,
+        "수동 조작 모드 - 브라우저를 직접 조작한 뒤 이어갈 단계를 지정해 재개해주세요",
+    );
+    // 사용자가 조작할 수 있도록 브라우저 창을 앞으로 가져옴
+    if let Err(e) = events::focus_browser(&app, &run_id).await {
+        warn!("⚠️ {}", e);
+    }
+    Ok(())
+}
+
+// from_step 이 없으면 멈춘 단계부터 이어서 진행
+#[tauri::command]
+async fn resume_automation(
+    app: tauri::AppHandle,
+    run_id: String,
+    from_step: Option<Step>,
+) -> Result<(), String> {
+    let (store_name, control) = run_control(&app, &run_id)?;
+    control.resume(from_step)?;
+    let message = match from_step {
+        Some(step) => format!("{} 단계부터 재개합니다", step.as_str()),
+        None => "자동화를 재개합니다".to_string(),
+    };
+    log_user_action("자동화 재개", &format!("매장: {}, {}", store_name, message));
+    publish_control_event(&app, run_id, store_name, RunEventKind::Resumed, &message);
+    Ok(())
+}
+
+// 실행 중인 브라우저의 날짜/시간 선택 폼에서 선택 가능한 항목 조회
+#[tauri::command]
+async fn list_available_slots(
+    app: tauri::AppHandle,
+    run_id: String,
+) -> Result<page_state::AvailableSlots, String> {
+    let page = app
+        .state::<events::ActiveRuns>()
+        .page(&run_id)
+        .ok_or_else(|| format!("실행 중인 브라우저가 없습니다: {}", run_id))?;
+    page_state::read_available_slots(&page).await
+}
+
+// list_available_slots 에서 고른 날짜/시간으로 실행을 이어감
+#[tauri::command]
+async fn choose_slot(
+    app: tauri::AppHandle,
+    run_id: String,
+    date: Option<String>,
+    time: Option<String>,
+) -> Result<(), String> {
+    let (store_name, control) = run_control(&app, &run_id)?;
+    log_user_action(
+        "예약 시간 선택",
+        &format!("매장: {}, 날짜: {:?}, 시간: {:?}", store_name, date, time),
+    );
+    control.choose_slot(control::SlotChoice { date, time })
+}
+
+#[tauri::command]
+async fn focus_browser_window(app: tauri::AppHandle, run_id: String) -> Result<(), String> {
+    events::focus_browser(&app, &run_id).await
+}
+
+fn app_config_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("앱 데이터 디렉토리를 찾을 수 없습니다: {}", e))?;
+    Ok(config::config_path(&dir))
+}
+
+#[tauri::command]
+async fn load_automation_config(app: tauri::AppHandle) -> Result<config::LoadedConfig, String> {
+    let path = app_config_path(&app)?;
+    let loaded = config::load_config_file(&path)?;
+    log_user_action("설정 불러오기", &format!("{} 개 매장", loaded.stores.len()));
+
+    Ok(config::LoadedConfig {
+        store_configs: loaded.to_store_configs(),
+        config: loaded,
+    })
+}
+
+#[tauri::command]
+async fn save_automation_config(
+    app: tauri::AppHandle,
+    config: config::ConfigFile,
+) -> Result<String, String> {
+    let path = app_config_path(&app)?;
+    config::save_config_file(&path, &config)?;
+    log_user_action("설정 저장", &format!("{} 개 매장", config.stores.len()));
+    Ok(format!("설정을 저장했습니다: {}", path.display()))
+}
+
+// 저장하지 않고 검사만 수행 (스키마 오류 + 로컬 일정 충돌)
+#[tauri::command]
+async fn validate_automation_config(
+    config: config::ConfigFile,
+) -> Result<config::ValidationReport, String> {
+    Ok(config.validation_report())
+}
+
+// automation_config_example.json 형식의 외부 파일을 검사한 뒤 앱 데이터 디렉토리로 가져옴
+#[tauri::command]
+async fn import_automation_config(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<config::LoadedConfig, String> {
+    let imported = config::load_config_file(std::path::Path::new(&path))?;
+    config::save_config_file(&app_config_path(&app)?, &imported)?;
+    log_user_action("설정 가져오기", &format!("{} 개 매장", imported.stores.len()));
+
+    Ok(config::LoadedConfig {
+        store_configs: imported.to_store_configs(),
+        config: imported,
+    })
+}
+
+// 셀렉터 점검 - snapshot_path 가 있으면 저장된 HTML, 없으면 매장의 authUrl/reserveUrl 을 열어 확인
+#[tauri::command]
+async fn check_selectors(
+    store_config: Option<StoreConfig>,
+    snapshot_path: Option<String>,
+) -> Result<selectors::SelectorHealthReport, String> {
+    let report = match (snapshot_path, store_config) {
+        // HTML 만 지정한 경우에도 매장 설정이 있으면 그 매장의 로케이터로 점검
+        (Some(path), store_config) => {
+            let overrides = store_config.map(|c| c.locators).unwrap_or_default();
+            selectors::check_snapshot(std::path::Path::new(&path), &overrides).await?
+        }
+        (None, Some(store_config)) => {
+            selectors::check_urls(&store_config.auth_url, &store_config.reserve_url, &store_config.locators)
+                .await?
+        }
+        (None, None) => return Err("점검할 매장 또는 HTML 파일을 지정해주세요".to_string()),
+    };
+
+    for check in report.checks.iter().filter(|c| c.status != selectors::SelectorStatus::Ok) {
+        warn!("⚠️ 셀렉터 {} ({}): {:?}", check.name, check.selector, check.status);
+    }
+    log_user_action(
+        "셀렉터 점검",
+        &format!("{} - {}", report.sources.join(", "), if report.healthy { "정상" } else { "문제 있음" }),
+    );
+    Ok(report)
+}
+
+fn app_data_dir(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| format!("앱 데이터 디렉토리를 찾을 수 없습니다: {}", e))
+}
+
+// 예약 페이지의 현재 구조를 매장별 기준으로 저장 (snapshot_path 가 있으면 저장된 HTML 에서 수집)
+#[tauri::command]
+async fn capture_dom_baseline(
+    app: tauri::AppHandle,
+    store_config: StoreConfig,
+    snapshot_path: Option<String>,
+) -> Result<drift::DomFingerprint, String> {
+    let fingerprint = drift::capture(
+        &store_config.store_name,
+        &store_config.reserve_url,
+        snapshot_path.as_deref().map(std::path::Path::new),
+    )
+    .await?;
+    drift::save_baseline(&app_data_dir(&app)?, &fingerprint)?;
+    log_user_action("기준 구조 저장", &format!("매장: {}", store_config.store_name));
+    Ok(fingerprint)
+}
+
+// 저장된 기준 구조와 현재 페이지 구조를 비교
+#[tauri::command]
+async fn check_dom_drift(
+    app: tauri::AppHandle,
+    store_config: StoreConfig,
+    snapshot_path: Option<String>,
+) -> Result<drift::DriftReport, String> {
+    let baseline = drift::load_baseline(&app_data_dir(&app)?, &store_config.store_name)?;
+    let current = drift::capture(
+        &store_config.store_name,
+        &store_config.reserve_url,
+        snapshot_path.as_deref().map(std::path::Path::new),
+    )
+    .await?;
+    let report = drift::diff(&baseline, &current);
+    if report.drifted {
+        warn!("⚠️ 페이지 구조가 바뀌었습니다\n{}", report.summary);
+    } else {
+        info!("✅ 페이지 구조 변경 없음: {}", store_config.store_name);
+    }
+    log_user_action(
+        "구조 변경 점검",
+        &format!("매장: {}, 변경: {}", store_config.store_name, report.drifted),
+    );
+    Ok(report)
+}
+
+// 흐름 기록 시작 - 열린 브라우저에서 사용자가 예약 흐름을 직접 진행
+#[tauri::command]
+async fn start_flow_recording(app: tauri::AppHandle, url: String) -> Result<(), String> {
+    url::Url::parse(&url).map_err(|_| format!("잘못된 URL: {}", url))?;
+    app.state::<recorder::FlowRecorder>().start(&url).await?;
+    log_user_action("흐름 기록 시작", &url);
+    Ok(())
+}
+
+// 흐름 기록 종료 - path 가 없으면 앱 데이터 디렉토리의 flows/ 에 저장
+#[tauri::command]
+async fn stop_flow_recording(
+    app: tauri::AppHandle,
+    path: Option<String>,
+) -> Result<recorder::SavedFlow, String> {
+    let flow = app.state::<recorder::FlowRecorder>().stop().await?;
+    let path = match path {
+        Some(p) => std::path::PathBuf::from(p),
+        None => recorder::flows_dir(&app_data_dir(&app)?)
+            .join(format!("flow-{}.json", chrono::Local::now().format("%Y%m%d-%H%M%S"))),
+    };
+    recorder::save_flow(&path, &flow)?;
+    log_user_action("흐름 기록 종료", &format!("{}개 동작", flow.actions.len()));
+    Ok(recorder::SavedFlow {
+        path: path.to_string_lossy().to_string(),
+        flow,
+    })
+}
+
+// 편집한 흐름 정의를 저장된 설정의 recordedFlow 로 가져옴 - 버튼/연락처 단계에서 기본 동작 대신 재생되고,
+// 단계별 첫 클릭의 로케이터는 재생에 실패했을 때 기본 동작이 쓰도록 locators 에도 반영
+// 날짜/시간 클릭, 시작 페이지 이동 등 재생하지 않는 동작은 이유와 함께 돌려줌
+#[tauri::command]
+async fn import_recorded_flow(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<recorder::ImportedFlow, String> {
+    let flow = recorder::load_flow(std::path::Path::new(&path))?;
+    let (plan, skipped) = replay::ReplayPlan::new(&flow);
+    for action in &skipped {
+        warn!(
+            "⚠️ 흐름 동작 {}번({:?} '{}') 제외: {}",
+            action.index, action.kind, action.label, action.reason
+        );
+    }
+    if plan.is_empty() {
+        return Err(format!(
+            "흐름에 재생할 수 있는 동작이 없습니다 (동작 {}개 모두 제외)",
+            skipped.len()
+        ));
+    }
+
+    let config_path = app_config_path(&app)?;
+    let mut config = config::load_config_file(&config_path)?;
+    let imported_steps = plan.steps();
+    let steps: Vec<&str> = imported_steps.iter().map(|s| s.as_str()).collect();
+    log_user_action(
+        "흐름 가져오기",
+        &format!("단계: {}, 제외한 동작: {}개", steps.join(", "), skipped.len()),
+    );
+    config.locators.extend(flow.to_locator_overrides());
+    config.recorded_flow = Some(flow);
+    config::save_config_file(&config_path, &config)?;
+
+    Ok(recorder::ImportedFlow {
+        loaded: config::LoadedConfig {
+            store_configs: config.to_store_configs(),
+            config,
+        },
+        imported_steps,
+        skipped,
+    })
+}
+
+fn app_log_dir(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_log_dir()
+        .map_err(|e| format!("로그 디렉토리를 찾을 수 없습니다: {}", e))
+}
+
+#[tauri::command]
+async fn list_run_logs(app: tauri::AppHandle) -> Result<Vec<logging::RunLogInfo>, String> {
+    logging::list_run_logs(&app_log_dir(&app)?)
+}
+
+#[tauri::command]
+async fn read_run_log(
+    app: tauri::AppHandle,
+    run_id: String,
+    tail_lines: Option<usize>,
+) -> Result<String, String> {
+    logging::read_run_log(&app_log_dir(&app)?, &run_id, tail_lines)
+}
+
+// 확정된 실행의 .ics 파일을 만들어 지정 경로(없으면 실행 산출물 디렉토리)에 저장
+#[tauri::command]
+async fn export_run_calendar(
+    app: tauri::AppHandle,
+    run_id: String,
+    path: Option<String>,
+) -> Result<String, String> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("앱 데이터 디렉토리를 찾을 수 없습니다: {}", e))?;
+    let record = history::load_record(&data_dir, &run_id)?;
+    let ics = calendar::build_ics(&record)?;
+
+    let path = match path {
+        Some(p) => std::path::PathBuf::from(p),
+        None => history::run_dir(&data_dir, &run_id)?.join("appointment.ics"),
+    };
+    std::fs::write(&path, ics)
+        .map_err(|e| format!("캘린더 파일을 저장할 수 없습니다 ({}): {}", path.display(), e))?;
+    log_user_action("캘린더 내보내기", &format!("매장: {}", record.store_name));
+    Ok(path.to_string_lossy().to_string())
+}
+
+fn app_secrets_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("앱 데이터 디렉토리를 찾을 수 없습니다: {}", e))?;
+    Ok(secrets::secrets_path(&dir))
+}
+
+#[tauri::command]
+async fn unlock_secret_store(
+    app: tauri::AppHandle,
+    passphrase: String,
+) -> Result<usize, String> {
+    let path = app_secrets_path(&app)?;
+    let count = app.state::<secrets::SecretStore>().unlock(&path, &passphrase)?;
+    log_user_action("개인정보 저장소 잠금 해제", &format!("{} 개 항목", count));
+    Ok(count)
+}
 
-    println!("✅ 모든 자동화 중지 신호 전송");
-    Ok("모든 자동화 중지 신호를 전송했습니다. 브라우저들을 수동으로 닫아주세요.".to_string())
+#[tauri::command]
+async fn lock_secret_store(app: tauri::AppHandle) -> Result<(), String> {
+    app.state::<secrets::SecretStore>().lock();
+    log_user_action("개인정보 저장소 잠금", "사용자 요청");
+    Ok(())
+}
+
+#[tauri::command]
+async fn is_secret_store_unlocked(app: tauri::AppHandle) -> Result<bool, String> {
+    Ok(app.state::<secrets::SecretStore>().is_unlocked())
+}
+
+#[tauri::command]
+async fn list_personal_details(
+    app: tauri::AppHandle,
+) -> Result<Vec<secrets::PersonalDetailsSummary>, String> {
+    app.state::<secrets::SecretStore>().list()
+}
+
+#[tauri::command]
+async fn save_personal_details(
+    app: tauri::AppHandle,
+    id: Option<String>,
+    details: secrets::PersonalDetails,
+) -> Result<String, String> {
+    let id = app.state::<secrets::SecretStore>().put(id, details)?;
+    log_user_action("개인정보 저장", &format!("id: {}", id));
+    Ok(id)
+}
+
+#[tauri::command]
+async fn delete_personal_details(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    app.state::<secrets::SecretStore>().remove(&id)?;
+    log_user_action("개인정보 삭제", &format!("id: {}", id));
+    Ok(())
 }
 
 #[allow(dead_code)]
@@ -254,13 +1004,56 @@ struct AutomationConfig {
     email: String,
 }
 
-async fn run_rolex_automation(config: &StoreConfig) -> Result<String, String> {
-    println!("🚀 {} 자동화 시작", config.store_name);
-    println!("📧 이메일: {}", config.email);
-    println!("📱 통신사: {}", config.carrier);
-    println!("📅 예약날짜: {:?}", config.visit_date);
-    println!("🕐 예약시간: {:?}", config.visit_time);
-    println!("⏰ 시작시간: {:?}", config.start_time);
+// 실행 로그에 단계/시도 정보를 남기기 위한 스팬
+fn step_span(step: &'static str, attempt: u32) -> tracing::Span {
+    info_span!("step", step, attempt)
+}
+
+// 예약 가능한 날짜/시간 목록을 앱에 알리고 사용자가 choose_slot 으로 고를 때까지 대기
+async fn ask_slot_choice(
+    page: &chromiumoxide::Page,
+    run: &RunContext,
+    timeout: Duration,
+    pick_time: bool,
+) -> Option<control::SlotChoice> {
+    let what = if pick_time { "시간" } else { "날짜" };
+    let slots = match page_state::read_available_slots(page).await {
+        Ok(slots) => slots,
+        Err(e) => {
+            warn!("⚠️ {}", e);
+            return None;
+        }
+    };
+    let available = if pick_time { &slots.times } else { &slots.dates };
+    if available.is_empty() {
+        warn!("⚠️ 선택 가능한 {}이 없습니다", what);
+        return None;
+    }
+
+    info!("📋 선택 가능한 {}: {}", what, available.join(", "));
+    run.emit(
+        RunEventKind::ChoiceRequired,
+        format!("예약 가능한 {} {}개 중 하나를 선택해주세요", what, available.len()),
+    );
+    let choice = run.wait_for_slot_choice(slots, timeout).await;
+    match &choice {
+        Some(choice) => info!("✅ 사용자 선택: 날짜 {:?}, 시간 {:?}", choice.date, choice.time),
+        None if run.is_cancelled() => info!("🛑 중지 요청으로 {} 선택 대기를 끝냅니다", what),
+        None => warn!("⚠️ {}초 안에 {}을 선택하지 않아 설정값으로 진행합니다", timeout.as_secs(), what),
+    }
+    choice
+}
+
+async fn run_rolex_automation(
+    config: &StoreConfig,
+    run: &RunContext,
+) -> Result<(String, Confirmation), String> {
+    info!("🚀 {} 자동화 시작", config.store_name);
+    info!("📧 이메일: {}", config.email);
+    info!("📱 통신사: {}", config.carrier);
+    info!("📅 예약날짜: {:?}", config.visit_date);
+    info!("🕐 예약시간: {:?}", config.visit_time);
+    info!("⏰ 시작시간: {:?}", config.start_time);
 
     // 병렬 실행을 위한 고유한 사용자 데이터 디렉토리 생성
     let unique_id = format!("{}-{}", 
@@ -302,8 +1095,8 @@ async fn run_rolex_automation(config: &StoreConfig) -> Result<String, String> {
         .build()
         .map_err(|e| format!("{:?}", e))?;
 
-    println!("🔥 {} 브라우저 시작 중...", config.store_name);
-    let (browser, mut handler) = Browser::launch(browser_config)
+    info!("🔥 {} 브라우저 시작 중...", config.store_name);
+    let (mut browser, mut handler) = Browser::launch(browser_config)
         .await
         .map_err(|e| format!("{:?}", e))?;
     
@@ -325,32 +1118,33 @@ async fn run_rolex_automation(config: &StoreConfig) -> Result<String, String> {
     });
 
     // 브라우저 안정화 (병렬 실행 시 더 긴 대기)
-    println!("⏳ 브라우저 안정화 중...");
+    info!("⏳ 브라우저 안정화 중...");
     tokio::time::sleep(Duration::from_secs(3)).await;
-    println!("✅ {} 브라우저 시작 완료", config.store_name);
+    info!("✅ {} 브라우저 시작 완료", config.store_name);
 
     // 새 페이지 생성 (더 안전한 방식)
-    println!("📄 새 페이지 생성 중...");
+    info!("📄 새 페이지 생성 중...");
     let page = match tokio::time::timeout(
         Duration::from_secs(15),
         browser.new_page("about:blank")
     ).await {
         Ok(Ok(p)) => {
-            println!("✅ 새 페이지 생성 완료");
+            info!("✅ 새 페이지 생성 완료");
+            run.set_page(&p);
             tokio::time::sleep(Duration::from_secs(2)).await; // 페이지 안정화 시간 증가
             p
         }
         Ok(Err(e)) => {
-            println!("❌ 새 페이지 생성 실패: {:?}", e);
-            println!("🕐 브라우저를 10초 동안 유지합니다...");
+            error!("❌ 새 페이지 생성 실패: {:?}", e);
+            info!("🕐 브라우저를 10초 동안 유지합니다...");
             tokio::time::sleep(Duration::from_secs(10)).await;
             let _ = handler_task.await;
             let _ = std::fs::remove_dir_all(&user_data_dir);
             return Err("새 페이지 생성 실패".to_string());
         }
         Err(_) => {
-            println!("❌ 새 페이지 생성 타임아웃");
-            println!("🕐 브라우저를 10초 동안 유지합니다...");
+            error!("❌ 새 페이지 생성 타임아웃");
+            info!("🕐 브라우저를 10초 동안 유지합니다...");
             tokio::time::sleep(Duration::from_secs(10)).await;
             let _ = handler_task.await;
             let _ = std::fs::remove_dir_all(&user_data_dir);
@@ -358,258 +1152,528 @@ async fn run_rolex_automation(config: &StoreConfig) -> Result<String, String> {
         }
     };
 
-    println!("✅ 인증 URL로 이동합니다: {}", config.auth_url);
+    // PASS 인증 팝업 창/iframe 추적 시작
+    let tracker = targets::TargetTracker::start(&browser, &page).await;
+    // 서버 응답(429/5xx)에 따른 요청 간격 조절
+    let pacer = pacing::Pacer::start(&page, config.pacing.clone()).await;
+    // 예약 페이지 로딩 지연/실패 감지 (통계는 실행 기록에 저장)
+    let monitor = network::NetworkMonitor::start(&page, config.network_monitor.clone(), run.network_recorder()).await;
+    // 페이지 콘솔 출력/JS 예외 수집 (각 단계가 끝날 때 해당 단계 로그로 기록)
+    let console = console::ConsoleCapture::start(&page).await;
+    // 네트워크 요청/응답 HAR 기록 (실행이 끝나면 실행 산출물 디렉토리에 저장)
+    let _har = match &config.record_har {
+        Some(options) => Some(har::HarRecorder::start(&page, options.clone(), run.har_recorder()).await),
+        None => None,
+    };
+    // 가져온 흐름 기록이 있으면 버튼/연락처 단계에서 기본 동작 대신 재생
+    let replay_plan = config.recorded_flow.as_ref().map(|flow| {
+        let (plan, skipped) = replay::ReplayPlan::new(flow);
+        let steps: Vec<&str> = plan.steps().iter().map(|s| s.as_str()).collect();
+        info!("▶️ 기록한 흐름 재생 단계: {} (제외한 동작 {}개)", steps.join(", "), skipped.len());
+        plan
+    });
+    let user_action_timeout = Duration::from_secs(
+        config
+            .user_action_timeout_secs
+            .unwrap_or(automation::DEFAULT_USER_ACTION_TIMEOUT_SECS),
+    );
 
-    // 1. 인증 URL로 이동 (더 안전한 방식)
-    match tokio::time::timeout(
-        Duration::from_secs(20),
-        page.goto(&config.auth_url)
-    ).await {
-        Ok(Ok(_)) => {
-            println!("✅ 인증 URL 로딩 완료");
-            tokio::time::sleep(Duration::from_secs(3)).await; // 페이지 로딩 대기 시간 증가
-        }
-        Ok(Err(e)) => {
-            println!("❌ URL 이동 실패: {:?}", e);
-            println!("🕐 브라우저를 10초 동안 유지합니다...");
-            tokio::time::sleep(Duration::from_secs(10)).await;
-            let _ = handler_task.await;
-            let _ = std::fs::remove_dir_all(&user_data_dir);
-            return Err("URL 이동 실패".to_string());
-        }
-        Err(_) => {
-            println!("❌ URL 이동 타임아웃");
-            println!("🕐 브라우저를 10초 동안 유지합니다...");
-            tokio::time::sleep(Duration::from_secs(10)).await;
-            let _ = handler_task.await;
-            let _ = std::fs::remove_dir_all(&user_data_dir);
-            return Err("URL 이동 타임아웃".to_string());
-        }
-    }
+    info!("✅ 인증 URL로 이동합니다: {}", config.auth_url);
 
-    // 최대 3번 시도하는 메인 자동화 루프
-    for main_attempt in 1..=3 {
-        println!("🔄 메인 자동화 시도 {}/3", main_attempt);
-        
-        // 2. 쿠키 팝업 처리
-        if let Err(e) = handle_initial_popup(&page).await {
-            println!("⚠️ 쿠키 팝업 처리 실패: {}", e);
-        }
-        tokio::time::sleep(Duration::from_millis(1000)).await; // 단계간 대기 시간 증가
-
-        // 3. 방문 예약하기 버튼 클릭
-        if let Err(e) = click_visit_reservation_button(&page).await {
-            println!("❌ 방문 예약하기 버튼 클릭 실패: {}", e);
-            if main_attempt == 3 {
-                println!("🕐 브라우저를 30초 동안 유지합니다 (수동 확인용)...");
-                tokio::time::sleep(Duration::from_secs(30)).await;
+    // 1. 인증 URL로 이동 - 실패/지연 시 네트워크 모니터가 허용하는 횟수만큼 다시 이동
+    loop {
+        pacer.wait_turn("인증 URL 이동").await;
+        let problem = match tokio::time::timeout(
+            Duration::from_secs(config.network_monitor.stall_timeout_secs),
+            page.goto(&config.auth_url)
+        ).await {
+            Ok(Ok(_)) => {
+                info!("✅ 인증 URL 로딩 완료");
+                tokio::time::sleep(Duration::from_secs(3)).await; // 페이지 로딩 대기 시간 증가
+                break;
+            }
+            Ok(Err(e)) => {
+                error!("❌ URL 이동 실패: {:?}", e);
+                network::NetworkProblem::Failed { reason: e.to_string() }
+            }
+            Err(_) => {
+                error!("❌ URL 이동 타임아웃");
+                network::NetworkProblem::Stalled { secs: config.network_monitor.stall_timeout_secs }
+            }
+        };
+        match monitor.take_reload(&problem) {
+            Ok(reload) => warn!("🔄 {} - 인증 URL 다시 이동 {}/{}", problem, reload, config.network_monitor.max_reloads),
+            Err(e) => {
+                error!("❌ {}", e);
+                info!("🕐 브라우저를 10초 동안 유지합니다...");
+                tokio::time::sleep(Duration::from_secs(10)).await;
                 let _ = handler_task.await;
                 let _ = std::fs::remove_dir_all(&user_data_dir);
-                return Err("방문 예약하기 버튼을 찾을 수 없습니다".to_string());
+                return Err(match problem {
+                    network::NetworkProblem::Stalled { .. } => "URL 이동 타임아웃".to_string(),
+                    network::NetworkProblem::Failed { .. } => "URL 이동 실패".to_string(),
+                });
             }
-            continue;
         }
+    }
 
-        // 4. 롤렉스 컬렉션 버튼 클릭 (1단계)
-        if let Err(e) = click_rolex_collection_button(&page).await {
-            println!("❌ 롤렉스 컬렉션 버튼 클릭 실패: {}", e);
-            if main_attempt == 3 {
-                println!("🕐 브라우저를 30초 동안 유지합니다 (수동 확인용)...");
-                tokio::time::sleep(Duration::from_secs(30)).await;
+    // 선호 목록의 첫 항목으로 시작해 페이지에서 선택 가능한 값에 맞춰 바꿈
+    // chooseSlot 으로 사용자가 고른 값이 있으면 선호 목록 대신 사용
+    let date_preferences = config.date_preferences();
+    let time_preferences = config.time_preferences();
+    let mut visit_date = date_preferences.first().cloned();
+    let mut visit_time = time_preferences.first().map(|w| w.start.format("%H:%M").to_string());
+    let mut time_chosen_by_user = false;
+
+    // 단계별 재시도 - 필수 단계가 실패하면 현재 페이지 단계를 확인해 그 단계부터 다시 진행
+    // 단계 사이마다 일시정지/수동 조작 체크포인트
+    let mut attempts: std::collections::HashMap<Step, u32> = std::collections::HashMap::new();
+    let mut step = Step::CookiePopup;
+    loop {
+        // 재개 시 사용자가 지정한 단계가 있으면 그 단계부터 진행
+        let resume_from = match run.checkpoint(step).await {
+            Ok(resume_from) => resume_from,
+            Err(e) => {
+                // 중지 요청 - 브라우저를 닫고 바로 종료
+                let _ = browser.close().await;
                 let _ = handler_task.await;
                 let _ = std::fs::remove_dir_all(&user_data_dir);
-                return Err("롤렉스 컬렉션 버튼을 찾을 수 없습니다".to_string());
+                return Err(e);
             }
-            continue;
+        };
+        if let Some(resume_from) = resume_from {
+            info!("⏭️ {} 단계부터 이어서 진행합니다", resume_from.as_str());
+            step = resume_from;
         }
-
-        // 5. 시간 기반 대기 (설정된 시작 시간까지 - 동의합니다 버튼 클릭 전)
-        if let Some(start_time_str) = &config.start_time {
-            println!("⏰ 시작 시간 확인: {}", start_time_str);
-            
-            // 클라이언트 시간과 비교하여 대기
-            if let Some(client_time_str) = &config.client_time {
-                println!("📱 클라이언트 현재 시간: {}", client_time_str);
-                
-                // 클라이언트 현재 시간 파싱 (ISO 8601 형식)
-                let client_current_time = match chrono::DateTime::parse_from_rfc3339(client_time_str) {
-                    Ok(dt) => dt.naive_local(),
-                    Err(e) => {
-                        println!("⚠️ 클라이언트 시간 파싱 실패: {} - 서버 시간 사용", e);
-                        Local::now().naive_local()
+        // 예약 페이지 로딩이 멈췄거나 실패했으면 새로고침 후 페이지가 도달한 단계부터 진행
+        if network::reloadable(step) {
+            if let Some(problem) = monitor.problem() {
+                match monitor.reload(&page, &pacer, &problem).await {
+                    Ok(()) => {
+                        tokio::time::sleep(Duration::from_secs(2)).await;
+                        let resume_step = page_state::detect_stage(&page, &config.locators).await.resume_step();
+                        info!("🔄 새로고침 후 {} 단계부터 진행", resume_step.as_str());
+                        step = resume_step;
                     }
-                };
-                
-                // 시작 시간 파싱 (여러 형식 지원)
-                let parse_formats = [
-                    "%Y-%m-%dT%H:%M:%S",
-                    "%Y-%m-%d %H:%M:%S", 
-                    "%Y-%m-%dT%H:%M",
-                    "%Y-%m-%d %H:%M"
-                ];
-                
-                let mut target_datetime = None;
-                for fmt in &parse_formats {
-                    if let Ok(dt) = NaiveDateTime::parse_from_str(start_time_str, fmt) {
-                        target_datetime = Some(dt);
-                        println!("✅ 시작 시간 파싱 성공: {} → {}", start_time_str, dt.format("%Y-%m-%d %H:%M:%S"));
-                        break;
+                    Err(e) => warn!("⚠️ {}", e),
+                }
+            }
+        }
+        let attempt = attempts.get(&step).copied().unwrap_or(0) + 1;
+        let span = step_span(step.as_str(), attempt);
+        let console_span = span.clone();
+        let locators = config.locator_chain(step);
+        let mut policy = config.retry_policy(step);
+        // 단계 안에서 재시도하는 동안에도 로딩 문제를 확인해 바로 새로고침으로 넘어감
+        if network::reloadable(step) {
+            policy.watch = Some(monitor.watch());
+        }
+        if pacing::navigates(step) {
+            pacer.wait_turn(step.as_str()).await;
+        }
+
+        // 필수 단계 실패 시 Err(최종 실패 메시지), 나머지 단계는 경고만 남기고 Ok
+        let outcome: Result<(), String> = match step {
+            // 2. 쿠키 팝업 처리
+            Step::CookiePopup => {
+                let replayed = replay::try_step(&page, replay_plan.as_ref(), step, &config.email, &config.contact_fields, &policy)
+                    .instrument(span.clone())
+                    .await;
+                if replayed.is_none() {
+                    if let Err(e) = handle_initial_popup(&page, &locators, &policy).instrument(span).await {
+                        warn!("⚠️ 쿠키 팝업 처리 실패: {}", e);
                     }
                 }
-                
-                if let Some(target_dt) = target_datetime {
-                    println!("⏰ 설정된 시작 시간: {}까지 대기합니다.", target_dt.format("%Y-%m-%d %H:%M:%S"));
-                    println!("📅 현재 클라이언트 시간: {}", client_current_time.format("%Y-%m-%d %H:%M:%S"));
-                    
-                    // 시작 시간이 아직 도달하지 않았으면 대기
-                    if client_current_time < target_dt {
-                        let wait_duration = target_dt.signed_duration_since(client_current_time);
-                        if wait_duration.num_seconds() > 0 && wait_duration.num_seconds() < 86400 { // 24시간 이내만 대기
-                            println!("⏱️ {}초 대기 중... ({}시간 {}분)", 
-                                wait_duration.num_seconds(),
-                                wait_duration.num_hours(),
-                                wait_duration.num_minutes() % 60
-                            );
-                            
-                            // 1초씩 대기하며 실시간 업데이트
-                            let mut remaining = wait_duration.num_seconds();
-                            while remaining > 0 {
-                                if remaining % 60 == 0 || remaining <= 10 {
-                                    let hours = remaining / 3600;
-                                    let minutes = (remaining % 3600) / 60;
-                                    let secs = remaining % 60;
-                                    println!("⏰ 동의합니다 버튼 클릭까지 {}시간 {}분 {}초 남음...", hours, minutes, secs);
+                tokio::time::sleep(Duration::from_millis(1000)).await; // 단계간 대기 시간 증가
+                Ok(())
+            }
+
+            // 3. 방문 예약하기 버튼 클릭
+            Step::VisitReservation => match replay::try_step(&page, replay_plan.as_ref(), step, &config.email, &config.contact_fields, &policy)
+                .instrument(span.clone())
+                .await
+            {
+                Some(_) => Ok(()),
+                None => click_visit_reservation_button(&page, &locators, &policy)
+                    .instrument(span)
+                    .await
+                    .map_err(|e| {
+                        error!("❌ 방문 예약하기 버튼 클릭 실패: {}", e);
+                        "방문 예약하기 버튼을 찾을 수 없습니다".to_string()
+                    }),
+            },
+
+            // 4. 롤렉스 컬렉션 버튼 클릭 (1단계)
+            Step::RolexCollection => match replay::try_step(&page, replay_plan.as_ref(), step, &config.email, &config.contact_fields, &policy)
+                .instrument(span.clone())
+                .await
+            {
+                Some(_) => Ok(()),
+                None => click_rolex_collection_button(&page, &locators, &policy)
+                    .instrument(span)
+                    .await
+                    .map_err(|e| {
+                        error!("❌ 롤렉스 컬렉션 버튼 클릭 실패: {}", e);
+                        "롤렉스 컬렉션 버튼을 찾을 수 없습니다".to_string()
+                    }),
+            },
+
+            Step::Agree => {
+                // 5. 시간 기반 대기 (설정된 시작 시간까지 - 동의합니다 버튼 클릭 전)
+                if let Some(start_time_str) = &config.start_time {
+                    info!("⏰ 시작 시간 확인: {}", start_time_str);
+
+                    // 클라이언트 시간과 비교하여 대기
+                    if let Some(client_time_str) = &config.client_time {
+                        info!("📱 클라이언트 현재 시간: {}", client_time_str);
+
+                        // 클라이언트 현재 시간 파싱 (ISO 8601 형식)
+                        let client_current_time = match chrono::DateTime::parse_from_rfc3339(client_time_str) {
+                            Ok(dt) => dt.naive_local(),
+                            Err(e) => {
+                                warn!("⚠️ 클라이언트 시간 파싱 실패: {} - 서버 시간 사용", e);
+                                Local::now().naive_local()
+                            }
+                        };
+
+                        // 시작 시간 파싱 (여러 형식 지원)
+                        let parse_formats = [
+                            "%Y-%m-%dT%H:%M:%S",
+                            "%Y-%m-%d %H:%M:%S", 
+                            "%Y-%m-%dT%H:%M",
+                            "%Y-%m-%d %H:%M"
+                        ];
+
+                        let mut target_datetime = None;
+                        for fmt in &parse_formats {
+                            if let Ok(dt) = NaiveDateTime::parse_from_str(start_time_str, fmt) {
+                                target_datetime = Some(dt);
+                                info!("✅ 시작 시간 파싱 성공: {} → {}", start_time_str, dt.format("%Y-%m-%d %H:%M:%S"));
+                                break;
+                            }
+                        }
+
+                        if let Some(target_dt) = target_datetime {
+                            info!("⏰ 설정된 시작 시간: {}까지 대기합니다.", target_dt.format("%Y-%m-%d %H:%M:%S"));
+                            info!("📅 현재 클라이언트 시간: {}", client_current_time.format("%Y-%m-%d %H:%M:%S"));
+
+                            // 시작 시간이 아직 도달하지 않았으면 대기
+                            if client_current_time < target_dt {
+                                let wait_duration = target_dt.signed_duration_since(client_current_time);
+                                if wait_duration.num_seconds() > 0 && wait_duration.num_seconds() < 86400 { // 24시간 이내만 대기
+                                    info!("⏱️ {}초 대기 중... ({}시간 {}분)", 
+                                        wait_duration.num_seconds(),
+                                        wait_duration.num_hours(),
+                                        wait_duration.num_minutes() % 60
+                                    );
+
+                                    // 1초씩 대기하며 실시간 업데이트
+                                    let mut remaining = wait_duration.num_seconds();
+                                    while remaining > 0 {
+                                        if remaining % 60 == 0 || remaining <= 10 {
+                                            let hours = remaining / 3600;
+                                            let minutes = (remaining % 3600) / 60;
+                                            let secs = remaining % 60;
+                                            info!("⏰ 동의합니다 버튼 클릭까지 {}시간 {}분 {}초 남음...", hours, minutes, secs);
+                                        }
+                                        tokio::time::sleep(Duration::from_secs(1)).await;
+                                        remaining -= 1;
+                                    }
+                                } else if wait_duration.num_seconds() >= 86400 {
+                                    warn!("⚠️ 시작 시간이 24시간 이상 미래입니다. 즉시 시작합니다.");
                                 }
-                                tokio::time::sleep(Duration::from_secs(1)).await;
-                                remaining -= 1;
                             }
-                        } else if wait_duration.num_seconds() >= 86400 {
-                            println!("⚠️ 시작 시간이 24시간 이상 미래입니다. 즉시 시작합니다.");
+
+                            info!("🚀 설정된 시작 시간 도달! 동의합니다 버튼을 클릭합니다.");
+                        } else {
+                            warn!("⚠️ 시간 파싱 실패: {} (지원 포맷: YYYY-MM-DDTHH:mm[:ss] 또는 YYYY-MM-DD HH:mm[:ss])", start_time_str);
+                            info!("📋 파싱 실패로 인해 즉시 진행합니다.");
                         }
+                    } else {
+                        warn!("⚠️ 클라이언트 시간이 제공되지 않음 - 즉시 진행합니다.");
                     }
-                    
-                    println!("🚀 설정된 시작 시간 도달! 동의합니다 버튼을 클릭합니다.");
                 } else {
-                    println!("⚠️ 시간 파싱 실패: {} (지원 포맷: YYYY-MM-DDTHH:mm[:ss] 또는 YYYY-MM-DD HH:mm[:ss])", start_time_str);
-                    println!("📋 파싱 실패로 인해 즉시 진행합니다.");
+                    warn!("⚠️ 시작 시간이 설정되지 않음 - 즉시 진행합니다.");
                 }
-            } else {
-                println!("⚠️ 클라이언트 시간이 제공되지 않음 - 즉시 진행합니다.");
+
+                // 6. 동의합니다 버튼 클릭 (설정 시간에 맞춰 실행)
+                let replayed = replay::try_step(&page, replay_plan.as_ref(), step, &config.email, &config.contact_fields, &policy)
+                    .instrument(span.clone())
+                    .await;
+                if replayed.is_none() {
+                    if let Err(e) = click_agree_button(&page, &locators, &policy).instrument(span).await {
+                        warn!("⚠️ 동의 버튼 클릭 실패: {}", e);
+                    }
+                }
+                Ok(())
             }
-        } else {
-            println!("⚠️ 시작 시간이 설정되지 않음 - 즉시 진행합니다.");
-        }
 
-        // 6. 동의합니다 버튼 클릭 (설정 시간에 맞춰 실행)
-        if let Err(e) = click_agree_button(&page).await {
-            println!("⚠️ 동의 버튼 클릭 실패: {}", e);
-        }
+            // 6. 방문 날짜 선택 (3단계) - 날짜 목록 상태로 예약 마감 여부를 먼저 판단
+            Step::VisitDate => {
+                // chooseSlot 이면 열린 날짜 목록을 보여주고 사용자가 고른 날짜/시간으로 진행
+                let mut date_chosen_by_user = false;
+                if config.choose_slot {
+                    if let Some(choice) = ask_slot_choice(&page, run, user_action_timeout, false).await {
+                        if let Some(date) = choice.date {
+                            visit_date = Some(date);
+                            date_chosen_by_user = true;
+                        }
+                        if let Some(time) = choice.time {
+                            visit_time = Some(time);
+                            time_chosen_by_user = true;
+                        }
+                    }
+                }
 
-        // 6. 방문 날짜 선택 (3단계)
-        if let Some(visit_date) = &config.visit_date {
-            if let Err(e) = select_visit_date(&page, visit_date).await {
-                println!("⚠️ 방문 날짜 선택 실패: {}", e);
-                if e.contains("마감") {
-                    // 예약이 마감된 경우 즉시 종료
-                    println!("🕐 브라우저를 30초 동안 유지합니다 (예약 마감 확인용)...");
-                    tokio::time::sleep(Duration::from_secs(30)).await;
-                    let _ = handler_task.await;
-                    let _ = std::fs::remove_dir_all(&user_data_dir);
-                    return Err(e);
+                // 아니면 선호 날짜 순서대로 페이지에서 선택 가능한 첫 날짜를 고름
+                if date_chosen_by_user {
+                    run.update_selection(|s| {
+                        s.date = visit_date.clone();
+                        s.date_preference = None;
+                    });
+                } else if !date_preferences.is_empty() {
+                    match page_state::read_available_slots(&page).await {
+                        Ok(slots) => match preferences::pick_date(&date_preferences, &slots.dates) {
+                            Some((index, date)) => {
+                                info!("📅 선호 날짜 {}순위 선택: {}", index + 1, date);
+                                visit_date = Some(date);
+                                run.update_selection(|s| {
+                                    s.date = visit_date.clone();
+                                    s.date_preference = Some(index);
+                                });
+                            }
+                            None => warn!(
+                                "⚠️ 선호 날짜 중 예약 가능한 날짜가 없습니다: {}",
+                                date_preferences.join(", ")
+                            ),
+                        },
+                        Err(e) => warn!("⚠️ {}", e),
+                    }
+                }
+
+                match page_state::detect(&page, visit_date.as_deref()).await {
+                    PageState::Closed { reason } => {
+                        // 예약이 마감된 경우 즉시 종료
+                        error!("❌ 예약 마감 감지: {}", reason);
+                        info!("🕐 브라우저를 30초 동안 유지합니다 (예약 마감 확인용)...");
+                        tokio::time::sleep(Duration::from_secs(30)).await;
+                        let _ = handler_task.await;
+                        let _ = std::fs::remove_dir_all(&user_data_dir);
+                        return Err(format!(
+                            "온라인 예약이 마감되었습니다 ({}). 다음 예약 오픈 시간을 확인해주세요.",
+                            reason
+                        ));
+                    }
+                    PageState::NoSlotsForDate { date } => {
+                        warn!("⚠️ 선택한 날짜({})에 예약 가능한 슬롯이 없습니다", date);
+                    }
+                    PageState::Unknown => {
+                        warn!("⚠️ 예약 페이지 상태를 판단할 수 없습니다");
+                    }
+                    PageState::Open => {}
                 }
-            }
-        }
 
-        // 7. 방문 시간 선택 (3단계 계속)
-        if let Some(visit_time) = &config.visit_time {
-            if let Err(e) = select_visit_time(&page, visit_time).await {
-                println!("⚠️ 방문 시간 선택 실패: {}", e);
+                if let Some(visit_date) = &visit_date {
+                    if let Err(e) = select_visit_date(&page, visit_date, &policy).instrument(span).await {
+                        warn!("⚠️ 방문 날짜 선택 실패: {}", e);
+                    }
+                }
+                Ok(())
             }
-        }
 
-        // 8. 다음 버튼 클릭 (3단계에서 PASS 인증으로)
-        if let Err(e) = click_next_button(&page).await {
-            println!("⚠️ 다음 버튼 클릭 실패: {}", e);
-        }
+            // 7. 방문 시간 선택 (3단계 계속)
+            Step::VisitTime => {
+                // 날짜를 고른 뒤에야 시간 목록이 채워지므로 시간은 따로 물어봄
+                if config.choose_slot && !time_chosen_by_user && time_preferences.is_empty() {
+                    if let Some(choice) = ask_slot_choice(&page, run, user_action_timeout, true).await {
+                        if choice.time.is_some() {
+                            visit_time = choice.time;
+                            time_chosen_by_user = true;
+                        }
+                    }
+                }
 
-        // 9. PASS 인증 처리 (사용자 개입 대기)
-        if let Err(e) = handle_pass_authentication(&page, &config.carrier).await {
-            println!("❌ PASS 인증 실패: {}", e);
-            if main_attempt == 3 {
-                println!("🕐 브라우저를 30초 동안 유지합니다 (수동 확인용)...");
-                tokio::time::sleep(Duration::from_secs(30)).await;
-                let _ = handler_task.await;
-                let _ = std::fs::remove_dir_all(&user_data_dir);
-                return Err("PASS 인증 실패".to_string());
+                // 선호 시간대 순서대로, 시간대 안에서는 가장 이른 선택 가능한 시간을 고름
+                if time_chosen_by_user {
+                    run.update_selection(|s| {
+                        s.time = visit_time.clone();
+                        s.time_preference = None;
+                        s.time_window = None;
+                    });
+                } else if !time_preferences.is_empty() {
+                    match page_state::read_available_slots(&page).await {
+                        Ok(slots) => match preferences::pick_time(&time_preferences, &slots.times) {
+                            Some((index, window, time)) => {
+                                info!("🕐 선호 시간 {}순위({}) 선택: {}", index + 1, window, time);
+                                visit_time = Some(time);
+                                run.update_selection(|s| {
+                                    s.time = visit_time.clone();
+                                    s.time_preference = Some(index);
+                                    s.time_window = Some(window.to_string());
+                                });
+                            }
+                            None => {
+                                let windows: Vec<String> = time_preferences.iter().map(|w| w.to_string()).collect();
+                                warn!("⚠️ 선호 시간대 중 예약 가능한 시간이 없습니다: {}", windows.join(", "));
+                            }
+                        },
+                        Err(e) => warn!("⚠️ {}", e),
+                    }
+                }
+
+                if let Some(visit_time) = &visit_time {
+                    if let Err(e) = select_visit_time(&page, visit_time, &policy).instrument(span).await {
+                        warn!("⚠️ 방문 시간 선택 실패: {}", e);
+                    }
+                }
+                Ok(())
             }
-            continue;
-        }
 
-        // 10. 이메일 입력 및 최종 예약 (4단계)
-        if let Err(e) = submit_final_reservation(&page, &config.email).await {
-            println!("❌ 최종 예약 제출 실패: {}", e);
-            if main_attempt == 3 {
-                println!("🕐 브라우저를 30초 동안 유지합니다 (수동 확인용)...");
-                tokio::time::sleep(Duration::from_secs(30)).await;
-                let _ = handler_task.await;
-                let _ = std::fs::remove_dir_all(&user_data_dir);
-                return Err("최종 예약 제출 실패".to_string());
+            // 8. 다음 버튼 클릭 (3단계에서 PASS 인증으로)
+            Step::Next => {
+                let replayed = replay::try_step(&page, replay_plan.as_ref(), step, &config.email, &config.contact_fields, &policy)
+                    .instrument(span.clone())
+                    .await;
+                if replayed.is_none() {
+                    if let Err(e) = click_next_button(&page, &locators, &policy).instrument(span).await {
+                        warn!("⚠️ 다음 버튼 클릭 실패: {}", e);
+                    }
+                }
+                Ok(())
             }
-            continue;
-        }
 
-        // 11. 성공 페이지 확인
-        match check_success_page(&page).await {
-            Ok(true) => {
-                println!("🎉 {} 자동화가 성공적으로 완료되었습니다!", config.store_name);
-                log_user_action(
-                    "예약 성공",
-                    &format!("매장: {}, 이메일: {}", config.store_name, config.email),
-                );
-                
-    // 성공 시 30초 동안 브라우저를 유지 (결과 확인용)
-                println!("🕐 성공! 브라우저를 30초 동안 유지합니다...");
-                tokio::time::sleep(Duration::from_secs(30)).await;
-                let _ = handler_task.await;
-                
-                // 임시 디렉토리 정리
-                let _ = std::fs::remove_dir_all(&user_data_dir);
-                
-                return Ok(format!("{} 예약이 성공적으로 완료되었습니다!", config.store_name));
+            // 9. PASS 인증 처리 (사용자 개입 대기)
+            Step::PassAuthentication => {
+                handle_pass_authentication(&page, &config.carrier, &tracker, &policy, run)
+                    .instrument(span)
+                    .await
+                    .map_err(|e| {
+                        error!("❌ PASS 인증 실패: {}", e);
+                        "PASS 인증 실패".to_string()
+                    })
             }
-            Ok(false) => {
-                println!("❌ 예약 실패 (시도 {}/3)", main_attempt);
-                if main_attempt < 3 {
-                    println!("🔄 5초 후 다시 시도합니다...");
-                    tokio::time::sleep(Duration::from_secs(5)).await;
-                    continue;
+
+            // 10. 이메일 입력 및 최종 예약 (4단계)
+            Step::Submit => match replay::try_step(&page, replay_plan.as_ref(), step, &config.email, &config.contact_fields, &policy)
+                .instrument(span.clone())
+                .await
+            {
+                Some(report) => {
+                    run.set_contact_report(report);
+                    Ok(())
                 }
-            }
-            Err(e) => {
-                println!("❌ 성공 페이지 확인 중 오류: {}", e);
-                if main_attempt < 3 {
-                    continue;
+                None => submit_final_reservation(&page, &config.email, &config.contact_fields, &locators, &policy)
+                    .instrument(span)
+                    .await
+                    .map(|report| run.set_contact_report(report))
+                    .map_err(|e| {
+                        error!("❌ 최종 예약 제출 실패: {}", e);
+                        "최종 예약 제출 실패".to_string()
+                    }),
+            },
+
+            // 11. 성공 페이지 확인
+            Step::CheckSuccess => match check_success_page(&page, &policy).instrument(span).await {
+                Ok(ConfirmationStatus::Confirmed(confirmation)) => {
+                    console.flush(&console_span).await;
+                    info!("🎉 {} 자동화가 성공적으로 완료되었습니다!", config.store_name);
+                    log_user_action(
+                        "예약 성공",
+                        &format!(
+                            "매장: {}, 예약번호: {}, 이메일: {}",
+                            config.store_name, confirmation.reference, config.email
+                        ),
+                    );
+
+                    // 성공 시 30초 동안 브라우저를 유지 (결과 확인용)
+                    info!("🕐 성공! 브라우저를 30초 동안 유지합니다...");
+                    tokio::time::sleep(Duration::from_secs(30)).await;
+                    let _ = handler_task.await;
+
+                    // 임시 디렉토리 정리
+                    let _ = std::fs::remove_dir_all(&user_data_dir);
+
+                    return Ok((
+                        format!(
+                            "{} 예약이 성공적으로 완료되었습니다! (예약번호: {})",
+                            config.store_name, confirmation.reference
+                        ),
+                        confirmation,
+                    ));
+                }
+                Ok(ConfirmationStatus::Unverified { reason }) => {
+                    error!("❌ 예약 실패 또는 확인 불가 (시도 {}/{}): {}", attempt, policy.step_attempts, reason);
+                    Err(reason)
+                }
+                Err(e) => {
+                    error!("❌ 성공 페이지 확인 중 오류: {}", e);
+                    Err(e)
+                }
+            },
+        };
+        console.flush(&console_span).await;
+
+        match outcome {
+            Ok(()) => match step.next() {
+                Some(next) => step = next,
+                None => break,
+            },
+            Err(message) => {
+                attempts.insert(step, attempt);
+                if attempt >= policy.step_attempts {
+                    if step == Step::CheckSuccess {
+                        break;
+                    }
+                    info!("🕐 브라우저를 30초 동안 유지합니다 (수동 확인용)...");
+                    tokio::time::sleep(Duration::from_secs(30)).await;
+                    let _ = handler_task.await;
+                    let _ = std::fs::remove_dir_all(&user_data_dir);
+                    return Err(message);
+                }
+
+                // 처음부터 다시 시작하지 않고 페이지가 도달한 단계부터 재시도
+                if step == Step::CheckSuccess {
+                    info!("🔄 5초 후 다시 시도합니다...");
+                    tokio::time::sleep(Duration::from_secs(5)).await;
                 }
+                let resume_step = if tracker.auth_open(&page).await {
+                    Step::PassAuthentication
+                } else {
+                    page_state::detect_stage(&page, &config.locators).await.resume_step()
+                };
+                info!(
+                    "🔄 {} 단계 재시도 {}/{} - 현재 페이지 기준 {} 단계부터 진행",
+                    step.as_str(),
+                    attempt + 1,
+                    policy.step_attempts,
+                    resume_step.as_str()
+                );
+                step = resume_step;
             }
         }
     }
 
-    // 3번 모두 실패한 경우
-    println!("❌ {} 자동화 최종 실패 (3회 시도 모두 실패)", config.store_name);
+    // 성공 페이지 확인이 재시도 횟수를 모두 소진한 경우
+    let check_success_attempts = config.retry_policy(Step::CheckSuccess).step_attempts;
+    error!("❌ {} 자동화 최종 실패 ({}회 시도 모두 실패)", config.store_name, check_success_attempts);
     log_user_action(
         "예약 실패",
         &format!("매장: {}, 최종 실패", config.store_name),
     );
     
     // 실패 시 30초 동안 브라우저를 유지 (디버깅용)
-    println!("🕐 실패! 브라우저를 30초 동안 유지합니다...");
+    info!("🕐 실패! 브라우저를 30초 동안 유지합니다...");
     tokio::time::sleep(Duration::from_secs(30)).await;
     let _ = handler_task.await;
     
     // 임시 디렉토리 정리
     let _ = std::fs::remove_dir_all(&user_data_dir);
     
-    Err(format!("{} 예약 실패 (3회 시도 모두 실패)", config.store_name))
+    Err(format!(
+        "{} 예약 실패 ({}회 시도 모두 실패)",
+        config.store_name,
+        check_success_attempts
+    ))
 }
 
 // 기존 코드와의 호환성을 위한 레거시 함수들 제거됨 - 새로운 플로우 사용
@@ -618,13 +1682,52 @@ async fn run_rolex_automation(config: &StoreConfig) -> Result<String, String> {
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
+        .setup(|app| {
+            logging::init(&app.path().app_log_dir()?);
+            let preferences = notify::load_preferences(&notify::preferences_path(&app.path().app_data_dir()?));
+            app.manage(notify::NotificationSettings(std::sync::Mutex::new(preferences)));
+            Ok(())
+        })
+        .manage(secrets::SecretStore::default())
+        .manage(events::ActiveRuns::default())
+        .manage(recorder::FlowRecorder::default())
         .invoke_handler(tauri::generate_handler![
             greet,
             setup_automation,
             run_single_automation,
             run_multiple_automation,
             stop_automation,
-            stop_all_automation
+            stop_all_automation,
+            load_automation_config,
+            save_automation_config,
+            import_automation_config,
+            validate_automation_config,
+            unlock_secret_store,
+            lock_secret_store,
+            is_secret_store_unlocked,
+            list_personal_details,
+            save_personal_details,
+            delete_personal_details,
+            list_run_logs,
+            read_run_log,
+            export_run_calendar,
+            get_notification_preferences,
+            set_notification_preferences,
+            focus_browser_window,
+            test_notification_sinks,
+            list_active_runs,
+            pause_automation,
+            take_over_automation,
+            resume_automation,
+            list_available_slots,
+            choose_slot,
+            check_selectors,
+            capture_dom_baseline,
+            check_dom_drift,
+            start_flow_recording,
+            stop_flow_recording,
+            import_recorded_flow
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,472 @@
+// 단계별 요소 찾기 - 셀렉터 하나 대신 순서대로 시도하는 대체 로케이터 목록 사용
+// CSS / 화면 텍스트 / ARIA 역할·이름(CDP Accessibility) / XPath 중 처음 찾은 요소에 표식 속성을 붙인 뒤 그 요소를 클릭
+// 표식은 클릭할 때 지우고, 새로 표식을 붙일 때도 남아 있는 표식을 먼저 지움
+use chromiumoxide::cdp::browser_protocol::accessibility::{EnableParams, QueryAxTreeParams};
+use chromiumoxide::cdp::browser_protocol::dom::{BackendNodeId, GetDocumentParams, ResolveNodeParams};
+use chromiumoxide::cdp::js_protocol::runtime::CallFunctionOnParams;
+use chromiumoxide::Page;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::control::Step;
+
+// 찾은 요소에 붙이는 속성 (값은 호출마다 다름)
+const MARK_ATTRIBUTE: &str = "data-rolex-locator";
+
+// 텍스트 로케이터에 selector 가 없을 때 검사할 요소
+const CLICKABLE_SELECTOR: &str = "a, button, [role=\"button\"], input[type=\"submit\"], input[type=\"button\"]";
+
+static MARK_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "by", rename_all = "snake_case")]
+pub enum Locator {
+    Css {
+        selector: String,
+    },
+    // 화면에 보이는 텍스트를 포함하는 요소 (selector 가 있으면 그 안에서만)
+    Text {
+        text: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        selector: Option<String>,
+    },
+    // 접근성 트리의 역할/이름 (예: role "button", name "동의합니다")
+    Aria {
+        role: String,
+        name: String,
+    },
+    Xpath {
+        xpath: String,
+    },
+}
+
+impl std::fmt::Display for Locator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Locator::Css { selector } => write!(f, "css `{}`", selector),
+            Locator::Text { text, selector: Some(selector) } => write!(f, "text '{}' in `{}`", text, selector),
+            Locator::Text { text, selector: None } => write!(f, "text '{}'", text),
+            Locator::Aria { role, name } => write!(f, "aria {} '{}'", role, name),
+            Locator::Xpath { xpath } => write!(f, "xpath `{}`", xpath),
+        }
+    }
+}
+
+// 설정 파일의 단계별 로케이터 (없는 단계는 기본 목록 사용)
+pub type LocatorOverrides = BTreeMap<Step, Vec<Locator>>;
+
+fn css(selector: &str) -> Locator {
+    Locator::Css {
+        selector: selector.to_string(),
+    }
+}
+
+fn text(text: &str, selector: Option<&str>) -> Locator {
+    Locator::Text {
+        text: text.to_string(),
+        selector: selector.map(str::to_string),
+    }
+}
+
+fn aria(role: &str, name: &str) -> Locator {
+    Locator::Aria {
+        role: role.to_string(),
+        name: name.to_string(),
+    }
+}
+
+fn xpath(xpath: &str) -> Locator {
+    Locator::Xpath {
+        xpath: xpath.to_string(),
+    }
+}
+
+// 기본 로케이터 - 첫 번째는 기존 셀렉터, 나머지는 마크업이 바뀌었을 때의 대체 수단
+// 텍스트 대체 로케이터는 해당 단계의 영역 안에서만 찾음 (예: "다음" 이 날짜 선택기의 다음 달 버튼과 맞지 않도록)
+// 날짜/시간 선택은 data-date/data-time 값으로 찾으므로 대상이 아님
+pub fn default_chain(step: Step) -> Vec<Locator> {
+    match step {
+        Step::CookiePopup => vec![
+            css(".cookies__button--accept"),
+            text("수락", Some(r#"[class*="cookies"] button"#)),
+            xpath("//button[contains(@class, 'cookies') and contains(@class, 'accept')]"),
+        ],
+        Step::VisitReservation => vec![
+            css(r#"a[href="https://www.chronodigmwatch.co.kr/rolex/contact-seoul/appointment/"]"#),
+            xpath("//a[contains(@href, '/contact-seoul/appointment')]"),
+            aria("link", "방문 예약하기"),
+            text("방문 예약", Some(r#"a[href*="appointment"]"#)),
+        ],
+        Step::RolexCollection => vec![
+            css(r#"a[onclick="select_type('collection');"]"#),
+            xpath("//a[contains(@onclick, \"select_type('collection')\")]"),
+            text("롤렉스 컬렉션", Some(r#"[onclick*="select_type"]"#)),
+        ],
+        Step::Agree => vec![
+            text("동의합니다", Some("button.rolex-button")),
+            aria("button", "동의합니다"),
+            text("동의합니다", Some("#appointment button")),
+        ],
+        // 날짜 선택기의 이동 버튼은 type="button" 이므로 폼 제출 버튼으로 한정
+        Step::Next => vec![
+            css(r#"button[name="verification"]"#),
+            text("다음", Some(r#"#appointment form button[type="submit"]"#)),
+        ],
+        Step::Submit => vec![
+            css(r#"button[type="submit"][name="submit_appointment"]"#),
+            xpath("//button[@type='submit' and contains(@name, 'appointment')]"),
+            css(r#"#appointment button[type="submit"]"#),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+// 설정의 단계별 로케이터 (없는 단계는 기본 목록)
+pub fn chain(overrides: &LocatorOverrides, step: Step) -> Vec<Locator> {
+    overrides.get(&step).cloned().unwrap_or_else(|| default_chain(step))
+}
+
+pub fn validate(overrides: &LocatorOverrides) -> Vec<(String, String)> {
+    let mut errors = Vec::new();
+    for (step, chain) in overrides {
+        if chain.is_empty() {
+            errors.push((step.as_str().to_string(), "로케이터가 하나 이상 필요합니다".to_string()));
+        }
+        for (i, locator) in chain.iter().enumerate() {
+            let empty = match locator {
+                Locator::Css { selector } => selector.trim().is_empty(),
+                Locator::Text { text, .. } => text.trim().is_empty(),
+                Locator::Aria { role, name } => role.trim().is_empty() || name.trim().is_empty(),
+                Locator::Xpath { xpath } => xpath.trim().is_empty(),
+            };
+            if empty {
+                errors.push((format!("{}[{}]", step.as_str(), i), format!("비어 있는 로케이터: {}", locator)));
+            }
+        }
+    }
+    errors
+}
+
+// 찾은 요소 - selector 로 이후 스크립트에서 같은 요소를 다시 찾음
+#[derive(Debug, Clone)]
+pub struct LocatorMatch {
+    pub index: usize,
+    pub locator: Locator,
+    pub selector: String,
+}
+
+// 목록 순서대로 시도해 처음 찾은(화면에 보이는) 요소를 돌려줌
+pub async fn resolve(page: &Page, chain: &[Locator]) -> Result<Option<LocatorMatch>, String> {
+    for (index, locator) in chain.iter().enumerate() {
+        let token = format!("{}", MARK_COUNTER.fetch_add(1, Ordering::Relaxed));
+        let marked = match locator {
+            Locator::Aria { role, name } => mark_aria(page, role, name, &token).await,
+            _ => mark_in_page(page, locator, &token).await,
+        };
+        match marked {
+            Ok(true) => {
+                return Ok(Some(LocatorMatch {
+                    index,
+                    locator: locator.clone(),
+                    selector: format!("[{}=\"{}\"]", MARK_ATTRIBUTE, token),
+                }))
+            }
+            Ok(false) => {}
+            Err(e) => tracing::debug!("로케이터 {} 확인 실패: {}", locator, e),
+        }
+    }
+    Ok(None)
+}
+
+// CSS / 텍스트 / XPath 로케이터의 후보 요소를 모으는 스크립트 조각 (찾기와 점검에서 같이 사용)
+const CANDIDATES_JS: &str = r#"
+            const candidatesFor = (locator, clickable) => {
+                let candidates = [];
+                if (locator.by === 'css') {
+                    candidates = Array.from(document.querySelectorAll(locator.selector));
+                } else if (locator.by === 'text') {
+                    candidates = Array.from(document.querySelectorAll(locator.selector || clickable))
+                        .filter(el => el.textContent.trim().includes(locator.text));
+                } else if (locator.by === 'xpath') {
+                    const result = document.evaluate(locator.xpath, document, null, XPathResult.ORDERED_NODE_SNAPSHOT_TYPE, null);
+                    for (let i = 0; i < result.snapshotLength; i++) candidates.push(result.snapshotItem(i));
+                    candidates = candidates.filter(node => node.nodeType === Node.ELEMENT_NODE);
+                }
+                return candidates;
+            };
+"#;
+
+// 점검에서 보이는/활성 요소를 세는 기준
+const STATE_JS: &str = r#"
+            const shown = (el) => window.getComputedStyle(el).display !== 'none' && el.offsetParent !== null;
+            const enabled = (el) => !el.disabled && !el.classList.contains('off') && el.getAttribute('aria-disabled') !== 'true';
+"#;
+
+// CSS / 텍스트 / XPath - 페이지 안에서 찾아 표식을 붙임
+async fn mark_in_page(page: &Page, locator: &Locator, token: &str) -> Result<bool, String> {
+    let locator_arg = serde_json::to_string(locator).map_err(|e| e.to_string())?;
+    let args = serde_json::to_string(&(MARK_ATTRIBUTE, token, CLICKABLE_SELECTOR)).map_err(|e| e.to_string())?;
+
+    let mark_js = format!(
+        r#"
+        (() => {{
+            const locator = {};
+            const [attr, token, clickable] = {};
+            const visible = (el) => window.getComputedStyle(el).display !== 'none';
+            {}
+            document.querySelectorAll('[' + attr + ']').forEach(el => el.removeAttribute(attr));
+            const element = candidatesFor(locator, clickable).find(visible);
+            if (!element) return false;
+            element.setAttribute(attr, token);
+            return true;
+        }})()
+    "#,
+        locator_arg, args, CANDIDATES_JS
+    );
+
+    page.evaluate_expression(mark_js)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_value::<bool>()
+        .map_err(|e| e.to_string())
+}
+
+// 접근성 트리에서 역할/이름이 일치하는 DOM 노드
+async fn query_aria(page: &Page, role: &str, name: &str) -> Result<Vec<BackendNodeId>, String> {
+    // 이미 활성화되어 있으면 무시됨
+    let _ = page.execute(EnableParams::default()).await;
+
+    let document = page
+        .execute(GetDocumentParams::default())
+        .await
+        .map_err(|e| e.to_string())?;
+    let query = QueryAxTreeParams::builder()
+        .node_id(document.result.root.node_id)
+        .role(role)
+        .accessible_name(name)
+        .build();
+    let nodes = page.execute(query).await.map_err(|e| e.to_string())?.result.nodes;
+    Ok(nodes
+        .into_iter()
+        .filter(|n| !n.ignored)
+        .filter_map(|n| n.backend_dom_node_id)
+        .collect())
+}
+
+// DOM 노드를 this 로 함수를 호출해 결과 값을 돌려줌 (노드를 찾을 수 없으면 None)
+pub async fn call_on_node(
+    page: &Page,
+    backend_node_id: BackendNodeId,
+    function: &str,
+) -> Result<Option<serde_json::Value>, String> {
+    let resolved = page
+        .execute(ResolveNodeParams::builder().backend_node_id(backend_node_id).build())
+        .await
+        .map_err(|e| e.to_string())?;
+    let Some(object_id) = resolved.result.object.object_id else {
+        return Ok(None);
+    };
+    let call = CallFunctionOnParams::builder()
+        .function_declaration(function)
+        .object_id(object_id)
+        .return_by_value(true)
+        .build()?;
+    Ok(page.execute(call).await.map_err(|e| e.to_string())?.result.result.value)
+}
+
+// ARIA - 접근성 트리에서 역할/이름으로 찾은 DOM 노드에 표식을 붙임
+async fn mark_aria(page: &Page, role: &str, name: &str, token: &str) -> Result<bool, String> {
+    let args = serde_json::to_string(&(MARK_ATTRIBUTE, token)).map_err(|e| e.to_string())?;
+    let mark_fn = format!(
+        "function() {{ const [attr, token] = {}; if (window.getComputedStyle(this).display === 'none') return false; document.querySelectorAll('[' + attr + ']').forEach(el => el.removeAttribute(attr)); this.setAttribute(attr, token); return true; }}",
+        args
+    );
+
+    for backend_node_id in query_aria(page, role, name).await? {
+        let marked = call_on_node(page, backend_node_id, &mark_fn).await?;
+        if marked.and_then(|v| v.as_bool()).unwrap_or(false) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// 로케이터 하나가 찾는 요소 수 - 셀렉터 점검과 페이지 단계 확인용 (표식은 붙이지 않음)
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct LocatorCounts {
+    #[serde(default)]
+    pub matches: u32,
+    #[serde(default)]
+    pub visible: u32,
+    #[serde(default)]
+    pub enabled: u32,
+}
+
+pub async fn count(page: &Page, locator: &Locator) -> Result<LocatorCounts, String> {
+    if let Locator::Aria { role, name } = locator {
+        return count_aria(page, role, name).await;
+    }
+
+    let locator_arg = serde_json::to_string(locator).map_err(|e| e.to_string())?;
+    let clickable = serde_json::to_string(CLICKABLE_SELECTOR).map_err(|e| e.to_string())?;
+    let count_js = format!(
+        r#"
+        (() => {{
+            const locator = {};
+            const clickable = {};
+            {}
+            {}
+            let elements = [];
+            try {{
+                elements = candidatesFor(locator, clickable);
+            }} catch (e) {{
+                return {{ matches: 0, visible: 0, enabled: 0 }};
+            }}
+            const visible = elements.filter(shown);
+            return {{ matches: elements.length, visible: visible.length, enabled: visible.filter(enabled).length }};
+        }})()
+    "#,
+        locator_arg, clickable, CANDIDATES_JS, STATE_JS
+    );
+
+    page.evaluate_expression(count_js)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_value::<LocatorCounts>()
+        .map_err(|e| e.to_string())
+}
+
+async fn count_aria(page: &Page, role: &str, name: &str) -> Result<LocatorCounts, String> {
+    let state_fn = format!(
+        "function() {{ {} return [shown(this), shown(this) && enabled(this)]; }}",
+        STATE_JS
+    );
+    let mut counts = LocatorCounts::default();
+    for backend_node_id in query_aria(page, role, name).await? {
+        let Some(state) = call_on_node(page, backend_node_id, &state_fn).await? else {
+            continue;
+        };
+        counts.matches += 1;
+        if state[0].as_bool().unwrap_or(false) {
+            counts.visible += 1;
+        }
+        if state[1].as_bool().unwrap_or(false) {
+            counts.enabled += 1;
+        }
+    }
+    Ok(counts)
+}
+
+// 목록 중 하나라도 화면에 보이는 요소를 찾으면 true (페이지 단계 확인용)
+pub async fn any_visible(page: &Page, chain: &[Locator]) -> bool {
+    for locator in chain {
+        match count(page, locator).await {
+            Ok(counts) if counts.visible > 0 => return true,
+            Ok(_) => {}
+            Err(e) => tracing::debug!("로케이터 {} 확인 실패: {}", locator, e),
+        }
+    }
+    false
+}
+
+// 찾은 요소의 표식을 지우고 화면 가운데로 옮긴 뒤 클릭
+pub async fn click(page: &Page, found: &LocatorMatch) -> Result<bool, String> {
+    let args = serde_json::to_string(&(&found.selector, MARK_ATTRIBUTE)).map_err(|e| e.to_string())?;
+    let click_js = format!(
+        r#"
+        (() => {{
+            const [selector, attr] = {};
+            const element = document.querySelector(selector);
+            if (!element) return false;
+            element.removeAttribute(attr);
+            element.scrollIntoView({{behavior: 'instant', block: 'center'}});
+            element.click();
+            return true;
+        }})()
+    "#,
+        args
+    );
+
+    page.evaluate_expression(click_js)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_value::<bool>()
+        .map_err(|e| e.to_string())
+}
+
+// 찾기 + 클릭 - 찾은 로케이터를 실행 로그에 남김
+pub async fn find_and_click(page: &Page, step: Step, chain: &[Locator]) -> Result<Option<LocatorMatch>, String> {
+    let Some(found) = resolve(page, chain).await? else {
+        return Ok(None);
+    };
+    if found.index > 0 {
+        tracing::warn!(
+            step = step.as_str(),
+            locator = %found.locator,
+            "⚠️ 기본 셀렉터 대신 대체 로케이터 #{} 사용: {}",
+            found.index + 1,
+            found.locator
+        );
+    } else {
+        tracing::info!(step = step.as_str(), locator = %found.locator, "🎯 로케이터 일치: {}", found.locator);
+    }
+    if click(page, &found).await? {
+        Ok(Some(found))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUTTON_STEPS: [Step; 6] = [
+        Step::CookiePopup,
+        Step::VisitReservation,
+        Step::RolexCollection,
+        Step::Agree,
+        Step::Next,
+        Step::Submit,
+    ];
+
+    #[test]
+    fn default_text_fallbacks_are_scoped_to_the_step() {
+        for step in BUTTON_STEPS {
+            for locator in default_chain(step) {
+                if let Locator::Text { selector, .. } = &locator {
+                    let selector = selector.as_deref().unwrap_or("");
+                    assert!(
+                        !matches!(selector, "" | "a" | "button"),
+                        "{} 단계의 {} 가 페이지 전체에서 찾음",
+                        step.as_str(),
+                        locator
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn next_does_not_match_date_picker_buttons() {
+        let chain = default_chain(Step::Next);
+        assert!(!chain.iter().any(|l| matches!(l, Locator::Aria { .. })));
+        assert!(chain.iter().all(|l| match l {
+            Locator::Text { selector, .. } => selector.as_deref().is_some_and(|s| s.contains(r#"type="submit""#)),
+            _ => true,
+        }));
+    }
+
+    #[test]
+    fn overrides_replace_the_default_chain() {
+        let custom = vec![css("#next-new")];
+        let overrides = LocatorOverrides::from([(Step::Next, custom.clone())]);
+        assert_eq!(chain(&overrides, Step::Next), custom);
+        assert_eq!(chain(&overrides, Step::Agree), default_chain(Step::Agree));
+
+        let invalid = LocatorOverrides::from([(Step::Agree, vec![text(" ", None)]), (Step::Next, Vec::new())]);
+        let fields: Vec<String> = validate(&invalid).into_iter().map(|(field, _)| field).collect();
+        assert_eq!(fields, ["agree[0]", "next"]);
+    }
+}
@@ -0,0 +1,398 @@
+// tracing 기반 로깅 초기화 및 실행(run)별 로그 파일 관리
+// "run" 스팬 안에서 발생한 이벤트는 <로그 디렉토리>/runs/<run_id>.log 에 스팬 정보(store, step, attempt)와 함께 기록됨
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs::{File, OpenOptions};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, Layer};
+
+use crate::pii;
+
+pub const RUN_SPAN_NAME: &str = "run";
+const RUNS_DIR_NAME: &str = "runs";
+
+// 보관할 최대 실행 로그 개수 (초과 시 오래된 파일부터 삭제)
+const MAX_RUN_LOGS: usize = 50;
+// 실행 로그 한 개의 최대 크기 - 넘으면 <run_id>.log.1 로 옮기고 새로 씀
+const MAX_RUN_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+pub fn init(log_dir: &Path) {
+    let runs_dir = runs_dir(log_dir);
+    if let Err(e) = std::fs::create_dir_all(&runs_dir) {
+        eprintln!("⚠️ 실행 로그 디렉토리를 만들 수 없습니다 ({}): {}", runs_dir.display(), e);
+    }
+    prune_run_logs(&runs_dir, MAX_RUN_LOGS);
+
+    let _ = tracing_subscriber::registry()
+        .with(
+            fmt::layer()
+                .with_target(false)
+                .with_filter(tracing_subscriber::filter::LevelFilter::INFO),
+        )
+        .with(RunFileLayer::new(runs_dir).with_filter(tracing_subscriber::filter::LevelFilter::INFO))
+        .try_init();
+
+    pii::init_from_env();
+}
+
+pub fn runs_dir(log_dir: &Path) -> PathBuf {
+    log_dir.join(RUNS_DIR_NAME)
+}
+
+// 같은 초에 같은 매장으로 시작한 실행도 구분되도록 실행마다 증가
+static RUN_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+// 매장 이름과 시각으로 파일 이름에 안전한 실행 id 를 만듦
+pub fn new_run_id(store_name: &str) -> String {
+    let store: String = store_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    format!(
+        "{}-{}-{:04}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        store,
+        RUN_COUNTER.fetch_add(1, Ordering::Relaxed) % 10000
+    )
+}
+
+#[derive(Serialize, Debug)]
+pub struct RunLogInfo {
+    pub run_id: String,
+    pub size: u64,
+    pub modified: String,
+}
+
+pub fn list_run_logs(log_dir: &Path) -> Result<Vec<RunLogInfo>, String> {
+    let dir = runs_dir(log_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut logs: Vec<RunLogInfo> = run_log_files(&dir)
+        .into_iter()
+        .filter_map(|(path, modified)| {
+            let run_id = path.file_stem()?.to_string_lossy().to_string();
+            let size = std::fs::metadata(&path).ok()?.len();
+            Some(RunLogInfo {
+                run_id,
+                size,
+                modified: chrono::DateTime::<chrono::Local>::from(modified).to_rfc3339(),
+            })
+        })
+        .collect();
+    logs.sort_by(|a, b| b.modified.cmp(&a.modified));
+    Ok(logs)
+}
+
+// 실행 id 는 파일/디렉토리 이름으로 쓰이므로 경로 구분자를 허용하지 않음
+pub fn validate_run_id(run_id: &str) -> Result<(), String> {
+    if run_id.is_empty() || run_id.contains(['/', '\\']) || run_id.contains("..") {
+        return Err(format!("잘못된 실행 id: {}", run_id));
+    }
+    Ok(())
+}
+
+// tail_lines 가 있으면 마지막 N 줄만 반환
+pub fn read_run_log(log_dir: &Path, run_id: &str, tail_lines: Option<usize>) -> Result<String, String> {
+    validate_run_id(run_id)?;
+
+    let path = runs_dir(log_dir).join(format!("{}.log", run_id));
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("실행 로그를 읽을 수 없습니다 ({}): {}", path.display(), e))?;
+
+    Ok(match tail_lines {
+        Some(n) => {
+            let lines: Vec<&str> = content.lines().collect();
+            lines[lines.len().saturating_sub(n)..].join("\n")
+        }
+        None => content,
+    })
+}
+
+fn run_log_files(dir: &Path) -> Vec<(PathBuf, std::time::SystemTime)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let path = entry.path();
+            if path.extension()? != "log" {
+                return None;
+            }
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+fn prune_run_logs(dir: &Path, keep: usize) {
+    let mut files = run_log_files(dir);
+    if files.len() <= keep {
+        return;
+    }
+    files.sort_by_key(|(_, modified)| *modified);
+    for (path, _) in &files[..files.len() - keep] {
+        let _ = std::fs::remove_file(path);
+        let _ = std::fs::remove_file(path.with_extension("log.1"));
+    }
+}
+
+// 스팬 필드를 "key=value" 문자열로 모으는 방문자 (run_id 는 따로 보관)
+#[derive(Default)]
+struct SpanFields {
+    run_id: Option<String>,
+    text: String,
+}
+
+impl Visit for SpanFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "run_id" {
+            self.run_id = Some(value.to_string());
+        } else {
+            let _ = write!(self.text, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "run_id" {
+            self.run_id = Some(format!("{:?}", value).trim_matches('"').to_string());
+        } else {
+            let _ = write!(self.text, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+#[derive(Default)]
+struct EventFields {
+    message: String,
+    text: String,
+}
+
+impl Visit for EventFields {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.text, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+struct RunFile {
+    file: File,
+    written: u64,
+}
+
+pub struct RunFileLayer {
+    dir: PathBuf,
+    files: Mutex<HashMap<String, RunFile>>,
+}
+
+impl RunFileLayer {
+    fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            files: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn write_line(&self, run_id: &str, line: &str) {
+        let Ok(mut files) = self.files.lock() else {
+            return;
+        };
+
+        let path = self.dir.join(format!("{}.log", run_id));
+        let needs_rotate = files
+            .get(run_id)
+            .map(|f| f.written >= MAX_RUN_LOG_BYTES)
+            .unwrap_or(false);
+        if needs_rotate {
+            files.remove(run_id);
+            let _ = std::fs::rename(&path, path.with_extension("log.1"));
+        }
+
+        if !files.contains_key(run_id) {
+            let Ok(file) = OpenOptions::new().create(true).append(true).open(&path) else {
+                return;
+            };
+            let written = file.metadata().map(|m| m.len()).unwrap_or(0);
+            files.insert(run_id.to_string(), RunFile { file, written });
+        }
+
+        if let Some(run_file) = files.get_mut(run_id) {
+            if run_file.file.write_all(line.as_bytes()).is_ok() {
+                run_file.written += line.len() as u64;
+            }
+        }
+    }
+}
+
+impl<S> Layer<S> for RunFileLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = SpanFields::default();
+        attrs.record(&mut fields);
+        span.extensions_mut().insert(fields);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        if let Some(fields) = extensions.get_mut::<SpanFields>() {
+            values.record(fields);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let Some(scope) = ctx.event_scope(event) else {
+            return;
+        };
+
+        let mut run_id = None;
+        let mut context = String::new();
+        for span in scope.from_root() {
+            let extensions = span.extensions();
+            if let Some(fields) = extensions.get::<SpanFields>() {
+                if span.name() == RUN_SPAN_NAME {
+                    run_id = fields.run_id.clone();
+                }
+                context.push_str(&fields.text);
+            }
+        }
+        let Some(run_id) = run_id else {
+            return;
+        };
+
+        let mut fields = EventFields::default();
+        event.record(&mut fields);
+        let line = format!(
+            "{} {:>5}{}: {}{}\n",
+            chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.3f"),
+            event.metadata().level(),
+            context,
+            fields.message,
+            fields.text
+        );
+        self.write_line(&run_id, &line);
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        if span.name() != RUN_SPAN_NAME {
+            return;
+        }
+        let run_id = span.extensions().get::<SpanFields>().and_then(|f| f.run_id.clone());
+        if let (Some(run_id), Ok(mut files)) = (run_id, self.files.lock()) {
+            files.remove(&run_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::automation::log_user_action;
+    use crate::pii::{self, Email};
+    use std::sync::Arc;
+
+    const RAW_EMAIL: &str = "user@example.com";
+    const MASKED_EMAIL: &str = "u***@example.com";
+
+    // 콘솔 출력 대신 메모리에 모으는 writer
+    #[derive(Clone, Default)]
+    struct CaptureWriter(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CaptureWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl CaptureWriter {
+        fn take(&self) -> String {
+            String::from_utf8(std::mem::take(&mut *self.0.lock().unwrap())).unwrap()
+        }
+    }
+
+    // 자동화 실행과 같은 방식으로 run 스팬 안에서 이메일을 로그에 남김
+    fn log_run(run_id: &str, email: &Email) {
+        let span = tracing::info_span!(RUN_SPAN_NAME, run_id = %run_id, store = "서울");
+        let _entered = span.enter();
+        tracing::info!("📧 이메일 입력 완료: {}", email);
+        tracing::info!(email = %email, "✅ 연락처 항목 입력");
+        log_user_action("예약 성공", &format!("매장: 서울, 예약번호: A1, 이메일: {}", email));
+        tracing::warn!("{:?}", email);
+    }
+
+    fn run_log(dir: &Path, run_id: &str) -> String {
+        std::fs::read_to_string(dir.join(format!("{}.log", run_id))).unwrap_or_default()
+    }
+
+    #[test]
+    fn raw_email_never_reaches_logs_unless_opted_in() {
+        let dir = std::env::temp_dir().join(format!("rolex-logging-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let console = CaptureWriter::default();
+        let writer = console.clone();
+        let subscriber = tracing_subscriber::registry()
+            .with(fmt::layer().with_ansi(false).with_writer(move || writer.clone()))
+            .with(RunFileLayer::new(dir.clone()));
+        let email: Email = serde_json::from_value(serde_json::json!(RAW_EMAIL)).unwrap();
+
+        tracing::subscriber::with_default(subscriber, || {
+            pii::with_unredacted(false, || log_run("redacted", &email));
+
+            let output = console.take();
+            let file = run_log(&dir, "redacted");
+            for text in [&output, &file] {
+                assert!(!text.contains(RAW_EMAIL), "원본 이메일이 로그에 남음: {}", text);
+                assert!(text.contains(MASKED_EMAIL), "마스킹된 이메일이 없음: {}", text);
+            }
+
+            // 디버그 빌드에서 ROLEX_LOG_UNREDACTED=1 로 해제한 경우와 같음
+            pii::with_unredacted(true, || log_run("unredacted", &email));
+
+            assert!(console.take().contains(RAW_EMAIL));
+            assert!(run_log(&dir, "unredacted").contains(RAW_EMAIL));
+        });
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn run_ids_in_the_same_second_differ() {
+        let ids: Vec<_> = (0..3).map(|_| new_run_id("롤렉스 부티크/강남")).collect();
+        assert!(ids.iter().all(|id| validate_run_id(id).is_ok()));
+        assert!(ids[0].contains("-롤렉스_부티크_강남-"));
+        let unique: std::collections::HashSet<_> = ids.iter().collect();
+        assert_eq!(unique.len(), ids.len(), "{:?}", ids);
+    }
+}
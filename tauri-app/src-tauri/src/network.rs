@@ -0,0 +1,434 @@
+// 네트워크 상태 감시 - 예약 페이지의 문서 로딩 지연/실패를 CDP Network 이벤트로 감지하고 정해진 횟수만큼 새로고침
+// 새로고침은 요청 간격 조절(pacing) 을 거치며, 실행 동안의 요청 통계는 실행 기록에 저장됨
+use chromiumoxide::cdp::browser_protocol::network::{
+    EnableParams, EventLoadingFailed, EventLoadingFinished, EventRequestWillBeSent, EventResponseReceived,
+    ResourceType,
+};
+use chromiumoxide::Page;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use crate::control::Step;
+use crate::pacing::Pacer;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NetworkMonitorConfig {
+    // 문서 요청이 이 시간 안에 끝나지 않으면 지연으로 봄
+    #[serde(rename = "stallTimeoutSecs", default = "default_stall_timeout_secs")]
+    pub stall_timeout_secs: u64,
+    // 실행 하나에서 허용하는 최대 새로고침 횟수
+    #[serde(rename = "maxReloads", default = "default_max_reloads")]
+    pub max_reloads: u32,
+}
+
+fn default_stall_timeout_secs() -> u64 {
+    20
+}
+
+fn default_max_reloads() -> u32 {
+    3
+}
+
+impl Default for NetworkMonitorConfig {
+    fn default() -> Self {
+        Self {
+            stall_timeout_secs: default_stall_timeout_secs(),
+            max_reloads: default_max_reloads(),
+        }
+    }
+}
+
+pub fn validate(config: &NetworkMonitorConfig) -> Vec<(String, String)> {
+    let mut errors = Vec::new();
+    if config.stall_timeout_secs == 0 {
+        errors.push((
+            "stallTimeoutSecs".to_string(),
+            "지연 판단 시간은 1초 이상이어야 합니다".to_string(),
+        ));
+    }
+    if config.max_reloads > 20 {
+        errors.push(("maxReloads".to_string(), "새로고침 횟수는 20회 이하여야 합니다".to_string()));
+    }
+    errors
+}
+
+// 실행 기록에 남기는 네트워크 통계
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct NetworkStats {
+    pub requests: u32,
+    pub failed_requests: u32,
+    // 429/5xx 응답
+    pub error_responses: u32,
+    pub stalled_loads: u32,
+    pub failed_loads: u32,
+    pub reloads: u32,
+    // 예약 페이지 문서 로딩 중 가장 오래 걸린 시간
+    pub slowest_document_ms: Option<u64>,
+}
+
+impl NetworkStats {
+    pub fn is_empty(&self) -> bool {
+        self.requests == 0 && self.reloads == 0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetworkProblem {
+    Stalled { secs: u64 },
+    Failed { reason: String },
+}
+
+impl std::fmt::Display for NetworkProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NetworkProblem::Stalled { secs } => write!(f, "페이지 로딩 지연 ({}초 이상)", secs),
+            NetworkProblem::Failed { reason } => write!(f, "페이지 로딩 실패 ({})", reason),
+        }
+    }
+}
+
+#[derive(Default)]
+struct MonitorState {
+    // 진행 중인 예약 페이지 문서 요청 (request id -> 시작 시각)
+    documents: HashMap<String, Instant>,
+    failure: Option<String>,
+}
+
+impl MonitorState {
+    fn problem(&self, stall_timeout: Duration, now: Instant) -> Option<NetworkProblem> {
+        if let Some(reason) = &self.failure {
+            return Some(NetworkProblem::Failed { reason: reason.clone() });
+        }
+        self.documents
+            .values()
+            .map(|started| now.saturating_duration_since(*started))
+            .max()
+            .filter(|elapsed| *elapsed >= stall_timeout)
+            .map(|elapsed| NetworkProblem::Stalled { secs: elapsed.as_secs() })
+    }
+}
+
+// 단계 안의 재시도 루프에서 로딩 문제를 확인하기 위한 읽기 전용 핸들
+#[derive(Clone)]
+pub struct NetworkWatch {
+    state: Arc<Mutex<MonitorState>>,
+    stall_timeout: Duration,
+}
+
+impl NetworkWatch {
+    pub fn problem(&self) -> Option<NetworkProblem> {
+        self.state.lock().ok()?.problem(self.stall_timeout, Instant::now())
+    }
+}
+
+impl std::fmt::Debug for NetworkWatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NetworkWatch").field("stall_timeout", &self.stall_timeout).finish()
+    }
+}
+
+// 같은 모니터를 보는 핸들끼리 같음
+impl PartialEq for NetworkWatch {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.state, &other.state)
+    }
+}
+
+pub struct NetworkMonitor {
+    config: NetworkMonitorConfig,
+    state: Arc<Mutex<MonitorState>>,
+    stats: Arc<Mutex<NetworkStats>>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+// 인증 대기 중(팝업 조작)과 제출 직후(새로고침 시 재전송 위험)에는 새로고침하지 않음
+pub fn reloadable(step: Step) -> bool {
+    !matches!(step, Step::PassAuthentication | Step::CheckSuccess)
+}
+
+impl NetworkMonitor {
+    // 통계는 실행 컨텍스트와 공유 (실행이 어디서 끝나든 기록에 남도록)
+    pub async fn start(page: &Page, config: NetworkMonitorConfig, stats: Arc<Mutex<NetworkStats>>) -> Self {
+        let mut monitor = Self::new(config, stats);
+        let state = monitor.state.clone();
+        let stats = monitor.stats.clone();
+        let tasks = &mut monitor.tasks;
+
+        // 이미 활성화되어 있으면 무시됨
+        let _ = page.execute(EnableParams::default()).await;
+        // 인증 iframe 등 하위 프레임의 문서는 제외
+        let main_frame = page.mainframe().await.ok().flatten().map(|id| id.as_ref().to_string());
+
+        match page.event_listener::<EventRequestWillBeSent>().await {
+            Ok(mut events) => {
+                let state = state.clone();
+                let stats = stats.clone();
+                tasks.push(tokio::spawn(async move {
+                    while let Some(event) = events.next().await {
+                        if let Ok(mut stats) = stats.lock() {
+                            stats.requests += 1;
+                        }
+                        let is_main_document = event.r#type == Some(ResourceType::Document)
+                            && match (&main_frame, &event.frame_id) {
+                                (Some(main), Some(frame)) => main == frame.as_ref(),
+                                _ => true,
+                            };
+                        if !is_main_document {
+                            continue;
+                        }
+                        if let Ok(mut state) = state.lock() {
+                            state
+                                .documents
+                                .entry(event.request_id.as_ref().to_string())
+                                .or_insert_with(Instant::now);
+                        }
+                    }
+                }));
+            }
+            Err(e) => warn!("⚠️ 네트워크 요청 이벤트를 구독할 수 없습니다: {}", e),
+        }
+
+        match page.event_listener::<EventResponseReceived>().await {
+            Ok(mut events) => {
+                let state = state.clone();
+                let stats = stats.clone();
+                tasks.push(tokio::spawn(async move {
+                    while let Some(event) = events.next().await {
+                        let status = event.response.status;
+                        let error_status = status == 429 || (500..600).contains(&status);
+                        if error_status {
+                            if let Ok(mut stats) = stats.lock() {
+                                stats.error_responses += 1;
+                            }
+                        }
+                        // state 잠금을 놓은 뒤 stats 를 잠금 (두 잠금을 동시에 잡지 않음)
+                        let started = {
+                            let Ok(mut state) = state.lock() else {
+                                continue;
+                            };
+                            let Some(started) = state.documents.get(event.request_id.as_ref()).copied() else {
+                                continue;
+                            };
+                            if error_status {
+                                state.failure = Some(format!("HTTP {}", status));
+                            }
+                            started
+                        };
+                        if let Ok(mut stats) = stats.lock() {
+                            let elapsed = started.elapsed().as_millis() as u64;
+                            stats.slowest_document_ms = Some(stats.slowest_document_ms.unwrap_or(0).max(elapsed));
+                        }
+                    }
+                }));
+            }
+            Err(e) => warn!("⚠️ 네트워크 응답 이벤트를 구독할 수 없습니다: {}", e),
+        }
+
+        match page.event_listener::<EventLoadingFinished>().await {
+            Ok(mut events) => {
+                let state = state.clone();
+                tasks.push(tokio::spawn(async move {
+                    while let Some(event) = events.next().await {
+                        if let Ok(mut state) = state.lock() {
+                            state.documents.remove(event.request_id.as_ref());
+                        }
+                    }
+                }));
+            }
+            Err(e) => warn!("⚠️ 네트워크 완료 이벤트를 구독할 수 없습니다: {}", e),
+        }
+
+        match page.event_listener::<EventLoadingFailed>().await {
+            Ok(mut events) => {
+                let state = state.clone();
+                let stats = stats.clone();
+                tasks.push(tokio::spawn(async move {
+                    while let Some(event) = events.next().await {
+                        // 페이지 이동으로 취소된 요청은 실패로 보지 않음
+                        if event.canceled.unwrap_or(false) {
+                            continue;
+                        }
+                        if let Ok(mut stats) = stats.lock() {
+                            stats.failed_requests += 1;
+                        }
+                        if let Ok(mut state) = state.lock() {
+                            if state.documents.remove(event.request_id.as_ref()).is_some() {
+                                warn!("📡 예약 페이지 로딩 실패: {}", event.error_text);
+                                state.failure = Some(event.error_text.clone());
+                            }
+                        }
+                    }
+                }));
+            }
+            Err(e) => warn!("⚠️ 네트워크 실패 이벤트를 구독할 수 없습니다: {}", e),
+        }
+
+        monitor
+    }
+
+    fn new(config: NetworkMonitorConfig, stats: Arc<Mutex<NetworkStats>>) -> Self {
+        Self {
+            config,
+            state: Arc::new(Mutex::new(MonitorState::default())),
+            stats,
+            tasks: Vec::new(),
+        }
+    }
+
+    // 현재 예약 페이지의 로딩 문제 (없으면 None)
+    pub fn problem(&self) -> Option<NetworkProblem> {
+        self.watch().problem()
+    }
+
+    pub fn watch(&self) -> NetworkWatch {
+        NetworkWatch {
+            state: self.state.clone(),
+            stall_timeout: Duration::from_secs(self.config.stall_timeout_secs),
+        }
+    }
+
+    // 새로고침 한 번을 사용 - 허용 횟수를 넘으면 Err
+    // 어느 쪽이든 감지한 문제는 지워서 같은 문제를 다시 세지 않음
+    pub fn take_reload(&self, problem: &NetworkProblem) -> Result<u32, String> {
+        let reloaded = {
+            let mut stats = self.stats.lock().map_err(|e| e.to_string())?;
+            match problem {
+                NetworkProblem::Stalled { .. } => stats.stalled_loads += 1,
+                NetworkProblem::Failed { .. } => stats.failed_loads += 1,
+            }
+            if stats.reloads < self.config.max_reloads {
+                stats.reloads += 1;
+                Some(stats.reloads)
+            } else {
+                None
+            }
+        };
+        if let Ok(mut state) = self.state.lock() {
+            state.documents.clear();
+            state.failure = None;
+        }
+        reloaded.ok_or_else(|| {
+            format!(
+                "{} - 새로고침 허용 횟수({}회)를 모두 사용했습니다",
+                problem, self.config.max_reloads
+            )
+        })
+    }
+
+    // 요청 간격 조절을 거쳐 예약 페이지 새로고침
+    pub async fn reload(&self, page: &Page, pacer: &Pacer, problem: &NetworkProblem) -> Result<(), String> {
+        let reload = self.take_reload(problem)?;
+        warn!("🔄 {} - 새로고침 {}/{}", problem, reload, self.config.max_reloads);
+        pacer.wait_turn("새로고침").await;
+
+        match tokio::time::timeout(Duration::from_secs(self.config.stall_timeout_secs), page.reload()).await {
+            Ok(Ok(_)) => {
+                info!("✅ 새로고침 완료");
+                Ok(())
+            }
+            Ok(Err(e)) => Err(format!("새로고침 실패: {}", e)),
+            Err(_) => Err(format!("새로고침 타임아웃 ({}초)", self.config.stall_timeout_secs)),
+        }
+    }
+}
+
+impl Drop for NetworkMonitor {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::retry::RetryPolicy;
+
+    fn monitor(max_reloads: u32) -> NetworkMonitor {
+        let config = NetworkMonitorConfig {
+            stall_timeout_secs: 5,
+            max_reloads,
+        };
+        NetworkMonitor::new(config, Arc::new(Mutex::new(NetworkStats::default())))
+    }
+
+    fn start_document(monitor: &NetworkMonitor, id: &str, age: Duration) {
+        monitor
+            .state
+            .lock()
+            .unwrap()
+            .documents
+            .insert(id.to_string(), Instant::now() - age);
+    }
+
+    #[test]
+    fn documents_past_the_stall_timeout_are_stalled() {
+        let monitor = monitor(3);
+        assert_eq!(monitor.problem(), None);
+
+        start_document(&monitor, "fresh", Duration::from_secs(1));
+        assert_eq!(monitor.problem(), None);
+
+        start_document(&monitor, "slow", Duration::from_secs(7));
+        assert_eq!(monitor.problem(), Some(NetworkProblem::Stalled { secs: 7 }));
+
+        // 실패가 있으면 지연보다 먼저 보고
+        monitor.state.lock().unwrap().failure = Some("HTTP 503".to_string());
+        assert_eq!(
+            monitor.problem(),
+            Some(NetworkProblem::Failed { reason: "HTTP 503".to_string() })
+        );
+    }
+
+    #[test]
+    fn reload_clears_the_problem() {
+        let monitor = monitor(3);
+        start_document(&monitor, "slow", Duration::from_secs(10));
+        let problem = monitor.problem().unwrap();
+
+        assert_eq!(monitor.take_reload(&problem), Ok(1));
+        assert_eq!(monitor.problem(), None);
+        assert!(monitor.state.lock().unwrap().documents.is_empty());
+    }
+
+    #[test]
+    fn reloads_stop_when_the_budget_is_used() {
+        let monitor = monitor(2);
+        let stalled = NetworkProblem::Stalled { secs: 5 };
+        let failed = NetworkProblem::Failed { reason: "net::ERR_CONNECTION_RESET".to_string() };
+
+        assert_eq!(monitor.take_reload(&stalled), Ok(1));
+        assert_eq!(monitor.take_reload(&failed), Ok(2));
+        monitor.state.lock().unwrap().failure = Some("HTTP 502".to_string());
+        let err = monitor.take_reload(&failed).unwrap_err();
+        assert!(err.contains("새로고침 허용 횟수(2회)"), "{}", err);
+        // 예산을 다 써도 같은 문제를 다시 세지 않도록 지움
+        assert_eq!(monitor.problem(), None);
+
+        let stats = monitor.stats.lock().unwrap().clone();
+        assert_eq!(stats.reloads, 2);
+        assert_eq!(stats.stalled_loads, 1);
+        assert_eq!(stats.failed_loads, 2);
+    }
+
+    #[tokio::test]
+    async fn retry_attempts_stop_when_the_page_has_a_problem() {
+        let monitor = monitor(3);
+        let policy = RetryPolicy {
+            watch: Some(monitor.watch()),
+            ..RetryPolicy::default_for(Step::Next)
+        };
+        let mut attempts = policy.start();
+        assert_eq!(attempts.next().await, Some(1));
+
+        monitor.state.lock().unwrap().failure = Some("HTTP 500".to_string());
+        assert_eq!(attempts.next().await, None);
+    }
+}
@@ -0,0 +1,117 @@
+// 데스크톱 알림 (tauri-plugin-notification) 과 이벤트별 알림 설정
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tracing::warn;
+
+use crate::events::{self, RunEvent, RunEventKind};
+
+pub const PREFERENCES_FILE_NAME: &str = "notification_preferences.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationPreferences {
+    pub waiting_for_user: bool,
+    pub success: bool,
+    pub failure: bool,
+    pub cancellation: bool,
+    // 사용자 개입이 필요할 때 해당 브라우저 창을 자동으로 앞으로 가져옴
+    pub focus_browser_on_wait: bool,
+}
+
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            waiting_for_user: true,
+            success: true,
+            failure: true,
+            cancellation: true,
+            focus_browser_on_wait: true,
+        }
+    }
+}
+
+impl NotificationPreferences {
+    fn is_enabled(&self, kind: RunEventKind) -> bool {
+        match kind {
+            RunEventKind::WaitingForUser | RunEventKind::ChoiceRequired => self.waiting_for_user,
+            RunEventKind::Succeeded => self.success,
+            RunEventKind::Failed => self.failure,
+            RunEventKind::Cancelled => self.cancellation,
+            // 사용자가 직접 요청한 동작이므로 데스크톱 알림은 띄우지 않음
+            RunEventKind::Paused | RunEventKind::Resumed => false,
+        }
+    }
+}
+
+// Tauri 관리 상태
+#[derive(Default)]
+pub struct NotificationSettings(pub Mutex<NotificationPreferences>);
+
+pub fn preferences_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join(PREFERENCES_FILE_NAME)
+}
+
+// 파일이 없거나 읽을 수 없으면 기본값 사용
+pub fn load_preferences(path: &Path) -> NotificationPreferences {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_preferences(path: &Path, preferences: &NotificationPreferences) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("설정 디렉토리를 만들 수 없습니다 ({}): {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(preferences).map_err(|e| e.to_string())?;
+    std::fs::write(path, json)
+        .map_err(|e| format!("알림 설정을 저장할 수 없습니다 ({}): {}", path.display(), e))
+}
+
+fn title(event: &RunEvent) -> String {
+    match event.kind {
+        RunEventKind::WaitingForUser => format!("🔔 {} - 사용자 개입 필요", event.store_name),
+        RunEventKind::Succeeded => format!("🎉 {} - 예약 성공", event.store_name),
+        RunEventKind::Failed => format!("❌ {} - 예약 실패", event.store_name),
+        RunEventKind::Cancelled => format!("🛑 {} - 자동화 중지", event.store_name),
+        RunEventKind::ChoiceRequired => format!("📅 {} - 예약 시간 선택 필요", event.store_name),
+        RunEventKind::Paused => format!("⏸️ {} - 일시정지", event.store_name),
+        RunEventKind::Resumed => format!("▶️ {} - 재개", event.store_name),
+    }
+}
+
+pub fn notify_desktop(app: &AppHandle, event: &RunEvent) {
+    let preferences = app
+        .state::<NotificationSettings>()
+        .0
+        .lock()
+        .map(|p| p.clone())
+        .unwrap_or_default();
+    if !preferences.is_enabled(event.kind) {
+        return;
+    }
+
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title(title(event))
+        .body(&event.message)
+        .show()
+    {
+        warn!("⚠️ 데스크톱 알림 표시 실패: {}", e);
+    }
+
+    if event.kind == RunEventKind::WaitingForUser && preferences.focus_browser_on_wait {
+        let app = app.clone();
+        let run_id = event.run_id.clone();
+        tokio::spawn(async move {
+            if let Err(e) = events::focus_browser(&app, &run_id).await {
+                warn!("⚠️ {}", e);
+            }
+        });
+    }
+}
@@ -0,0 +1,341 @@
+// 요청 간격 조절 - 오픈 시간에 서버가 느리거나 429/5xx 를 돌려줄 때 같은 간격으로 계속 요청하지 않도록 함
+// CDP Network 응답 이벤트로 서버 상태를 보고, 페이지 이동/새로고침 전에 최소 간격과 backoff 만큼 기다림
+use chromiumoxide::cdp::browser_protocol::network::{EnableParams, EventResponseReceived, ResourceType};
+use chromiumoxide::Page;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use crate::control::Step;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PacingConfig {
+    // 페이지 이동/새로고침 사이 최소 간격
+    #[serde(rename = "minIntervalMs", default = "default_min_interval_ms")]
+    pub min_interval_ms: u64,
+    // 첫 429/5xx 후 대기 시간 (연속 오류마다 factor 배)
+    #[serde(rename = "initialBackoffMs", default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    #[serde(default = "default_factor")]
+    pub factor: f64,
+    #[serde(rename = "maxBackoffMs", default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    // Retry-After 헤더를 따를지 (최대 maxBackoffMs)
+    #[serde(rename = "honorRetryAfter", default = "default_honor_retry_after")]
+    pub honor_retry_after: bool,
+}
+
+fn default_min_interval_ms() -> u64 {
+    1000
+}
+
+fn default_initial_backoff_ms() -> u64 {
+    2000
+}
+
+fn default_factor() -> f64 {
+    2.0
+}
+
+fn default_max_backoff_ms() -> u64 {
+    60_000
+}
+
+fn default_honor_retry_after() -> bool {
+    true
+}
+
+impl Default for PacingConfig {
+    fn default() -> Self {
+        Self {
+            min_interval_ms: default_min_interval_ms(),
+            initial_backoff_ms: default_initial_backoff_ms(),
+            factor: default_factor(),
+            max_backoff_ms: default_max_backoff_ms(),
+            honor_retry_after: default_honor_retry_after(),
+        }
+    }
+}
+
+impl PacingConfig {
+    // consecutive 번째 연속 오류 후 대기 시간 (1 부터)
+    fn backoff(&self, consecutive: u32) -> Duration {
+        let n = consecutive.saturating_sub(1).min(30) as i32;
+        let ms = (self.initial_backoff_ms as f64 * self.factor.powi(n)).min(self.max_backoff_ms as f64);
+        Duration::from_millis(ms as u64)
+    }
+}
+
+pub fn validate(config: &PacingConfig) -> Vec<(String, String)> {
+    let mut errors = Vec::new();
+    if !(1.0..=10.0).contains(&config.factor) {
+        errors.push(("factor".to_string(), "증가 배수는 1 이상 10 이하여야 합니다".to_string()));
+    }
+    if config.max_backoff_ms < config.initial_backoff_ms {
+        errors.push((
+            "maxBackoffMs".to_string(),
+            "최대 대기 시간은 initialBackoffMs 이상이어야 합니다".to_string(),
+        ));
+    }
+    errors
+}
+
+// 간격 조절 대상 응답 (이미지/스타일 등 하위 리소스 오류는 무시)
+fn is_paced_resource(resource: &ResourceType) -> bool {
+    matches!(resource, ResourceType::Document | ResourceType::Xhr | ResourceType::Fetch)
+}
+
+fn is_throttling_status(status: i64) -> bool {
+    status == 429 || (500..600).contains(&status)
+}
+
+// Retry-After: 초 단위 숫자 또는 HTTP 날짜
+fn retry_after(headers: &serde_json::Value) -> Option<Duration> {
+    let value = headers
+        .as_object()?
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("retry-after"))?
+        .1
+        .as_str()?
+        .trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    (at.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().ok()
+}
+
+#[derive(Default)]
+struct PacerState {
+    last_navigation: Option<Instant>,
+    blocked_until: Option<Instant>,
+    consecutive_errors: u32,
+}
+
+impl PacerState {
+    // 429/5xx 응답 기록 - 적용한 대기 시간과 Retry-After 사용 여부
+    fn record_error(&mut self, config: &PacingConfig, headers: &serde_json::Value, now: Instant) -> (Duration, bool) {
+        self.consecutive_errors += 1;
+        let header_delay = retry_after(headers)
+            .filter(|_| config.honor_retry_after)
+            .map(|delay| delay.min(Duration::from_millis(config.max_backoff_ms)));
+        let delay = header_delay.unwrap_or_else(|| config.backoff(self.consecutive_errors));
+        let until = now + delay;
+        if self.blocked_until.map(|current| until > current).unwrap_or(true) {
+            self.blocked_until = Some(until);
+        }
+        (delay, header_delay.is_some())
+    }
+
+    // 다음 이동까지 대기 시간과 그 대기가 서버 오류 backoff 때문인지
+    fn next_wait(&self, config: &PacingConfig, now: Instant) -> (Duration, bool) {
+        let interval = self
+            .last_navigation
+            .map(|last| (last + Duration::from_millis(config.min_interval_ms)).saturating_duration_since(now))
+            .unwrap_or_default();
+        let backoff = self
+            .blocked_until
+            .map(|until| until.saturating_duration_since(now))
+            .unwrap_or_default();
+        (interval.max(backoff), backoff > interval)
+    }
+}
+
+pub struct Pacer {
+    config: PacingConfig,
+    state: Arc<Mutex<PacerState>>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl Pacer {
+    // 응답 이벤트를 구독하지 못해도 최소 간격은 적용
+    pub async fn start(page: &Page, config: PacingConfig) -> Self {
+        let state = Arc::new(Mutex::new(PacerState::default()));
+        let mut tasks = Vec::new();
+
+        // 이미 활성화되어 있으면 무시됨
+        let _ = page.execute(EnableParams::default()).await;
+
+        match page.event_listener::<EventResponseReceived>().await {
+            Ok(mut events) => {
+                let state = state.clone();
+                let config = config.clone();
+                tasks.push(tokio::spawn(async move {
+                    while let Some(event) = events.next().await {
+                        if !is_paced_resource(&event.r#type) {
+                            continue;
+                        }
+                        let status = event.response.status;
+                        let Ok(mut state) = state.lock() else {
+                            continue;
+                        };
+                        if !is_throttling_status(status) {
+                            if event.r#type == ResourceType::Document && state.consecutive_errors > 0 {
+                                info!("✅ 서버 응답 정상화 ({}) - 요청 간격 조절 해제", status);
+                                state.consecutive_errors = 0;
+                            }
+                            continue;
+                        }
+
+                        let (delay, from_header) =
+                            state.record_error(&config, event.response.headers.inner(), Instant::now());
+                        warn!(
+                            status,
+                            url = %event.response.url,
+                            "🐢 서버 응답 {} - 다음 요청까지 {:.1}초 대기 ({}, 연속 {}회)",
+                            status,
+                            delay.as_secs_f64(),
+                            if from_header { "Retry-After" } else { "backoff" },
+                            state.consecutive_errors
+                        );
+                    }
+                }));
+            }
+            Err(e) => warn!("⚠️ 네트워크 응답 이벤트를 구독할 수 없습니다: {}", e),
+        }
+
+        Self { config, state, tasks }
+    }
+
+    // 페이지 이동/새로고침 전에 호출 - 최소 간격과 서버 오류 backoff 중 긴 쪽만큼 대기
+    pub async fn wait_turn(&self, action: &str) {
+        let (wait, throttled) = {
+            let Ok(state) = self.state.lock() else {
+                return;
+            };
+            state.next_wait(&self.config, Instant::now())
+        };
+
+        if !wait.is_zero() {
+            if throttled {
+                info!("🐢 요청 간격 조절: {} 전 서버 오류 backoff {:.1}초 대기", action, wait.as_secs_f64());
+            } else {
+                info!("⏱️ 요청 간격 조절: {} 전 {}ms 대기", action, wait.as_millis());
+            }
+            tokio::time::sleep(wait).await;
+        }
+
+        if let Ok(mut state) = self.state.lock() {
+            state.last_navigation = Some(Instant::now());
+        }
+    }
+}
+
+impl Drop for Pacer {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+// 클릭으로 페이지 이동/요청이 일어나는 단계
+pub fn navigates(step: Step) -> bool {
+    matches!(
+        step,
+        Step::VisitReservation | Step::RolexCollection | Step::Next | Step::Submit
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    fn config() -> PacingConfig {
+        PacingConfig {
+            min_interval_ms: 1000,
+            initial_backoff_ms: 2000,
+            factor: 2.0,
+            max_backoff_ms: 10_000,
+            honor_retry_after: true,
+        }
+    }
+
+    #[test]
+    fn backoff_grows_per_consecutive_error_up_to_the_cap() {
+        let config = config();
+        let delays: Vec<Duration> = (1..=5).map(|n| config.backoff(n)).collect();
+        assert_eq!(delays, [ms(2000), ms(4000), ms(8000), ms(10_000), ms(10_000)]);
+        assert_eq!(config.backoff(0), ms(2000));
+        assert_eq!(config.backoff(u32::MAX), ms(10_000));
+    }
+
+    #[test]
+    fn retry_after_accepts_seconds_and_http_dates() {
+        assert_eq!(retry_after(&serde_json::json!({ "Retry-After": " 30 " })), Some(Duration::from_secs(30)));
+        assert_eq!(retry_after(&serde_json::json!({ "retry-after": "0" })), Some(Duration::ZERO));
+
+        let at = (chrono::Utc::now() + chrono::Duration::seconds(120)).to_rfc2822();
+        let delay = retry_after(&serde_json::json!({ "Retry-After": at })).unwrap();
+        assert!(delay > Duration::from_secs(110) && delay <= Duration::from_secs(120), "{:?}", delay);
+
+        // 지난 날짜, 형식 오류, 헤더 없음
+        let past = (chrono::Utc::now() - chrono::Duration::seconds(60)).to_rfc2822();
+        assert_eq!(retry_after(&serde_json::json!({ "Retry-After": past })), None);
+        assert_eq!(retry_after(&serde_json::json!({ "Retry-After": "soon" })), None);
+        assert_eq!(retry_after(&serde_json::json!({})), None);
+    }
+
+    #[test]
+    fn retry_after_is_capped_at_max_backoff() {
+        let now = Instant::now();
+        let mut state = PacerState::default();
+        let (delay, from_header) = state.record_error(&config(), &serde_json::json!({ "Retry-After": "3600" }), now);
+        assert_eq!(delay, ms(10_000));
+        assert!(from_header);
+        assert_eq!(state.blocked_until, Some(now + ms(10_000)));
+
+        let ignore_header = PacingConfig { honor_retry_after: false, ..config() };
+        let mut state = PacerState::default();
+        let (delay, from_header) = state.record_error(&ignore_header, &serde_json::json!({ "Retry-After": "5" }), now);
+        assert_eq!(delay, ms(2000));
+        assert!(!from_header);
+    }
+
+    #[test]
+    fn consecutive_errors_extend_the_block() {
+        let now = Instant::now();
+        let mut state = PacerState::default();
+        let no_header = serde_json::json!({});
+        let delays: Vec<Duration> = (0..3).map(|_| state.record_error(&config(), &no_header, now).0).collect();
+        assert_eq!(delays, [ms(2000), ms(4000), ms(8000)]);
+        assert_eq!(state.consecutive_errors, 3);
+        assert_eq!(state.blocked_until, Some(now + ms(8000)));
+
+        // 더 짧은 Retry-After 가 와도 이미 정한 대기 시간은 줄이지 않음
+        state.record_error(&config(), &serde_json::json!({ "Retry-After": "1" }), now);
+        assert_eq!(state.blocked_until, Some(now + ms(8000)));
+    }
+
+    #[test]
+    fn wait_turn_uses_the_longer_of_interval_and_backoff() {
+        let now = Instant::now();
+        let config = config();
+
+        assert_eq!(PacerState::default().next_wait(&config, now), (Duration::ZERO, false));
+
+        let mut state = PacerState {
+            last_navigation: Some(now - ms(400)),
+            ..PacerState::default()
+        };
+        assert_eq!(state.next_wait(&config, now), (ms(600), false));
+
+        state.blocked_until = Some(now + ms(3000));
+        assert_eq!(state.next_wait(&config, now), (ms(3000), true));
+
+        state.blocked_until = Some(now + ms(100));
+        assert_eq!(state.next_wait(&config, now), (ms(600), false));
+
+        state.last_navigation = Some(now - ms(5000));
+        state.blocked_until = Some(now - ms(1));
+        assert_eq!(state.next_wait(&config, now), (Duration::ZERO, false));
+    }
+}
@@ -0,0 +1,438 @@
+// 예약 페이지 상태 분류 - 페이지 전체 텍스트 대신 날짜 목록과 안내 영역만 보고 판단
+use chromiumoxide::Page;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::control::Step;
+use crate::locators::{self, Locator, LocatorOverrides};
+
+// 예약 마감 안내가 표시되는 영역 (본문 전체가 아닌 예약 폼 주변만 검사)
+const NOTICE_SELECTORS: &str = "#appointment .datetime-form .notice, \
+     #appointment .datetime-form .alert, \
+     #appointment .datetime-form .message, \
+     #appointment .closed, \
+     #appointment .appointment-closed";
+
+const CLOSED_KEYWORDS: [&str; 3] = ["온라인 예약이 마감되었습니다", "예약이 마감", "예약 마감"];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum PageState {
+    Open,
+    Closed { reason: String },
+    NoSlotsForDate { date: String },
+    Unknown,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct DateSlot {
+    pub date: String,
+    #[serde(default)]
+    pub off: bool,
+}
+
+// 페이지에서 읽어온 판단 근거
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct PageSnapshot {
+    #[serde(default)]
+    pub form_present: bool,
+    #[serde(default)]
+    pub notices: Vec<String>,
+    #[serde(default)]
+    pub dates: Vec<DateSlot>,
+}
+
+pub fn classify(snapshot: &PageSnapshot, target_date: Option<&str>) -> PageState {
+    if let Some(notice) = snapshot
+        .notices
+        .iter()
+        .find(|text| CLOSED_KEYWORDS.iter().any(|k| text.contains(k)))
+    {
+        return PageState::Closed {
+            reason: notice.trim().to_string(),
+        };
+    }
+
+    if !snapshot.form_present || snapshot.dates.is_empty() {
+        return PageState::Unknown;
+    }
+
+    if snapshot.dates.iter().all(|slot| slot.off) {
+        return PageState::Closed {
+            reason: "선택 가능한 날짜가 없습니다".to_string(),
+        };
+    }
+
+    match target_date {
+        Some(date) => match snapshot.dates.iter().find(|slot| slot.date == date) {
+            Some(slot) if !slot.off => PageState::Open,
+            _ => PageState::NoSlotsForDate {
+                date: date.to_string(),
+            },
+        },
+        None => PageState::Open,
+    }
+}
+
+pub async fn read_snapshot(page: &Page) -> Result<PageSnapshot, String> {
+    let snapshot_js = format!(
+        r#"
+        (() => {{
+            const form = document.querySelector('#appointment .datetime-form');
+            const notices = Array.from(document.querySelectorAll('{}'))
+                .filter(el => window.getComputedStyle(el).display !== 'none')
+                .map(el => el.textContent.trim())
+                .filter(text => text.length > 0);
+            const dates = Array.from(document.querySelectorAll('#appointment .datetime-form .date-list ul > li[data-date]'))
+                .map(li => ({{ date: li.getAttribute('data-date'), off: li.classList.contains('off') }}));
+            return {{ form_present: !!form, notices, dates }};
+        }})()
+    "#,
+        NOTICE_SELECTORS
+    );
+
+    page.evaluate_expression(snapshot_js)
+        .await
+        .map_err(|e| format!("페이지 상태 조회 실패: {}", e))?
+        .into_value::<PageSnapshot>()
+        .map_err(|e| format!("페이지 상태 파싱 실패: {}", e))
+}
+
+pub async fn detect(page: &Page, target_date: Option<&str>) -> PageState {
+    match read_snapshot(page).await {
+        Ok(snapshot) => classify(&snapshot, target_date),
+        Err(e) => {
+            warn!("⚠️ {}", e);
+            PageState::Unknown
+        }
+    }
+}
+
+// 예약 흐름에서 현재 페이지가 도달한 단계 - 재시도할 때 처음부터가 아니라 이 단계부터 이어감
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FlowStage {
+    // 매장 안내 페이지 (방문 예약하기 버튼)
+    Landing,
+    // 컬렉션 선택 (롤렉스 컬렉션 버튼)
+    CollectionChoice,
+    // 개인정보 동의 (동의합니다 버튼)
+    Terms,
+    // 날짜/시간 선택 폼
+    DateTime,
+    // PASS 인증 후 연락처 입력 폼
+    Contact,
+    Unknown,
+}
+
+impl FlowStage {
+    // 이 단계에서 이어서 실행할 자동화 단계
+    pub fn resume_step(&self) -> Step {
+        match self {
+            FlowStage::Landing => Step::VisitReservation,
+            FlowStage::CollectionChoice => Step::RolexCollection,
+            FlowStage::Terms => Step::Agree,
+            FlowStage::DateTime => Step::VisitDate,
+            FlowStage::Contact => Step::Submit,
+            FlowStage::Unknown => Step::CookiePopup,
+        }
+    }
+}
+
+// 각 단계의 버튼/폼이 화면에 보이는지 여부 (셀렉터는 automation.rs 의 각 단계와 동일)
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct StageSnapshot {
+    #[serde(default)]
+    pub reservation_button: bool,
+    #[serde(default)]
+    pub collection_button: bool,
+    #[serde(default)]
+    pub agree_button: bool,
+    #[serde(default)]
+    pub date_list: bool,
+    #[serde(default)]
+    pub contact_form: bool,
+}
+
+// 흐름상 뒤쪽 단계의 표식을 우선함 (이전 단계의 요소가 숨겨지지 않고 남아 있을 수 있음)
+pub fn classify_stage(snapshot: &StageSnapshot) -> FlowStage {
+    if snapshot.contact_form {
+        FlowStage::Contact
+    } else if snapshot.date_list {
+        FlowStage::DateTime
+    } else if snapshot.agree_button {
+        FlowStage::Terms
+    } else if snapshot.collection_button {
+        FlowStage::CollectionChoice
+    } else if snapshot.reservation_button {
+        FlowStage::Landing
+    } else {
+        FlowStage::Unknown
+    }
+}
+
+// 버튼 단계는 자동화와 같은 로케이터 목록(설정의 locators, 없으면 기본 목록)으로 확인
+pub async fn read_stage_snapshot(page: &Page, overrides: &LocatorOverrides) -> StageSnapshot {
+    let fixed = |selector: &str| {
+        vec![Locator::Css {
+            selector: selector.to_string(),
+        }]
+    };
+    StageSnapshot {
+        reservation_button: locators::any_visible(page, &locators::chain(overrides, Step::VisitReservation)).await,
+        collection_button: locators::any_visible(page, &locators::chain(overrides, Step::RolexCollection)).await,
+        agree_button: locators::any_visible(page, &locators::chain(overrides, Step::Agree)).await,
+        date_list: locators::any_visible(page, &fixed("#appointment .datetime-form .date-list")).await,
+        contact_form: locators::any_visible(page, &fixed(r#"input[name="email"]"#)).await,
+    }
+}
+
+pub async fn detect_stage(page: &Page, overrides: &LocatorOverrides) -> FlowStage {
+    classify_stage(&read_stage_snapshot(page, overrides).await)
+}
+
+// 날짜/시간 선택 폼에서 선택 가능한(off 가 아닌) 항목 - 사용자가 직접 고를 수 있도록 UI 에 표시
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct AvailableSlots {
+    // YYYY-MM-DD
+    pub dates: Vec<String>,
+    // HH:mm (현재 선택된 날짜 기준, 날짜를 고르기 전에는 비어 있을 수 있음)
+    pub times: Vec<String>,
+    pub selected_date: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct TimeSlot {
+    // 자정부터의 분 (예: 17:30 -> "1050")
+    pub time: String,
+    #[serde(default)]
+    pub off: bool,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct SlotSnapshot {
+    #[serde(default)]
+    pub dates: Vec<DateSlot>,
+    #[serde(default)]
+    pub times: Vec<TimeSlot>,
+    #[serde(default)]
+    pub selected_date: Option<String>,
+}
+
+pub fn available_slots(snapshot: &SlotSnapshot) -> AvailableSlots {
+    AvailableSlots {
+        dates: snapshot
+            .dates
+            .iter()
+            .filter(|slot| !slot.off && !slot.date.is_empty())
+            .map(|slot| slot.date.clone())
+            .collect(),
+        times: snapshot
+            .times
+            .iter()
+            .filter(|slot| !slot.off)
+            .filter_map(|slot| minutes_to_time(&slot.time))
+            .collect(),
+        selected_date: snapshot.selected_date.clone(),
+    }
+}
+
+// data-time 값(분)을 HH:mm 으로 변환
+fn minutes_to_time(value: &str) -> Option<String> {
+    let minutes: u32 = value.trim().parse().ok()?;
+    if minutes >= 24 * 60 {
+        return None;
+    }
+    Some(format!("{:02}:{:02}", minutes / 60, minutes % 60))
+}
+
+pub async fn read_available_slots(page: &Page) -> Result<AvailableSlots, String> {
+    let slots_js = r#"
+        (() => {
+            const base = '#appointment .datetime-form';
+            const dates = Array.from(document.querySelectorAll(base + ' .date-list ul > li[data-date]'))
+                .map(li => ({ date: li.getAttribute('data-date'), off: li.classList.contains('off') }));
+            const times = Array.from(document.querySelectorAll(base + ' .time-list ul > li[data-time]'))
+                .filter(li => window.getComputedStyle(li).display !== 'none')
+                .map(li => ({ time: li.getAttribute('data-time'), off: li.classList.contains('off') }));
+            const selected = document.querySelector(base + ' .date-list ul > li[data-date].on, '
+                + base + ' .date-list ul > li[data-date].active, '
+                + base + ' .date-list ul > li[data-date].selected');
+            return { dates, times, selected_date: selected ? selected.getAttribute('data-date') : null };
+        })()
+    "#;
+
+    let snapshot = page
+        .evaluate_expression(slots_js)
+        .await
+        .map_err(|e| format!("예약 가능 시간 조회 실패: {}", e))?
+        .into_value::<SlotSnapshot>()
+        .map_err(|e| format!("예약 가능 시간 파싱 실패: {}", e))?;
+    Ok(available_slots(&snapshot))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // read_available_slots 의 스크립트가 돌려주는 형태 그대로
+    fn slot_snapshot(value: serde_json::Value) -> SlotSnapshot {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn available_slots_skip_off_dates_and_times() {
+        let slots = available_slots(&slot_snapshot(json!({
+            "dates": [
+                { "date": "2025-06-01", "off": true },
+                { "date": "2025-06-02", "off": false },
+                { "date": "2025-06-03" },
+            ],
+            "times": [
+                { "time": "600", "off": true },
+                { "time": "660", "off": false },
+            ],
+            "selected_date": null,
+        })));
+        assert_eq!(slots.dates, vec!["2025-06-02", "2025-06-03"]);
+        assert_eq!(slots.times, vec!["11:00"]);
+    }
+
+    #[test]
+    fn available_slots_drop_empty_dates() {
+        let slots = available_slots(&slot_snapshot(json!({
+            "dates": [{ "date": "", "off": false }, { "date": "2025-06-02", "off": false }],
+        })));
+        assert_eq!(slots.dates, vec!["2025-06-02"]);
+        assert!(slots.times.is_empty());
+    }
+
+    #[test]
+    fn minutes_convert_to_hh_mm() {
+        assert_eq!(minutes_to_time("1050").as_deref(), Some("17:30"));
+        assert_eq!(minutes_to_time(" 0 ").as_deref(), Some("00:00"));
+        assert_eq!(minutes_to_time("1439").as_deref(), Some("23:59"));
+    }
+
+    #[test]
+    fn minutes_out_of_range_or_non_numeric_are_rejected() {
+        assert_eq!(minutes_to_time("1440"), None);
+        assert_eq!(minutes_to_time("2000"), None);
+        assert_eq!(minutes_to_time("17:30"), None);
+        assert_eq!(minutes_to_time("abc"), None);
+        assert_eq!(minutes_to_time("-30"), None);
+
+        let slots = available_slots(&slot_snapshot(json!({
+            "times": [{ "time": "1440" }, { "time": "x" }, { "time": "900" }],
+        })));
+        assert_eq!(slots.times, vec!["15:00"]);
+    }
+
+    #[test]
+    fn selected_date_passes_through() {
+        let slots = available_slots(&slot_snapshot(json!({
+            "dates": [{ "date": "2025-06-02" }],
+            "selected_date": "2025-06-02",
+        })));
+        assert_eq!(slots.selected_date.as_deref(), Some("2025-06-02"));
+    }
+
+    // read_snapshot 의 스크립트가 돌려주는 형태 그대로
+    fn page_snapshot(value: serde_json::Value) -> PageSnapshot {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn open_dates() -> serde_json::Value {
+        json!([
+            { "date": "2025-06-01", "off": true },
+            { "date": "2025-06-02", "off": false },
+        ])
+    }
+
+    #[test]
+    fn closed_notice_wins_over_dates() {
+        let snapshot = page_snapshot(json!({
+            "form_present": true,
+            "notices": ["  온라인 예약이 마감되었습니다. 다음 기회를 이용해주세요  "],
+            "dates": open_dates(),
+        }));
+        assert_eq!(
+            classify(&snapshot, Some("2025-06-02")),
+            PageState::Closed {
+                reason: "온라인 예약이 마감되었습니다. 다음 기회를 이용해주세요".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn unrelated_closing_text_stays_open() {
+        let snapshot = page_snapshot(json!({
+            "form_present": true,
+            "notices": ["매장 영업 마감 시간은 20:00 입니다", "마감 임박"],
+            "dates": open_dates(),
+        }));
+        assert_eq!(classify(&snapshot, None), PageState::Open);
+        assert_eq!(classify(&snapshot, Some("2025-06-02")), PageState::Open);
+    }
+
+    #[test]
+    fn all_dates_off_is_closed() {
+        let snapshot = page_snapshot(json!({
+            "form_present": true,
+            "dates": [
+                { "date": "2025-06-01", "off": true },
+                { "date": "2025-06-02", "off": true },
+            ],
+        }));
+        assert!(matches!(classify(&snapshot, None), PageState::Closed { .. }));
+    }
+
+    #[test]
+    fn target_date_off_or_missing_has_no_slots() {
+        let snapshot = page_snapshot(json!({ "form_present": true, "dates": open_dates() }));
+        assert_eq!(
+            classify(&snapshot, Some("2025-06-01")),
+            PageState::NoSlotsForDate {
+                date: "2025-06-01".to_string()
+            }
+        );
+        assert_eq!(
+            classify(&snapshot, Some("2025-06-09")),
+            PageState::NoSlotsForDate {
+                date: "2025-06-09".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn missing_form_or_dates_is_unknown() {
+        let no_form = page_snapshot(json!({ "form_present": false, "dates": open_dates() }));
+        assert_eq!(classify(&no_form, None), PageState::Unknown);
+        let no_dates = page_snapshot(json!({ "form_present": true, "dates": [] }));
+        assert_eq!(classify(&no_dates, None), PageState::Unknown);
+    }
+
+    #[test]
+    fn later_stage_wins_when_several_are_visible() {
+        let stage = |value: serde_json::Value| classify_stage(&serde_json::from_value(value).unwrap());
+        assert_eq!(stage(json!({})), FlowStage::Unknown);
+        assert_eq!(stage(json!({ "reservation_button": true })), FlowStage::Landing);
+        assert_eq!(
+            stage(json!({ "reservation_button": true, "collection_button": true })),
+            FlowStage::CollectionChoice
+        );
+        assert_eq!(
+            stage(json!({ "collection_button": true, "agree_button": true })),
+            FlowStage::Terms
+        );
+        assert_eq!(
+            stage(json!({ "agree_button": true, "date_list": true })),
+            FlowStage::DateTime
+        );
+        assert_eq!(
+            stage(json!({ "reservation_button": true, "date_list": true, "contact_form": true })),
+            FlowStage::Contact
+        );
+    }
+}
@@ -0,0 +1,146 @@
+// 개인정보 타입 - Display/Debug 출력 시 항상 마스킹되어 로그에 원본 값이 남지 않음
+// 실제 값이 필요한 곳(폼 입력 등)에서는 expose() 로 명시적으로 꺼내 써야 함
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(test)]
+use std::cell::Cell;
+
+// 디버그 빌드에서 이 환경 변수를 1 로 설정하면 마스킹 없이 원본 값을 출력
+pub const UNREDACTED_ENV: &str = "ROLEX_LOG_UNREDACTED";
+
+static UNREDACTED: AtomicBool = AtomicBool::new(false);
+
+// 테스트는 병렬로 실행되므로 전역 설정 대신 현재 스레드에서만 마스킹 여부를 바꿈
+#[cfg(test)]
+thread_local! {
+    static UNREDACTED_OVERRIDE: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+// 릴리스 빌드에서는 환경 변수가 있어도 항상 마스킹
+pub fn init_from_env() {
+    let enabled = unredacted_from(std::env::var(UNREDACTED_ENV).ok().as_deref());
+    UNREDACTED.store(enabled, Ordering::Relaxed);
+    if enabled {
+        tracing::warn!("⚠️ 개인정보 마스킹이 해제되었습니다 ({}=1). 디버그 용도로만 사용하세요.", UNREDACTED_ENV);
+    }
+}
+
+fn unredacted_from(value: Option<&str>) -> bool {
+    cfg!(debug_assertions) && value == Some("1")
+}
+
+pub fn is_unredacted() -> bool {
+    #[cfg(test)]
+    if let Some(unredacted) = UNREDACTED_OVERRIDE.with(Cell::get) {
+        return unredacted;
+    }
+    UNREDACTED.load(Ordering::Relaxed)
+}
+
+// f 를 실행하는 동안 현재 스레드에서만 마스킹 여부를 지정
+#[cfg(test)]
+pub fn with_unredacted<R>(unredacted: bool, f: impl FnOnce() -> R) -> R {
+    let previous = UNREDACTED_OVERRIDE.with(|o| o.replace(Some(unredacted)));
+    let result = f();
+    UNREDACTED_OVERRIDE.with(|o| o.set(previous));
+    result
+}
+
+macro_rules! pii_type {
+    ($name:ident, $mask:path) => {
+        #[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn expose(&self) -> &str {
+                &self.0
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.0.trim().is_empty()
+            }
+
+            pub fn redacted(&self) -> String {
+                $mask(&self.0)
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                if is_unredacted() {
+                    f.write_str(&self.0)
+                } else {
+                    f.write_str(&self.redacted())
+                }
+            }
+        }
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}({})", stringify!($name), self)
+            }
+        }
+    };
+}
+
+pii_type!(Email, mask_email);
+pii_type!(Carrier, mask_all);
+
+// user@example.com -> u***@example.com
+pub fn mask_email(email: &str) -> String {
+    match email.trim().split_once('@') {
+        Some((local, domain)) => {
+            let first: String = local.chars().take(1).collect();
+            format!("{}***@{}", first, domain)
+        }
+        None if email.is_empty() => String::new(),
+        None => "***".to_string(),
+    }
+}
+
+fn mask_all(value: &str) -> String {
+    if value.is_empty() {
+        String::new()
+    } else {
+        "***".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_value_must_be_exactly_one() {
+        assert_eq!(unredacted_from(Some("1")), cfg!(debug_assertions));
+        assert!(!unredacted_from(Some("true")));
+        assert!(!unredacted_from(Some("")));
+        assert!(!unredacted_from(None));
+    }
+
+    #[test]
+    fn display_and_debug_mask_unless_unredacted() {
+        let email = Email("user@example.com".to_string());
+        let carrier = Carrier("SKT".to_string());
+
+        with_unredacted(false, || {
+            assert_eq!(email.to_string(), "u***@example.com");
+            assert_eq!(format!("{:?}", email), "Email(u***@example.com)");
+            assert_eq!(format!("{:?}", carrier), "Carrier(***)");
+        });
+        with_unredacted(true, || {
+            assert_eq!(email.to_string(), "user@example.com");
+            assert_eq!(format!("{:?}", carrier), "Carrier(SKT)");
+        });
+        assert_eq!(email.expose(), "user@example.com");
+    }
+
+    #[test]
+    fn masks_keep_only_the_first_character_and_domain() {
+        assert_eq!(mask_email(" user@example.com "), "u***@example.com");
+        assert_eq!(mask_email("no-at-sign"), "***");
+        assert_eq!(mask_email(""), "");
+        assert_eq!(mask_all(""), "");
+    }
+}
@@ -0,0 +1,195 @@
+// 방문 날짜/시간 선호 목록 - 순서대로 페이지에서 선택 가능한 값과 비교해 처음 맞는 것을 사용
+use chrono::{NaiveDate, NaiveTime};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// "HH:mm" (정확한 시간) 또는 "HH:mm-HH:mm" (시간대, 양끝 포함)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl TimeWindow {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let parse_time = |v: &str| {
+            NaiveTime::parse_from_str(v.trim(), "%H:%M")
+                .map_err(|_| format!("잘못된 시간 형식: {} (HH:mm 또는 HH:mm-HH:mm)", value))
+        };
+        let window = match value.split_once('-') {
+            Some((start, end)) => TimeWindow {
+                start: parse_time(start)?,
+                end: parse_time(end)?,
+            },
+            None => {
+                let time = parse_time(value)?;
+                TimeWindow { start: time, end: time }
+            }
+        };
+        if window.start > window.end {
+            return Err(format!("시작 시간이 종료 시간보다 늦습니다: {}", value));
+        }
+        Ok(window)
+    }
+
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        self.start <= time && time <= self.end
+    }
+}
+
+impl std::fmt::Display for TimeWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start.format("%H:%M"))
+        } else {
+            write!(f, "{}-{}", self.start.format("%H:%M"), self.end.format("%H:%M"))
+        }
+    }
+}
+
+impl Serialize for TimeWindow {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeWindow {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        TimeWindow::parse(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+// 실제로 사용된 선호 항목 (실행 결과/기록에 남김)
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SlotSelection {
+    pub date: Option<String>,
+    // 선호 목록에서의 순서 (0 부터, 사용자가 직접 고른 경우 None)
+    pub date_preference: Option<usize>,
+    pub time: Option<String>,
+    pub time_preference: Option<usize>,
+    // 선호 시간대 (예: "14:00-15:00")
+    pub time_window: Option<String>,
+}
+
+// 선호 날짜 순서대로 선택 가능한 날짜를 찾음 - (선호 순서, 날짜)
+pub fn pick_date(preferred: &[String], available: &[String]) -> Option<(usize, String)> {
+    preferred
+        .iter()
+        .enumerate()
+        .find(|(_, date)| available.iter().any(|a| a == *date))
+        .map(|(index, date)| (index, date.clone()))
+}
+
+// 선호 시간대 순서대로, 시간대 안에서는 가장 이른 시간을 고름 - (선호 순서, 시간대, HH:mm)
+pub fn pick_time(preferred: &[TimeWindow], available: &[String]) -> Option<(usize, TimeWindow, String)> {
+    let mut times: Vec<NaiveTime> = available
+        .iter()
+        .filter_map(|t| NaiveTime::parse_from_str(t, "%H:%M").ok())
+        .collect();
+    times.sort();
+
+    preferred.iter().enumerate().find_map(|(index, window)| {
+        times
+            .iter()
+            .find(|time| window.contains(**time))
+            .map(|time| (index, *window, time.format("%H:%M").to_string()))
+    })
+}
+
+pub fn validate_dates(dates: &[String]) -> Vec<(usize, String)> {
+    dates
+        .iter()
+        .enumerate()
+        .filter(|(_, date)| NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err())
+        .map(|(i, date)| (i, format!("잘못된 날짜 형식: {} (YYYY-MM-DD)", date)))
+        .collect()
+}
+
+// today 이전의 날짜 (형식이 잘못된 항목은 validate_dates 에서 다룸)
+pub fn past_dates(dates: &[String], today: NaiveDate) -> Vec<String> {
+    dates
+        .iter()
+        .filter(|date| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map(|d| d < today)
+                .unwrap_or(false)
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(value: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(value, "%H:%M").unwrap()
+    }
+
+    fn windows(values: &[&str]) -> Vec<TimeWindow> {
+        values.iter().map(|v| TimeWindow::parse(v).unwrap()).collect()
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_exact_times_and_windows() {
+        let exact = TimeWindow::parse("14:30").unwrap();
+        assert_eq!((exact.start, exact.end), (time("14:30"), time("14:30")));
+        assert_eq!(exact.to_string(), "14:30");
+
+        let window = TimeWindow::parse(" 14:00 - 15:30 ").unwrap();
+        assert_eq!((window.start, window.end), (time("14:00"), time("15:30")));
+        assert_eq!(window.to_string(), "14:00-15:30");
+        assert!(window.contains(time("14:00")) && window.contains(time("15:30")));
+        assert!(!window.contains(time("15:31")));
+    }
+
+    #[test]
+    fn rejects_malformed_windows() {
+        for value in ["", "2pm", "25:00", "14:00-", "-15:00", "14:00-15:00-16:00", "14:00~15:00"] {
+            let err = TimeWindow::parse(value).unwrap_err();
+            assert!(err.contains("잘못된 시간 형식"), "{}: {}", value, err);
+        }
+        let err = TimeWindow::parse("16:00-15:00").unwrap_err();
+        assert!(err.contains("시작 시간이 종료 시간보다 늦습니다"), "{}", err);
+        assert!(serde_json::from_value::<TimeWindow>(serde_json::json!("9시")).is_err());
+    }
+
+    #[test]
+    fn picks_the_first_available_date_in_preference_order() {
+        let preferred = strings(&["2026-11-02", "2026-11-01", "2026-11-03"]);
+        let available = strings(&["2026-11-01", "2026-11-03"]);
+        assert_eq!(pick_date(&preferred, &available), Some((1, "2026-11-01".to_string())));
+
+        assert_eq!(pick_date(&preferred, &strings(&["2026-11-04"])), None);
+        assert_eq!(pick_date(&[], &available), None);
+    }
+
+    #[test]
+    fn picks_the_earliest_time_in_the_first_matching_window() {
+        let preferred = windows(&["11:00-12:00", "15:00-17:00", "10:00"]);
+        let available = strings(&["16:30", "10:00", "15:30", "invalid"]);
+        let (index, window, picked) = pick_time(&preferred, &available).unwrap();
+        assert_eq!((index, window.to_string(), picked.as_str()), (1, "15:00-17:00".to_string(), "15:30"));
+
+        // 앞 순위 시간대가 비어 있으면 다음 순위, 모두 비어 있으면 None
+        assert_eq!(pick_time(&windows(&["10:00"]), &available).unwrap().2, "10:00");
+        assert_eq!(pick_time(&windows(&["11:00-12:00", "18:00"]), &available), None);
+    }
+
+    #[test]
+    fn reports_malformed_and_past_dates() {
+        let dates = strings(&["2026-11-01", "2026/11/02", "2026-02-30", "2026-10-18"]);
+        let errors = validate_dates(&dates);
+        let indexes: Vec<usize> = errors.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indexes, [1, 2]);
+        assert!(errors[0].1.contains("2026/11/02"));
+
+        let today = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        assert_eq!(past_dates(&dates, today), ["2026-10-18"]);
+        assert!(past_dates(&strings(&["2026-10-19"]), today).is_empty());
+    }
+}
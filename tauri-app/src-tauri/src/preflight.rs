@@ -0,0 +1,245 @@
+// 실행 전 점검 (브라우저를 띄우기 전에 막아야 할 문제를 찾음)
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::calendar::{self, ConflictMode};
+use crate::{contact, network, pacing, preferences, retry, StoreConfig};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Block,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PreflightCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PreflightReport {
+    pub checks: Vec<PreflightCheck>,
+}
+
+impl PreflightReport {
+    pub fn is_blocked(&self) -> bool {
+        self.checks.iter().any(|c| c.status == CheckStatus::Block)
+    }
+
+    pub fn blocking_messages(&self) -> Vec<String> {
+        self.checks
+            .iter()
+            .filter(|c| c.status == CheckStatus::Block)
+            .map(|c| c.message.clone())
+            .collect()
+    }
+
+    fn push(&mut self, name: &str, status: CheckStatus, message: String) {
+        match status {
+            CheckStatus::Ok => info!("✅ [사전 점검] {}: {}", name, message),
+            CheckStatus::Warn => warn!("⚠️ [사전 점검] {}: {}", name, message),
+            CheckStatus::Block => warn!("⛔ [사전 점검] {}: {}", name, message),
+        }
+        self.checks.push(PreflightCheck {
+            name: name.to_string(),
+            status,
+            message,
+        });
+    }
+}
+
+pub fn run(config: &StoreConfig) -> PreflightReport {
+    let mut report = PreflightReport::default();
+    check_settings(config, &mut report);
+    check_visit_dates(config, chrono::Local::now().date_naive(), &mut report);
+    check_calendar(config, &mut report);
+    report
+}
+
+// 화면에서 바로 넘어온 매장 설정은 설정 파일 검증을 거치지 않으므로 실행 전에 같은 검사를 다시 함
+fn check_settings(config: &StoreConfig, report: &mut PreflightReport) {
+    check_fields(report, "retry_policies", "retryPolicies", retry::validate(&config.retry_policies));
+    check_fields(report, "contact_fields", "contactFields", contact::validate(&config.contact_fields));
+    check_fields(report, "pacing", "pacing", pacing::validate(&config.pacing));
+    check_fields(report, "network_monitor", "networkMonitor", network::validate(&config.network_monitor));
+}
+
+fn check_fields(report: &mut PreflightReport, name: &str, prefix: &str, errors: Vec<(String, String)>) {
+    if errors.is_empty() {
+        return;
+    }
+    let messages: Vec<String> = errors
+        .iter()
+        .map(|(field, message)| format!("{}.{}: {}", prefix, field, message))
+        .collect();
+    report.push(name, CheckStatus::Block, messages.join("; "));
+}
+
+// 지난 날짜는 예약할 수 없으므로 경고하고, 모든 선호 날짜가 지났으면 실행을 막음
+fn check_visit_dates(config: &StoreConfig, today: chrono::NaiveDate, report: &mut PreflightReport) {
+    let dates = config.date_preferences();
+    let errors: Vec<String> = preferences::validate_dates(&dates)
+        .into_iter()
+        .map(|(_, message)| message)
+        .collect();
+    if !errors.is_empty() {
+        report.push("visit_dates", CheckStatus::Block, errors.join("; "));
+        return;
+    }
+    let past = preferences::past_dates(&dates, today);
+    if past.is_empty() {
+        return;
+    }
+    let status = if past.len() == dates.len() {
+        CheckStatus::Block
+    } else {
+        CheckStatus::Warn
+    };
+    report.push(
+        "visit_dates",
+        status,
+        format!("이미 지난 방문 날짜입니다: {}", past.join(", ")),
+    );
+}
+
+// 선호 날짜/시간대의 모든 조합을 확인 - 겹치는 조합이 있으면 경고하고,
+// 모든 조합이 겹칠 때만 block 모드에서 실행을 막음 (나머지 조합으로 예약할 수 있으므로)
+fn check_calendar(config: &StoreConfig, report: &mut PreflightReport) {
+    let Some(check) = &config.calendar_check else {
+        return;
+    };
+    let dates = config.date_preferences();
+    let windows = config.time_preferences();
+    if dates.is_empty() || windows.is_empty() {
+        report.push(
+            "calendar_conflict",
+            CheckStatus::Warn,
+            "방문 날짜/시간이 없어 일정 충돌을 확인하지 않았습니다".to_string(),
+        );
+        return;
+    }
+
+    let events = match calendar::load_events(check) {
+        Ok(events) => events,
+        Err(e) => {
+            report.push("calendar_conflict", CheckStatus::Warn, e);
+            return;
+        }
+    };
+    for warning in &events.warnings {
+        report.push("calendar_timezone", CheckStatus::Warn, warning.clone());
+    }
+
+    match calendar::find_preference_conflicts(&events, check, &dates, &windows) {
+        Ok(results) => {
+            let total = results.len();
+            let conflicted: Vec<String> = results
+                .iter()
+                .filter(|r| !r.conflicts.is_empty())
+                .map(|r| r.describe())
+                .collect();
+            if conflicted.is_empty() {
+                report.push(
+                    "calendar_conflict",
+                    CheckStatus::Ok,
+                    format!("선호 날짜/시간 {}개 조합 모두 겹치는 일정이 없습니다", total),
+                );
+                return;
+            }
+            let status = match check.mode {
+                ConflictMode::Block if conflicted.len() == total => CheckStatus::Block,
+                _ => CheckStatus::Warn,
+            };
+            report.push(
+                "calendar_conflict",
+                status,
+                format!("선호 날짜/시간 {}개 조합 중 {}개가 겹칩니다 - {}", total, conflicted.len(), conflicted.join("; ")),
+            );
+        }
+        Err(e) => report.push("calendar_conflict", CheckStatus::Warn, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store_config(retry_policies: serde_json::Value) -> StoreConfig {
+        store_config_with(serde_json::json!({ "retryPolicies": retry_policies }))
+    }
+
+    fn store_config_with(extra: serde_json::Value) -> StoreConfig {
+        let mut config = serde_json::json!({
+            "storeName": "롯데",
+            "authUrl": "https://example.com/auth",
+            "reserveUrl": "https://example.com/reserve",
+            "startTime": null,
+            "visitDate": null,
+            "visitTime": null,
+            "clientTime": null,
+        });
+        for (key, value) in extra.as_object().unwrap() {
+            config[key] = value.clone();
+        }
+        serde_json::from_value(config).unwrap()
+    }
+
+    #[test]
+    fn invalid_retry_policy_blocks_the_run() {
+        let report = run(&store_config(serde_json::json!({
+            "submit": { "attempts": 0, "backoff": { "type": "fixed", "delayMs": 500 } }
+        })));
+
+        assert!(report.is_blocked());
+        assert_eq!(report.checks[0].name, "retry_policies");
+        assert!(report.checks[0].message.starts_with("retryPolicies.submit.attempts"));
+    }
+
+    #[test]
+    fn default_retry_policies_pass() {
+        let report = run(&store_config(serde_json::json!({})));
+        assert!(!report.is_blocked());
+        assert!(report.checks.is_empty());
+    }
+
+    #[test]
+    fn invalid_contact_pacing_and_network_settings_block_the_run() {
+        let report = run(&store_config_with(serde_json::json!({
+            "contactFields": { "phone": "abc" },
+            "pacing": { "factor": 0.5 },
+            "networkMonitor": { "stallTimeoutSecs": 0 },
+        })));
+
+        assert!(report.is_blocked());
+        let names: Vec<&str> = report.checks.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["contact_fields", "pacing", "network_monitor"]);
+        let messages = report.blocking_messages();
+        assert!(messages[0].starts_with("contactFields.phone"));
+        assert!(messages[1].starts_with("pacing.factor"));
+        assert!(messages[2].starts_with("networkMonitor.stallTimeoutSecs"));
+    }
+
+    #[test]
+    fn past_visit_dates_warn_and_block_when_none_remain() {
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        let mut report = PreflightReport::default();
+        let config = store_config_with(serde_json::json!({ "visitDates": ["2026-10-18", "2026-10-20"] }));
+        check_visit_dates(&config, today, &mut report);
+        assert_eq!(report.checks[0].status, CheckStatus::Warn);
+        assert!(report.checks[0].message.contains("2026-10-18"));
+
+        let mut report = PreflightReport::default();
+        let config = store_config_with(serde_json::json!({ "visitDate": "2026-10-01" }));
+        check_visit_dates(&config, today, &mut report);
+        assert!(report.is_blocked());
+
+        let mut report = PreflightReport::default();
+        let config = store_config_with(serde_json::json!({ "visitDates": ["2026-13-01"] }));
+        check_visit_dates(&config, today, &mut report);
+        assert!(report.blocking_messages()[0].contains("잘못된 날짜 형식"));
+    }
+}
@@ -0,0 +1,530 @@
+// 흐름 기록 - 브라우저를 열어 사용자가 직접 누른 버튼과 입력한 항목을 기록하고 편집 가능한 흐름 정의(JSON)로 내보냄
+// 페이지에 넣은 리스너가 Runtime.addBinding 으로 동작을 전달하고, 페이지 이동은 CDP 이벤트로 기록
+// 가져온 흐름 정의는 설정 파일의 recordedFlow 로 저장되어 자동화 단계에서 재생되고 (replay.rs),
+// 단계별 첫 클릭의 로케이터는 재생에 실패했을 때 기본 동작이 쓸 locators 로도 반영됨
+use chromiumoxide::browser::{Browser, BrowserConfig};
+use chromiumoxide::cdp::browser_protocol::page::{AddScriptToEvaluateOnNewDocumentParams, EventFrameNavigated};
+use chromiumoxide::cdp::js_protocol::runtime::{AddBindingParams, EventBindingCalled};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::config::LoadedConfig;
+use crate::control::Step;
+use crate::locators::{self, Locator, LocatorOverrides};
+
+pub const FLOW_VERSION: u32 = 1;
+const FLOWS_DIR_NAME: &str = "flows";
+const BINDING_NAME: &str = "__rolexRecord";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FlowActionKind {
+    Navigate,
+    Click,
+    // 입력란 변경 - 값은 개인정보일 수 있으므로 항목 이름만 기록
+    Input,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FlowAction {
+    pub kind: FlowActionKind,
+    // 자동화 단계 (기록 중 추정, 편집 가능)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step: Option<Step>,
+    // 견고한 순서대로 (id/name/속성 CSS, ARIA, 텍스트, XPath)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locators: Vec<Locator>,
+    // Input 일 때 입력란 name 속성
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    // 검토용 요소 텍스트
+    #[serde(default)]
+    pub label: String,
+    pub url: String,
+    pub timestamp: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FlowDefinition {
+    pub version: u32,
+    pub start_url: String,
+    pub recorded_at: String,
+    pub actions: Vec<FlowAction>,
+}
+
+// 재생하지 않는 동작 (흐름 정의의 actions 순번과 이유)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct SkippedAction {
+    pub index: usize,
+    pub kind: FlowActionKind,
+    pub step: Option<Step>,
+    pub label: String,
+    pub reason: String,
+}
+
+impl FlowDefinition {
+    // 재생에 실패하면 기본 동작이 쓸 단계별 로케이터 - 버튼 단계별 첫 번째 클릭의 로케이터를 앞에 두고 기본 로케이터를 대체 수단으로 붙임
+    pub fn to_locator_overrides(&self) -> LocatorOverrides {
+        let mut overrides = LocatorOverrides::new();
+        for action in &self.actions {
+            let Some(step) = action.step else {
+                continue;
+            };
+            if action.kind != FlowActionKind::Click
+                || action.locators.is_empty()
+                || locators::default_chain(step).is_empty()
+                || overrides.contains_key(&step)
+            {
+                continue;
+            }
+            let mut chain = action.locators.clone();
+            chain.extend(
+                locators::default_chain(step)
+                    .into_iter()
+                    .filter(|l| !action.locators.contains(l)),
+            );
+            overrides.insert(step, chain);
+        }
+        overrides
+    }
+}
+
+// import_recorded_flow 결과 - 갱신된 설정과 재생할 단계, 재생하지 않는 동작
+#[derive(Serialize, Debug)]
+pub struct ImportedFlow {
+    #[serde(flatten)]
+    pub loaded: LoadedConfig,
+    pub imported_steps: Vec<Step>,
+    pub skipped: Vec<SkippedAction>,
+}
+
+#[derive(Serialize)]
+struct StepHint {
+    step: Step,
+    css: Vec<String>,
+    texts: Vec<(String, Option<String>)>,
+}
+
+// 기록 중 클릭한 요소가 어느 단계인지 추정하기 위한 기본 로케이터 (ARIA/XPath 는 제외)
+fn step_hints() -> Vec<StepHint> {
+    let mut hints: Vec<StepHint> = Step::ALL
+        .iter()
+        .map(|&step| {
+            let mut hint = StepHint {
+                step,
+                css: Vec::new(),
+                texts: Vec::new(),
+            };
+            for locator in locators::default_chain(step) {
+                match locator {
+                    Locator::Css { selector } => hint.css.push(selector),
+                    Locator::Text { text, selector } => hint.texts.push((text, selector)),
+                    _ => {}
+                }
+            }
+            hint
+        })
+        .collect();
+    for hint in hints.iter_mut() {
+        match hint.step {
+            Step::VisitDate => hint.css.push("#appointment .datetime-form .date-list ul > li[data-date]".to_string()),
+            Step::VisitTime => hint.css.push("#appointment .datetime-form .time-list ul > li[data-time]".to_string()),
+            _ => {}
+        }
+    }
+    hints.retain(|h| !h.css.is_empty() || !h.texts.is_empty());
+    hints
+}
+
+fn recorder_script() -> Result<String, String> {
+    let hints = serde_json::to_string(&step_hints()).map_err(|e| e.to_string())?;
+    let binding = serde_json::to_string(BINDING_NAME).map_err(|e| e.to_string())?;
+    Ok(format!(
+        r#"
+        (() => {{
+            if (window.__rolexRecorderInstalled) return;
+            window.__rolexRecorderInstalled = true;
+            const hints = {};
+            const binding = {};
+            const CLICKABLE = 'a, button, input, select, textarea, li, label, [role="button"], [onclick]';
+            const unique = (selector) => {{
+                try {{ return document.querySelectorAll(selector).length === 1; }} catch (e) {{ return false; }}
+            }};
+            const quote = (value) => '"' + String(value).replace(/\\/g, '\\\\').replace(/"/g, '\\"') + '"';
+            const shortText = (el) => (el.getAttribute('aria-label') || el.textContent || el.value || '').trim().replace(/\s+/g, ' ').slice(0, 60);
+            const role = (el) => {{
+                const explicit = el.getAttribute('role');
+                if (explicit) return explicit;
+                const tag = el.tagName.toLowerCase();
+                if (tag === 'a' && el.hasAttribute('href')) return 'link';
+                if (tag === 'button' || (tag === 'input' && ['submit', 'button'].includes(el.type))) return 'button';
+                if (tag === 'input' && el.type === 'checkbox') return 'checkbox';
+                if (tag === 'input' || tag === 'textarea') return 'textbox';
+                return null;
+            }};
+            const xpath = (el) => {{
+                const parts = [];
+                for (let node = el; node && node.nodeType === Node.ELEMENT_NODE; node = node.parentNode) {{
+                    if (node.id) {{ parts.unshift('//*[@id=' + quote(node.id).replace(/^"|"$/g, "'") + ']'); return parts.join('/'); }}
+                    let index = 1;
+                    for (let sib = node.previousElementSibling; sib; sib = sib.previousElementSibling) {{
+                        if (sib.tagName === node.tagName) index++;
+                    }}
+                    parts.unshift(node.tagName.toLowerCase() + '[' + index + ']');
+                }}
+                return '/' + parts.join('/');
+            }};
+            const locatorsFor = (el) => {{
+                const tag = el.tagName.toLowerCase();
+                const found = [];
+                const css = (selector) => {{ if (unique(selector)) found.push({{ by: 'css', selector }}); }};
+                if (el.id && !/\d{{3,}}/.test(el.id)) css('#' + CSS.escape(el.id));
+                if (el.getAttribute('name')) css(tag + '[name=' + quote(el.getAttribute('name')) + ']');
+                for (const attr of ['href', 'onclick', 'data-date', 'data-time', 'type']) {{
+                    if (el.getAttribute(attr)) css(tag + '[' + attr + '=' + quote(el.getAttribute(attr)) + ']');
+                }}
+                const text = shortText(el);
+                const r = role(el);
+                if (r && text) found.push({{ by: 'aria', role: r, name: text }});
+                if (text && ['a', 'button', 'li', 'label'].includes(tag)) found.push({{ by: 'text', text, selector: tag }});
+                found.push({{ by: 'xpath', xpath: xpath(el) }});
+                return found;
+            }};
+            const stepFor = (el) => {{
+                for (const hint of hints) {{
+                    if (hint.css.some(selector => {{ try {{ return !!el.closest(selector); }} catch (e) {{ return false; }} }})) return hint.step;
+                    if (hint.texts.some(([text, selector]) => (!selector || !!el.closest(selector)) && el.textContent.includes(text))) return hint.step;
+                }}
+                return null;
+            }};
+            const send = (action) => {{
+                try {{ window[binding](JSON.stringify(action)); }} catch (e) {{}}
+            }};
+            document.addEventListener('click', (event) => {{
+                const el = event.target.closest(CLICKABLE) || event.target;
+                if (['INPUT', 'TEXTAREA', 'SELECT'].includes(el.tagName) && !['checkbox', 'radio', 'submit', 'button'].includes(el.type)) return;
+                send({{ kind: 'click', step: stepFor(el), locators: locatorsFor(el), label: shortText(el), url: location.href, timestamp: new Date().toISOString() }});
+            }}, true);
+            document.addEventListener('change', (event) => {{
+                const el = event.target;
+                if (!['INPUT', 'TEXTAREA', 'SELECT'].includes(el.tagName) || ['checkbox', 'radio'].includes(el.type)) return;
+                send({{ kind: 'input', step: stepFor(el), locators: locatorsFor(el), field: el.getAttribute('name'), label: el.getAttribute('name') || '', url: location.href, timestamp: new Date().toISOString() }});
+            }}, true);
+        }})()
+    "#,
+        hints, binding
+    ))
+}
+
+struct RecordingSession {
+    browser: Browser,
+    start_url: String,
+    recorded_at: String,
+    actions: Arc<Mutex<Vec<FlowAction>>>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+// 기록 상태 - 브라우저를 띄우는 동안에도 자리를 차지해 동시에 두 번 시작되지 않도록 함
+#[derive(Default)]
+enum SessionSlot {
+    #[default]
+    Idle,
+    Starting,
+    Recording(Box<RecordingSession>),
+}
+
+// Tauri 관리 상태 - 한 번에 하나의 기록만 진행
+#[derive(Default)]
+pub struct FlowRecorder {
+    session: Mutex<SessionSlot>,
+}
+
+// 시작이 끝나기 전에 실패하거나 취소되면 자리를 비움
+struct StartingGuard<'a> {
+    session: &'a Mutex<SessionSlot>,
+    done: bool,
+}
+
+impl Drop for StartingGuard<'_> {
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+        if let Ok(mut slot) = self.session.lock() {
+            if matches!(*slot, SessionSlot::Starting) {
+                *slot = SessionSlot::Idle;
+            }
+        }
+    }
+}
+
+impl FlowRecorder {
+    pub async fn start(&self, url: &str) -> Result<(), String> {
+        {
+            let mut slot = self.session.lock().map_err(|e| e.to_string())?;
+            if !matches!(*slot, SessionSlot::Idle) {
+                return Err("이미 흐름을 기록 중입니다".to_string());
+            }
+            *slot = SessionSlot::Starting;
+        }
+        let mut guard = StartingGuard {
+            session: &self.session,
+            done: false,
+        };
+
+        let session = launch(url).await?;
+        let mut slot = self.session.lock().map_err(|e| e.to_string())?;
+        *slot = SessionSlot::Recording(Box::new(session));
+        guard.done = true;
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<FlowDefinition, String> {
+        let session = {
+            let mut slot = self.session.lock().map_err(|e| e.to_string())?;
+            match std::mem::take(&mut *slot) {
+                SessionSlot::Recording(session) => *session,
+                SessionSlot::Starting => {
+                    *slot = SessionSlot::Starting;
+                    return Err("흐름 기록을 시작하는 중입니다".to_string());
+                }
+                SessionSlot::Idle => return Err("기록 중인 흐름이 없습니다".to_string()),
+            }
+        };
+        let RecordingSession {
+            mut browser,
+            start_url,
+            recorded_at,
+            actions,
+            tasks,
+        } = session;
+
+        if let Err(e) = browser.close().await {
+            warn!("⚠️ 기록 브라우저 종료 실패: {}", e);
+        }
+        for task in &tasks {
+            task.abort();
+        }
+
+        let actions = actions.lock().map(|a| a.clone()).unwrap_or_default();
+        info!("⏹️ 흐름 기록 종료: {}개 동작", actions.len());
+        Ok(FlowDefinition {
+            version: FLOW_VERSION,
+            start_url,
+            recorded_at,
+            actions,
+        })
+    }
+}
+
+// 기록용 브라우저를 띄우고 리스너를 등록 - 중간에 실패하면 띄운 브라우저를 닫고 이벤트 작업을 멈춤
+async fn launch(url: &str) -> Result<RecordingSession, String> {
+    let config = BrowserConfig::builder()
+        .with_head()
+        .window_size(1200, 800)
+        .no_sandbox()
+        .args(vec!["--disable-gpu", "--no-first-run", "--disable-popup-blocking"])
+        .build()
+        .map_err(|e| format!("{:?}", e))?;
+    let (mut browser, mut handler) = Browser::launch(config)
+        .await
+        .map_err(|e| format!("브라우저를 시작할 수 없습니다: {}", e))?;
+    let mut tasks = vec![tokio::spawn(async move { while handler.next().await.is_some() {} })];
+    let actions = Arc::new(Mutex::new(Vec::new()));
+
+    if let Err(e) = record(&browser, url, &actions, &mut tasks).await {
+        if let Err(close) = browser.close().await {
+            warn!("⚠️ 기록 브라우저 종료 실패: {}", close);
+        }
+        for task in &tasks {
+            task.abort();
+        }
+        return Err(e);
+    }
+
+    info!("⏺️ 흐름 기록 시작: {}", url);
+    Ok(RecordingSession {
+        browser,
+        start_url: url.to_string(),
+        recorded_at: chrono::Local::now().to_rfc3339(),
+        actions,
+        tasks,
+    })
+}
+
+// 기록 페이지를 열고 동작/페이지 이동 리스너를 등록한 뒤 시작 주소로 이동
+async fn record(
+    browser: &Browser,
+    url: &str,
+    actions: &Arc<Mutex<Vec<FlowAction>>>,
+    tasks: &mut Vec<JoinHandle<()>>,
+) -> Result<(), String> {
+    let page = browser
+        .new_page("about:blank")
+        .await
+        .map_err(|e| format!("새 페이지 생성 실패: {}", e))?;
+    page.execute(AddBindingParams::new(BINDING_NAME))
+        .await
+        .map_err(|e| format!("기록 리스너를 등록할 수 없습니다: {}", e))?;
+    page.evaluate_on_new_document(AddScriptToEvaluateOnNewDocumentParams::new(recorder_script()?))
+        .await
+        .map_err(|e| format!("기록 스크립트를 등록할 수 없습니다: {}", e))?;
+
+    let mut bindings = page
+        .event_listener::<EventBindingCalled>()
+        .await
+        .map_err(|e| format!("기록 이벤트를 구독할 수 없습니다: {}", e))?;
+    let recorded = actions.clone();
+    tasks.push(tokio::spawn(async move {
+        while let Some(event) = bindings.next().await {
+            if event.name != BINDING_NAME {
+                continue;
+            }
+            match serde_json::from_str::<FlowAction>(&event.payload) {
+                Ok(action) => {
+                    info!("⏺️ 기록: {:?} {:?} '{}'", action.kind, action.step, action.label);
+                    if let Ok(mut actions) = recorded.lock() {
+                        actions.push(action);
+                    }
+                }
+                Err(e) => warn!("⚠️ 기록 항목 파싱 실패: {}", e),
+            }
+        }
+    }));
+
+    match page.event_listener::<EventFrameNavigated>().await {
+        Ok(mut navigations) => {
+            let recorded = actions.clone();
+            tasks.push(tokio::spawn(async move {
+                while let Some(event) = navigations.next().await {
+                    // 최상위 프레임 이동만 기록
+                    if event.frame.parent_id.is_some() {
+                        continue;
+                    }
+                    if let Ok(mut actions) = recorded.lock() {
+                        actions.push(FlowAction {
+                            kind: FlowActionKind::Navigate,
+                            step: None,
+                            locators: Vec::new(),
+                            field: None,
+                            label: String::new(),
+                            url: event.frame.url.clone(),
+                            timestamp: chrono::Utc::now().to_rfc3339(),
+                        });
+                    }
+                }
+            }));
+        }
+        Err(e) => warn!("⚠️ 페이지 이동 이벤트를 구독할 수 없습니다: {}", e),
+    }
+
+    page.goto(url)
+        .await
+        .map_err(|e| format!("페이지를 열 수 없습니다: {}", e))?;
+    Ok(())
+}
+
+// stop_flow_recording 결과 - 저장한 파일 경로와 흐름 정의
+#[derive(Serialize, Debug)]
+pub struct SavedFlow {
+    pub path: String,
+    pub flow: FlowDefinition,
+}
+
+pub fn flows_dir(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join(FLOWS_DIR_NAME)
+}
+
+pub fn save_flow(path: &Path, flow: &FlowDefinition) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("흐름 디렉토리를 만들 수 없습니다 ({}): {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(flow).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| format!("흐름을 저장할 수 없습니다 ({}): {}", path.display(), e))
+}
+
+pub fn load_flow(path: &Path) -> Result<FlowDefinition, String> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| format!("흐름 파일을 읽을 수 없습니다 ({}): {}", path.display(), e))?;
+    let flow: FlowDefinition =
+        serde_json::from_str(&json).map_err(|e| format!("흐름 파일 파싱 실패 ({}): {}", path.display(), e))?;
+    if flow.version != FLOW_VERSION {
+        return Err(format!("지원하지 않는 흐름 버전입니다: {} (지원: {})", flow.version, FLOW_VERSION));
+    }
+    Ok(flow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // stop_flow_recording 이 저장하는 형태 그대로
+    fn flow(actions: serde_json::Value) -> FlowDefinition {
+        serde_json::from_value(json!({
+            "version": FLOW_VERSION,
+            "start_url": "https://www.rolex.com/ko/store",
+            "recorded_at": "2025-06-01T09:00:00+09:00",
+            "actions": actions,
+        }))
+        .unwrap()
+    }
+
+    fn action(kind: &str, step: Option<&str>, locators: serde_json::Value, label: &str) -> serde_json::Value {
+        json!({
+            "kind": kind,
+            "step": step,
+            "locators": locators,
+            "label": label,
+            "url": "https://www.rolex.com/ko/store",
+            "timestamp": "2025-06-01T00:00:00Z",
+        })
+    }
+
+    #[test]
+    fn first_click_per_button_step_becomes_the_fallback_locator() {
+        let recorded = json!([{ "by": "css", "selector": "#agree-now" }]);
+        let flow = flow(json!([
+            action("navigate", None, json!([]), ""),
+            action("click", Some("agree"), recorded.clone(), "동의합니다"),
+            action("click", Some("agree"), json!([{ "by": "css", "selector": "#agree-again" }]), "동의합니다"),
+            action("click", None, json!([{ "by": "css", "selector": "#banner" }]), "배너"),
+            action("click", Some("visit_date"), json!([{ "by": "css", "selector": "li[data-date]" }]), "2"),
+            action("click", Some("submit"), json!([]), "제출"),
+            action("input", Some("submit"), json!([{ "by": "css", "selector": "input[name=\"phone\"]" }]), "phone"),
+        ]));
+
+        let overrides = flow.to_locator_overrides();
+        assert_eq!(overrides.keys().copied().collect::<Vec<_>>(), vec![Step::Agree]);
+        let chain = &overrides[&Step::Agree];
+        assert_eq!(chain[0], serde_json::from_value::<Locator>(recorded[0].clone()).unwrap());
+        assert_eq!(chain.len(), 1 + locators::default_chain(Step::Agree).len());
+    }
+
+    #[tokio::test]
+    async fn start_is_rejected_while_another_start_is_launching() {
+        let recorder = FlowRecorder::default();
+        *recorder.session.lock().unwrap() = SessionSlot::Starting;
+
+        let error = recorder.start("https://www.rolex.com/ko/store").await.unwrap_err();
+        assert_eq!(error, "이미 흐름을 기록 중입니다");
+        assert!(matches!(*recorder.session.lock().unwrap(), SessionSlot::Starting));
+        assert_eq!(recorder.stop().await.unwrap_err(), "흐름 기록을 시작하는 중입니다");
+    }
+
+    #[test]
+    fn failed_start_frees_the_slot() {
+        let recorder = FlowRecorder::default();
+        *recorder.session.lock().unwrap() = SessionSlot::Starting;
+        drop(StartingGuard {
+            session: &recorder.session,
+            done: false,
+        });
+        assert!(matches!(*recorder.session.lock().unwrap(), SessionSlot::Idle));
+    }
+}
@@ -0,0 +1,348 @@
+// 기록한 흐름 재생 - 흐름 정의의 동작을 자동화 단계별로 묶어 해당 단계에서 기본 동작 대신 실행
+// 날짜/시간은 선호 목록, PASS 인증은 사용자, 성공 확인은 확인 페이지 파싱으로 진행하므로 재생하지 않음
+// 재생 중 누를 요소를 찾지 못하면 그 클릭은 실행되지 않은 상태이므로 해당 단계의 기본 동작으로 이어감
+use chromiumoxide::Page;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::contact::{self, ContactFields, ContactFillReport};
+use crate::control::Step;
+use crate::locators;
+use crate::pii::Email;
+use crate::recorder::{FlowAction, FlowActionKind, FlowDefinition, SkippedAction};
+use crate::retry::RetryPolicy;
+
+// 기록한 동작으로 대신할 수 있는 단계 (버튼 클릭과 연락처 입력)
+pub fn replayable(step: Step) -> bool {
+    matches!(
+        step,
+        Step::CookiePopup | Step::VisitReservation | Step::RolexCollection | Step::Agree | Step::Next | Step::Submit
+    )
+}
+
+fn not_replayed_reason(step: Step) -> &'static str {
+    match step {
+        Step::VisitDate | Step::VisitTime => "날짜/시간은 선호 목록으로 선택합니다",
+        Step::PassAuthentication => "PASS 인증은 사용자가 직접 진행합니다",
+        _ => "재생하지 않는 단계입니다",
+    }
+}
+
+// 단계별로 재생할 동작 (기록 순서 유지)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplayPlan {
+    steps: BTreeMap<Step, Vec<FlowAction>>,
+}
+
+impl ReplayPlan {
+    // 단계가 없는 클릭/입력은 뒤따르는 단계 앞에, 페이지 이동은 바로 앞 동작의 단계 뒤에 붙임
+    // 같은 단계가 떨어져서 다시 나오면(기록 중 재시도 등) 처음 기록한 동작만 재생
+    pub fn new(flow: &FlowDefinition) -> (Self, Vec<SkippedAction>) {
+        let mut plan = ReplayPlan::default();
+        let mut skipped = Vec::new();
+        let mut pending: Vec<(usize, &FlowAction)> = Vec::new();
+        // 마지막으로 동작을 넣은 단계 (이어지는 페이지 이동을 붙임)
+        let mut current: Option<Step> = None;
+
+        for (index, action) in flow.actions.iter().enumerate() {
+            let step = match (action.kind, action.step) {
+                (FlowActionKind::Navigate, _) => {
+                    match current {
+                        Some(step) => plan.steps.entry(step).or_default().push(action.clone()),
+                        None => skipped.push(skip(
+                            index,
+                            action,
+                            "재생하는 동작 뒤의 페이지 이동이 아닙니다 (자동화는 매장 URL 에서 시작)",
+                        )),
+                    }
+                    continue;
+                }
+                (FlowActionKind::Click, _) if action.locators.is_empty() => {
+                    skipped.push(skip(index, action, "기록된 로케이터가 없습니다"));
+                    continue;
+                }
+                (FlowActionKind::Input, _) if action.field.is_none() => {
+                    skipped.push(skip(index, action, "입력란 name 속성이 없습니다"));
+                    continue;
+                }
+                (_, None) => {
+                    pending.push((index, action));
+                    continue;
+                }
+                (_, Some(step)) => step,
+            };
+
+            let reason = if !replayable(step) {
+                Some(not_replayed_reason(step))
+            } else if current != Some(step) && plan.steps.contains_key(&step) {
+                Some("같은 단계는 처음 기록한 동작만 재생합니다")
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                skipped.extend(
+                    pending
+                        .drain(..)
+                        .map(|(i, a)| skip(i, a, "재생하지 않는 동작 앞의 단계 없는 동작입니다")),
+                );
+                skipped.push(skip(index, action, reason));
+                current = None;
+                continue;
+            }
+
+            let actions = plan.steps.entry(step).or_default();
+            actions.extend(pending.drain(..).map(|(_, a)| a.clone()));
+            let repeated = action.kind == FlowActionKind::Click
+                && actions
+                    .last()
+                    .is_some_and(|last| last.kind == FlowActionKind::Click && last.locators == action.locators);
+            if repeated {
+                skipped.push(skip(index, action, "같은 요소를 연속으로 누른 동작입니다"));
+            } else {
+                actions.push(action.clone());
+            }
+            current = Some(step);
+        }
+        skipped.extend(
+            pending
+                .into_iter()
+                .map(|(i, a)| skip(i, a, "뒤따르는 단계가 없는 동작입니다")),
+        );
+        skipped.sort_by_key(|s| s.index);
+        (plan, skipped)
+    }
+
+    pub fn actions(&self, step: Step) -> &[FlowAction] {
+        self.steps.get(&step).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn steps(&self) -> Vec<Step> {
+        self.steps.keys().copied().collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+fn skip(index: usize, action: &FlowAction, reason: &str) -> SkippedAction {
+    SkippedAction {
+        index,
+        kind: action.kind,
+        step: action.step,
+        label: action.label.clone(),
+        reason: reason.to_string(),
+    }
+}
+
+// 기록한 흐름에 이 단계의 동작이 있으면 재생 - 재생을 마치면 Some(입력 결과), 동작이 없거나 실패하면 None
+pub async fn try_step(
+    page: &Page,
+    plan: Option<&ReplayPlan>,
+    step: Step,
+    email: &Email,
+    fields: &ContactFields,
+    policy: &RetryPolicy,
+) -> Option<ContactFillReport> {
+    let actions = plan?.actions(step);
+    if actions.is_empty() {
+        return None;
+    }
+    info!("▶️ 기록한 흐름으로 {} 단계 진행 ({}개 동작)", step.as_str(), actions.len());
+    match run_step(page, step, actions, email, fields, policy).await {
+        Ok(report) => Some(report),
+        Err(e) => {
+            warn!("⚠️ 기록한 흐름 재생 실패 - 기본 동작으로 진행: {}", e);
+            None
+        }
+    }
+}
+
+async fn run_step(
+    page: &Page,
+    step: Step,
+    actions: &[FlowAction],
+    email: &Email,
+    fields: &ContactFields,
+    policy: &RetryPolicy,
+) -> Result<ContactFillReport, String> {
+    let mut report = ContactFillReport::default();
+    for action in actions {
+        match action.kind {
+            FlowActionKind::Click => click(page, step, action, policy).await?,
+            FlowActionKind::Input => fill(page, action, email, fields, &mut report).await,
+            FlowActionKind::Navigate => wait_for_page(page, &action.url, policy).await,
+        }
+    }
+    Ok(report)
+}
+
+async fn click(page: &Page, step: Step, action: &FlowAction, policy: &RetryPolicy) -> Result<(), String> {
+    let mut attempts = policy.start();
+    while attempts.next().await.is_some() {
+        match attempts
+            .timed(locators::find_and_click(page, step, &action.locators))
+            .await
+            .and_then(|found| found)
+        {
+            Ok(Some(_)) => {
+                info!("✅ 기록한 클릭 재생: '{}'", action.label);
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                return Ok(());
+            }
+            Ok(None) => {}
+            Err(e) => warn!("⚠️ 기록한 클릭 '{}' 확인 중 오류: {}", action.label, e),
+        }
+    }
+    Err(format!("기록한 클릭 '{}' 의 요소를 찾을 수 없습니다", action.label))
+}
+
+// 입력값은 기록하지 않으므로 email 설정과 contactFields 에서 같은 이름의 값을 찾아 입력
+async fn fill(page: &Page, action: &FlowAction, email: &Email, fields: &ContactFields, report: &mut ContactFillReport) {
+    let Some(field) = action.field.as_deref() else {
+        return;
+    };
+    let value = if field == contact::EMAIL_FIELD {
+        Some(email.expose())
+    } else {
+        fields.get(field).map(String::as_str)
+    };
+    let Some(value) = value else {
+        warn!("⚠️ 기록한 입력란 {} 의 값이 설정되어 있지 않습니다 (contactFields)", field);
+        return;
+    };
+    match contact::fill(page, &[(field, value)]).await {
+        Ok(filled) => {
+            report.set.extend(filled.set);
+            report.missing.extend(filled.missing);
+        }
+        Err(e) => {
+            warn!("⚠️ {}", e);
+            report.missing.push(field.to_string());
+        }
+    }
+}
+
+// 기록 당시 이동한 페이지에 도착할 때까지 대기 - 도착하지 않아도 다음 동작은 진행
+async fn wait_for_page(page: &Page, url: &str, policy: &RetryPolicy) {
+    let mut attempts = policy.start();
+    while attempts.next().await.is_some() {
+        if let Ok(Some(current)) = page.url().await {
+            if same_page(&current, url) {
+                return;
+            }
+        }
+    }
+    warn!("⚠️ 기록한 페이지 이동을 확인하지 못했습니다: {}", url);
+}
+
+// 쿼리/해시는 실행마다 달라질 수 있으므로 주소와 경로만 비교
+fn same_page(current: &str, recorded: &str) -> bool {
+    match (url::Url::parse(current), url::Url::parse(recorded)) {
+        (Ok(current), Ok(recorded)) => {
+            current.scheme() == recorded.scheme()
+                && current.host_str() == recorded.host_str()
+                && current.path().trim_end_matches('/') == recorded.path().trim_end_matches('/')
+        }
+        _ => current == recorded,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn flow(actions: serde_json::Value) -> FlowDefinition {
+        serde_json::from_value(json!({
+            "version": crate::recorder::FLOW_VERSION,
+            "start_url": "https://www.rolex.com/ko/store",
+            "recorded_at": "2025-06-01T09:00:00+09:00",
+            "actions": actions,
+        }))
+        .unwrap()
+    }
+
+    fn action(kind: &str, step: Option<&str>, selector: Option<&str>, label: &str) -> serde_json::Value {
+        let locators = match selector {
+            Some(selector) => json!([{ "by": "css", "selector": selector }]),
+            None => json!([]),
+        };
+        json!({
+            "kind": kind,
+            "step": step,
+            "locators": locators,
+            "field": if kind == "input" { json!(label) } else { json!(null) },
+            "label": label,
+            "url": format!("https://www.rolex.com/ko/store/{}", label),
+            "timestamp": "2025-06-01T00:00:00Z",
+        })
+    }
+
+    fn labels(plan: &ReplayPlan, step: Step) -> Vec<&str> {
+        plan.actions(step).iter().map(|a| a.label.as_str()).collect()
+    }
+
+    #[test]
+    fn actions_are_grouped_into_replayable_steps() {
+        let flow = flow(json!([
+            action("navigate", None, None, "start"),
+            action("click", None, Some("#cookie-ok"), "쿠키"),
+            action("click", Some("visit_reservation"), Some("#visit"), "방문 예약"),
+            action("navigate", None, None, "appointment"),
+            action("click", Some("agree"), Some("#agree"), "동의"),
+            action("click", Some("agree"), Some("#agree"), "동의"),
+            action("click", Some("visit_date"), Some("li[data-date=\"2025-06-02\"]"), "2"),
+            action("click", Some("next"), Some("#next"), "다음"),
+            action("click", Some("agree"), Some("#agree-again"), "다시 동의"),
+            action("input", None, Some("input[name=\"email\"]"), "email"),
+            action("input", Some("submit"), Some("input[name=\"phone\"]"), "phone"),
+            action("click", Some("submit"), Some("#submit"), "제출"),
+            action("click", None, Some("#survey"), "설문"),
+        ]));
+
+        let (plan, skipped) = ReplayPlan::new(&flow);
+
+        assert_eq!(plan.steps(), vec![Step::VisitReservation, Step::Agree, Step::Next, Step::Submit]);
+        // 단계 없는 쿠키 클릭은 다음 단계 앞에, 페이지 이동은 앞 단계 뒤에 붙음
+        assert_eq!(labels(&plan, Step::VisitReservation), vec!["쿠키", "방문 예약", "appointment"]);
+        assert_eq!(labels(&plan, Step::Agree), vec!["동의"]);
+        assert_eq!(labels(&plan, Step::Submit), vec!["email", "phone", "제출"]);
+
+        let skipped: Vec<(usize, &str)> = skipped.iter().map(|s| (s.index, s.reason.as_str())).collect();
+        assert_eq!(
+            skipped,
+            vec![
+                (0, "재생하는 동작 뒤의 페이지 이동이 아닙니다 (자동화는 매장 URL 에서 시작)"),
+                (5, "같은 요소를 연속으로 누른 동작입니다"),
+                (6, "날짜/시간은 선호 목록으로 선택합니다"),
+                (8, "같은 단계는 처음 기록한 동작만 재생합니다"),
+                (12, "뒤따르는 단계가 없는 동작입니다"),
+            ]
+        );
+    }
+
+    #[test]
+    fn actions_without_locators_or_field_are_skipped() {
+        let mut input = action("input", Some("submit"), Some("input.phone"), "phone");
+        input["field"] = json!(null);
+        let flow = flow(json!([action("click", Some("next"), None, "다음"), input]));
+
+        let (plan, skipped) = ReplayPlan::new(&flow);
+
+        assert!(plan.is_empty());
+        let reasons: Vec<&str> = skipped.iter().map(|s| s.reason.as_str()).collect();
+        assert_eq!(reasons, vec!["기록된 로케이터가 없습니다", "입력란 name 속성이 없습니다"]);
+    }
+
+    #[test]
+    fn pages_match_on_host_and_path() {
+        assert!(same_page(
+            "https://www.rolex.com/ko/store/appointment?step=2#form",
+            "https://www.rolex.com/ko/store/appointment/"
+        ));
+        assert!(!same_page("https://www.rolex.com/ko/store", "https://www.rolex.com/ko/store/appointment"));
+        assert!(!same_page("http://www.rolex.com/ko/store", "https://www.rolex.com/ko/store"));
+    }
+}
@@ -0,0 +1,282 @@
+// 단계별 재시도 정책 - 단계 안의 시도 횟수, 시도 간 대기(backoff), 시도당 제한 시간, 전체 제한 시간
+// 기본값은 각 단계에 원래 하드코딩되어 있던 횟수/대기 시간과 같음
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::warn;
+
+use crate::control::Step;
+use crate::network::NetworkWatch;
+
+// 기존 전체 루프의 3회와 동일
+const DEFAULT_STEP_ATTEMPTS: u32 = 3;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Backoff {
+    Fixed {
+        #[serde(rename = "delayMs")]
+        delay_ms: u64,
+    },
+    // initialMs, initialMs + stepMs, initialMs + 2 * stepMs, ...
+    Linear {
+        #[serde(rename = "initialMs")]
+        initial_ms: u64,
+        #[serde(rename = "stepMs")]
+        step_ms: u64,
+    },
+    // initialMs * factor^n (maxMs 까지)
+    Exponential {
+        #[serde(rename = "initialMs")]
+        initial_ms: u64,
+        factor: f64,
+        #[serde(rename = "maxMs")]
+        max_ms: u64,
+    },
+}
+
+impl Backoff {
+    // retry 번째 재시도 전 대기 시간 (retry 는 1 부터)
+    pub fn delay(&self, retry: u32) -> Duration {
+        let n = retry.saturating_sub(1);
+        let ms = match self {
+            Backoff::Fixed { delay_ms } => *delay_ms,
+            Backoff::Linear { initial_ms, step_ms } => initial_ms.saturating_add(step_ms.saturating_mul(n as u64)),
+            Backoff::Exponential {
+                initial_ms,
+                factor,
+                max_ms,
+            } => ((*initial_ms as f64) * factor.powi(n as i32)).min(*max_ms as f64) as u64,
+        };
+        Duration::from_millis(ms)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    // 단계 안에서 요소를 찾는 시도 횟수
+    pub attempts: u32,
+    pub backoff: Backoff,
+    #[serde(rename = "attemptTimeoutSecs", default, skip_serializing_if = "Option::is_none")]
+    pub attempt_timeout_secs: Option<u64>,
+    // 단계 전체 제한 시간 (시도 횟수가 남아 있어도 중단)
+    #[serde(rename = "deadlineSecs", default, skip_serializing_if = "Option::is_none")]
+    pub deadline_secs: Option<u64>,
+    // 단계가 실패했을 때 자동화 루프에서 다시 진입하는 최대 횟수
+    #[serde(rename = "stepAttempts", default = "default_step_attempts")]
+    pub step_attempts: u32,
+    // 설정하면 예약 페이지 로딩이 멈췄거나 실패했을 때 남은 시도를 건너뜀 (새로고침은 자동화 루프에서)
+    #[serde(skip)]
+    pub watch: Option<NetworkWatch>,
+}
+
+fn default_step_attempts() -> u32 {
+    DEFAULT_STEP_ATTEMPTS
+}
+
+// 설정 파일의 단계별 정책 (없는 단계는 기본 정책)
+pub type RetryPolicies = BTreeMap<Step, RetryPolicy>;
+
+impl RetryPolicy {
+    fn fixed(attempts: u32, delay_ms: u64) -> Self {
+        Self {
+            attempts,
+            backoff: Backoff::Fixed { delay_ms },
+            attempt_timeout_secs: None,
+            deadline_secs: None,
+            step_attempts: DEFAULT_STEP_ATTEMPTS,
+            watch: None,
+        }
+    }
+
+    pub fn default_for(step: Step) -> Self {
+        match step {
+            Step::CookiePopup => Self::fixed(3, 1000),
+            Step::VisitReservation | Step::RolexCollection => Self::fixed(5, 2000),
+            Step::Agree | Step::Next => Self::fixed(3, 1000),
+            Step::VisitDate | Step::VisitTime => Self::fixed(3, 2000),
+            // 2초 간격으로 사용자 인증 완료 확인 (전체 대기 시간은 userActionTimeoutSecs)
+            Step::PassAuthentication => Self::pass_authentication(Duration::from_secs(120)),
+            Step::Submit | Step::CheckSuccess => Self::fixed(1, 1000),
+        }
+    }
+
+    pub fn pass_authentication(timeout: Duration) -> Self {
+        let secs = timeout.as_secs().max(1);
+        Self {
+            deadline_secs: Some(secs),
+            ..Self::fixed(secs.div_ceil(2) as u32, 2000)
+        }
+    }
+
+    pub fn deadline(&self) -> Option<Duration> {
+        self.deadline_secs.map(Duration::from_secs)
+    }
+
+    pub fn start(&self) -> Attempts<'_> {
+        Attempts {
+            policy: self,
+            started: Instant::now(),
+            attempt: 0,
+        }
+    }
+}
+
+pub fn validate(policies: &RetryPolicies) -> Vec<(String, String)> {
+    let mut errors = Vec::new();
+    for (step, policy) in policies {
+        let mut push = |field: &str, message: &str| {
+            errors.push((format!("{}.{}", step.as_str(), field), message.to_string()));
+        };
+        if policy.attempts == 0 {
+            push("attempts", "시도 횟수는 1 이상이어야 합니다");
+        }
+        if policy.step_attempts == 0 {
+            push("stepAttempts", "단계 재시도 횟수는 1 이상이어야 합니다");
+        }
+        if policy.attempt_timeout_secs == Some(0) {
+            push("attemptTimeoutSecs", "시도당 제한 시간은 1초 이상이어야 합니다");
+        }
+        if policy.deadline_secs == Some(0) {
+            push("deadlineSecs", "전체 제한 시간은 1초 이상이어야 합니다");
+        }
+        if let Backoff::Exponential { factor, .. } = policy.backoff {
+            if !(1.0..=10.0).contains(&factor) {
+                push("backoff.factor", "증가 배수는 1 이상 10 이하여야 합니다");
+            }
+        }
+    }
+    errors
+}
+
+// 정책에 따른 시도 - 두 번째 시도부터 backoff 만큼 대기하고, 횟수나 전체 제한 시간을 넘으면 None
+pub struct Attempts<'a> {
+    policy: &'a RetryPolicy,
+    started: Instant,
+    attempt: u32,
+}
+
+impl Attempts<'_> {
+    pub async fn next(&mut self) -> Option<u32> {
+        if self.attempt >= self.policy.attempts {
+            return None;
+        }
+        if self.attempt > 0 {
+            let mut delay = self.policy.backoff.delay(self.attempt);
+            if let Some(deadline) = self.policy.deadline() {
+                delay = delay.min(deadline.saturating_sub(self.started.elapsed()));
+            }
+            tokio::time::sleep(delay).await;
+        }
+        if self.deadline_passed() {
+            return None;
+        }
+        if self.attempt > 0 {
+            if let Some(problem) = self.policy.watch.as_ref().and_then(NetworkWatch::problem) {
+                warn!("📡 {} - 남은 시도를 건너뜁니다", problem);
+                return None;
+            }
+        }
+        self.attempt += 1;
+        Some(self.attempt)
+    }
+
+    pub fn total(&self) -> u32 {
+        self.policy.attempts
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn deadline_passed(&self) -> bool {
+        self.policy
+            .deadline()
+            .map(|deadline| self.started.elapsed() >= deadline)
+            .unwrap_or(false)
+    }
+
+    // 시도당 제한 시간 적용 (설정이 없으면 그대로 실행)
+    pub async fn timed<F: Future>(&self, future: F) -> Result<F::Output, String> {
+        match self.policy.attempt_timeout_secs {
+            Some(secs) => tokio::time::timeout(Duration::from_secs(secs), future)
+                .await
+                .map_err(|_| format!("시도 제한 시간 초과 ({}초)", secs)),
+            None => Ok(future.await),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn fixed_and_linear_delays() {
+        let fixed = Backoff::Fixed { delay_ms: 500 };
+        assert_eq!(fixed.delay(1), ms(500));
+        assert_eq!(fixed.delay(5), ms(500));
+
+        let linear = Backoff::Linear { initial_ms: 100, step_ms: 50 };
+        assert_eq!(linear.delay(1), ms(100));
+        assert_eq!(linear.delay(2), ms(150));
+        assert_eq!(linear.delay(4), ms(250));
+        assert_eq!(
+            Backoff::Linear { initial_ms: u64::MAX, step_ms: 1 }.delay(3),
+            ms(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn exponential_delay_grows_until_the_cap() {
+        let backoff = Backoff::Exponential { initial_ms: 100, factor: 2.0, max_ms: 1000 };
+        let delays: Vec<Duration> = (1..=6).map(|retry| backoff.delay(retry)).collect();
+        assert_eq!(delays, [ms(100), ms(200), ms(400), ms(800), ms(1000), ms(1000)]);
+        // retry 0 은 첫 재시도와 같음
+        assert_eq!(backoff.delay(0), ms(100));
+    }
+
+    #[tokio::test]
+    async fn attempts_stop_at_the_configured_count() {
+        let policy = RetryPolicy::fixed(3, 0);
+        let mut attempts = policy.start();
+        let mut seen = Vec::new();
+        while let Some(attempt) = attempts.next().await {
+            seen.push(attempt);
+        }
+        assert_eq!(seen, [1, 2, 3]);
+        assert_eq!(attempts.total(), 3);
+        assert!(!attempts.deadline_passed());
+    }
+
+    #[tokio::test]
+    async fn attempts_stop_at_the_deadline() {
+        let policy = RetryPolicy {
+            deadline_secs: Some(1),
+            ..RetryPolicy::fixed(100, 400)
+        };
+        let mut attempts = policy.start();
+        let mut count = 0;
+        while attempts.next().await.is_some() {
+            count += 1;
+        }
+        // 0ms, 400ms, 800ms 에 시도하고 마지막 대기는 남은 시간(200ms)으로 줄어듦
+        assert_eq!(count, 3);
+        assert!(attempts.deadline_passed());
+        assert!(attempts.elapsed() < ms(1500), "{:?}", attempts.elapsed());
+    }
+
+    #[test]
+    fn pass_authentication_polls_every_two_seconds_until_the_timeout() {
+        let policy = RetryPolicy::pass_authentication(Duration::from_secs(121));
+        assert_eq!(policy.attempts, 61);
+        assert_eq!(policy.deadline(), Some(Duration::from_secs(121)));
+        assert_eq!(policy.backoff.delay(1), ms(2000));
+    }
+}
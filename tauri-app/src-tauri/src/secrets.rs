@@ -0,0 +1,344 @@
+// 개인정보(이메일, 통신사 등) 로컬 암호화 저장소
+// 사용자 패스프레이즈로 Argon2id 키를 만들고 AES-256-GCM 으로 파일 전체를 암호화함
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::pii::{Carrier, Email};
+
+pub const SECRETS_FILE_NAME: &str = "secrets.enc.json";
+
+const FILE_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PersonalDetails {
+    #[serde(default)]
+    pub label: String,
+    pub email: Email,
+    pub carrier: Carrier,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+// 프론트엔드 목록 표시용 - 원본 값 대신 마스킹된 값만 내보냄
+#[derive(Serialize, Debug)]
+pub struct PersonalDetailsSummary {
+    pub id: String,
+    pub label: String,
+    pub email_hint: String,
+    pub carrier: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EncryptedFile {
+    version: u32,
+    kdf: String,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+struct Unlocked {
+    path: PathBuf,
+    key: [u8; 32],
+    salt: [u8; SALT_LEN],
+    entries: BTreeMap<String, PersonalDetails>,
+}
+
+impl Drop for Unlocked {
+    fn drop(&mut self) {
+        self.key.fill(0);
+    }
+}
+
+// Tauri 관리 상태 - 잠금 해제된 동안에만 키와 복호화된 항목을 메모리에 보관
+#[derive(Default)]
+pub struct SecretStore {
+    inner: Mutex<Option<Unlocked>>,
+}
+
+impl SecretStore {
+    // 파일이 없으면 새 저장소를 만들고, 있으면 패스프레이즈로 복호화
+    pub fn unlock(&self, path: &Path, passphrase: &str) -> Result<usize, String> {
+        if passphrase.is_empty() {
+            return Err("패스프레이즈가 비어 있습니다".to_string());
+        }
+
+        let unlocked = if path.exists() {
+            open_file(path, passphrase)?
+        } else {
+            let mut salt = [0u8; SALT_LEN];
+            OsRng.fill_bytes(&mut salt);
+            let unlocked = Unlocked {
+                path: path.to_path_buf(),
+                key: derive_key(passphrase, &salt)?,
+                salt,
+                entries: BTreeMap::new(),
+            };
+            write_file(&unlocked)?;
+            unlocked
+        };
+
+        let count = unlocked.entries.len();
+        *self.inner.lock().map_err(|e| e.to_string())? = Some(unlocked);
+        Ok(count)
+    }
+
+    pub fn lock(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            *inner = None;
+        }
+    }
+
+    pub fn is_unlocked(&self) -> bool {
+        self.inner.lock().map(|inner| inner.is_some()).unwrap_or(false)
+    }
+
+    pub fn list(&self) -> Result<Vec<PersonalDetailsSummary>, String> {
+        self.with_unlocked(|unlocked| {
+            Ok(unlocked
+                .entries
+                .iter()
+                .map(|(id, details)| PersonalDetailsSummary {
+                    id: id.clone(),
+                    label: details.label.clone(),
+                    email_hint: details.email.redacted(),
+                    carrier: details.carrier.expose().to_string(),
+                })
+                .collect())
+        })
+    }
+
+    // id 가 없으면 새로 발급하고, 있으면 해당 항목을 덮어씀
+    pub fn put(&self, id: Option<String>, details: PersonalDetails) -> Result<String, String> {
+        self.with_unlocked(|unlocked| {
+            let id = id.unwrap_or_else(|| format!("{:016x}", OsRng.next_u64()));
+            unlocked.entries.insert(id.clone(), details);
+            write_file(unlocked)?;
+            Ok(id)
+        })
+    }
+
+    pub fn remove(&self, id: &str) -> Result<(), String> {
+        self.with_unlocked(|unlocked| {
+            if unlocked.entries.remove(id).is_none() {
+                return Err(format!("저장된 개인정보가 없습니다: {}", id));
+            }
+            write_file(unlocked)
+        })
+    }
+
+    pub fn resolve(&self, id: &str) -> Result<PersonalDetails, String> {
+        self.with_unlocked(|unlocked| {
+            unlocked
+                .entries
+                .get(id)
+                .cloned()
+                .ok_or_else(|| format!("저장된 개인정보가 없습니다: {}", id))
+        })
+    }
+
+    fn with_unlocked<T>(
+        &self,
+        f: impl FnOnce(&mut Unlocked) -> Result<T, String>,
+    ) -> Result<T, String> {
+        let mut inner = self.inner.lock().map_err(|e| e.to_string())?;
+        match inner.as_mut() {
+            Some(unlocked) => f(unlocked),
+            None => Err("개인정보 저장소가 잠겨 있습니다. 먼저 잠금을 해제해주세요.".to_string()),
+        }
+    }
+}
+
+pub fn secrets_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join(SECRETS_FILE_NAME)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32], String> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("키 생성 실패: {}", e))?;
+    Ok(key)
+}
+
+fn open_file(path: &Path, passphrase: &str) -> Result<Unlocked, String> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| format!("개인정보 파일을 읽을 수 없습니다 ({}): {}", path.display(), e))?;
+    let file: EncryptedFile =
+        serde_json::from_str(&json).map_err(|e| format!("개인정보 파일 형식 오류: {}", e))?;
+    if file.version != FILE_VERSION {
+        return Err(format!("지원하지 않는 개인정보 파일 버전입니다: {}", file.version));
+    }
+    if file.kdf != "argon2id" {
+        return Err(format!("지원하지 않는 키 생성 방식입니다: {}", file.kdf));
+    }
+
+    let decode = |field: &str, value: &str| {
+        BASE64
+            .decode(value)
+            .map_err(|e| format!("개인정보 파일 형식 오류 ({}): {}", field, e))
+    };
+    let salt: [u8; SALT_LEN] = decode("salt", &file.salt)?
+        .try_into()
+        .map_err(|_| "개인정보 파일 형식 오류 (salt 길이)".to_string())?;
+    let nonce_bytes = decode("nonce", &file.nonce)?;
+    if nonce_bytes.len() != 12 {
+        return Err("개인정보 파일 형식 오류 (nonce 길이)".to_string());
+    }
+    let ciphertext = decode("ciphertext", &file.ciphertext)?;
+
+    let key = derive_key(passphrase, &salt)?;
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key));
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce_bytes), ciphertext.as_ref())
+        .map_err(|_| "패스프레이즈가 올바르지 않거나 파일이 손상되었습니다".to_string())?;
+    let entries = serde_json::from_slice(&plaintext)
+        .map_err(|e| format!("개인정보 파일 형식 오류: {}", e))?;
+
+    Ok(Unlocked {
+        path: path.to_path_buf(),
+        key,
+        salt,
+        entries,
+    })
+}
+
+// 저장할 때마다 새 nonce 로 전체 항목을 다시 암호화
+fn write_file(unlocked: &Unlocked) -> Result<(), String> {
+    let plaintext = serde_json::to_vec(&unlocked.entries).map_err(|e| e.to_string())?;
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&unlocked.key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_ref())
+        .map_err(|_| "개인정보 암호화 실패".to_string())?;
+
+    let file = EncryptedFile {
+        version: FILE_VERSION,
+        kdf: "argon2id".to_string(),
+        salt: BASE64.encode(unlocked.salt),
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    };
+    let json = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+
+    if let Some(parent) = unlocked.path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("개인정보 디렉토리를 만들 수 없습니다 ({}): {}", parent.display(), e))?;
+    }
+    let tmp_path = unlocked.path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, json)
+        .map_err(|e| format!("개인정보 파일을 저장할 수 없습니다 ({}): {}", tmp_path.display(), e))?;
+    std::fs::rename(&tmp_path, &unlocked.path)
+        .map_err(|e| format!("개인정보 파일을 저장할 수 없습니다 ({}): {}", unlocked.path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSPHRASE: &str = "correct horse battery staple";
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("secrets-{}-{}.enc.json", name, std::process::id()))
+    }
+
+    fn details() -> PersonalDetails {
+        serde_json::from_value(serde_json::json!({
+            "label": "기본",
+            "email": "user@example.com",
+            "carrier": "SKT",
+            "message": "방문 희망 모델: \"Submariner\""
+        }))
+        .unwrap()
+    }
+
+    // 새 저장소에 항목 하나를 저장하고 파일 경로를 돌려줌
+    fn saved_store(name: &str) -> (PathBuf, String) {
+        let path = temp_path(name);
+        let _ = std::fs::remove_file(&path);
+        let store = SecretStore::default();
+        assert_eq!(store.unlock(&path, PASSPHRASE).unwrap(), 0);
+        let id = store.put(None, details()).unwrap();
+        (path, id)
+    }
+
+    fn edit_file(path: &Path, edit: impl FnOnce(&mut EncryptedFile)) {
+        let mut file: EncryptedFile =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        edit(&mut file);
+        std::fs::write(path, serde_json::to_string(&file).unwrap()).unwrap();
+    }
+
+    fn flip_first_byte(value: &str) -> String {
+        let mut bytes = BASE64.decode(value).unwrap();
+        bytes[0] ^= 0x01;
+        BASE64.encode(bytes)
+    }
+
+    #[test]
+    fn entries_round_trip_through_the_encrypted_file() {
+        let (path, id) = saved_store("roundtrip");
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("user@example.com"));
+        assert!(!raw.contains("Submariner"));
+
+        let store = SecretStore::default();
+        assert_eq!(store.unlock(&path, PASSPHRASE).unwrap(), 1);
+        let resolved = store.resolve(&id).unwrap();
+        assert_eq!(resolved.email.expose(), "user@example.com");
+        assert_eq!(resolved.carrier.expose(), "SKT");
+        assert_eq!(resolved.message, details().message);
+
+        store.lock();
+        assert!(!store.is_unlocked());
+        assert!(store.resolve(&id).is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn wrong_passphrase_is_rejected() {
+        let (path, _) = saved_store("wrong-passphrase");
+
+        let store = SecretStore::default();
+        let err = store.unlock(&path, "not the passphrase").unwrap_err();
+        assert!(err.contains("패스프레이즈가 올바르지 않거나"), "{}", err);
+        assert!(!store.is_unlocked());
+        assert!(store.unlock(&path, "").is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn tampered_ciphertext_or_nonce_is_rejected() {
+        let (path, _) = saved_store("tampered");
+        let original = std::fs::read_to_string(&path).unwrap();
+
+        edit_file(&path, |file| file.ciphertext = flip_first_byte(&file.ciphertext));
+        assert!(SecretStore::default().unlock(&path, PASSPHRASE).is_err());
+
+        std::fs::write(&path, &original).unwrap();
+        edit_file(&path, |file| file.nonce = flip_first_byte(&file.nonce));
+        assert!(SecretStore::default().unlock(&path, PASSPHRASE).is_err());
+
+        std::fs::write(&path, &original).unwrap();
+        edit_file(&path, |file| file.nonce = BASE64.encode([0u8; 8]));
+        let err = SecretStore::default().unlock(&path, PASSPHRASE).unwrap_err();
+        assert!(err.contains("nonce 길이"), "{}", err);
+
+        std::fs::write(&path, &original).unwrap();
+        assert!(SecretStore::default().unlock(&path, PASSPHRASE).is_ok());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,441 @@
+// 셀렉터 점검 - 예약 사이트 마크업이 바뀌었는지 실행 전에 확인
+// 각 셀렉터가 요소를 찾는지, 화면에 보이는지, 활성 상태인지, 여러 요소와 겹치지 않는지 보고
+// 버튼 단계는 자동화와 같은 로케이터 목록(설정의 locators, 없으면 기본 목록)을 순서대로 점검
+use chromiumoxide::browser::{Browser, BrowserConfig};
+use chromiumoxide::Page;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::control::Step;
+use crate::locators::{self, Locator, LocatorCounts, LocatorOverrides};
+
+// 셀렉터가 나타나는 페이지
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FlowPage {
+    // authUrl (매장 안내, 쿠키 팝업)
+    Landing,
+    // reserveUrl (컬렉션 선택, 동의, 날짜/시간 선택)
+    Appointment,
+    // PASS 인증 후 연락처 입력 폼 - URL 로 바로 열 수 없어 저장된 HTML 로만 점검
+    Contact,
+}
+
+pub struct SelectorSpec {
+    pub step: Step,
+    pub name: &'static str,
+    pub page: FlowPage,
+    // 고정 셀렉터 - 없으면 단계의 로케이터 목록을 사용 (버튼 단계)
+    pub selector: Option<&'static str>,
+    // 날짜/시간 목록처럼 여러 요소가 정상인 경우
+    pub multiple: bool,
+    // 쿠키 팝업처럼 없어도 되는 경우
+    pub optional: bool,
+}
+
+// 날짜/시간 목록과 입력란은 automation.rs 의 셀렉터와 동일하게 유지
+pub const FLOW_SELECTORS: [SelectorSpec; 10] = [
+    SelectorSpec {
+        step: Step::CookiePopup,
+        name: "cookie_accept",
+        page: FlowPage::Landing,
+        selector: None,
+        multiple: false,
+        optional: true,
+    },
+    SelectorSpec {
+        step: Step::VisitReservation,
+        name: "reservation_button",
+        page: FlowPage::Landing,
+        selector: None,
+        multiple: false,
+        optional: false,
+    },
+    SelectorSpec {
+        step: Step::RolexCollection,
+        name: "collection_button",
+        page: FlowPage::Appointment,
+        selector: None,
+        multiple: false,
+        optional: false,
+    },
+    SelectorSpec {
+        step: Step::Agree,
+        name: "agree_button",
+        page: FlowPage::Appointment,
+        selector: None,
+        multiple: false,
+        optional: false,
+    },
+    SelectorSpec {
+        step: Step::VisitDate,
+        name: "date_list",
+        page: FlowPage::Appointment,
+        selector: Some("#appointment .datetime-form .date-list ul > li[data-date]"),
+        multiple: true,
+        optional: false,
+    },
+    SelectorSpec {
+        step: Step::VisitTime,
+        name: "time_list",
+        page: FlowPage::Appointment,
+        selector: Some("#appointment .datetime-form .time-list ul > li[data-time]"),
+        multiple: true,
+        optional: false,
+    },
+    SelectorSpec {
+        step: Step::Next,
+        name: "next_button",
+        page: FlowPage::Appointment,
+        selector: None,
+        multiple: false,
+        optional: false,
+    },
+    SelectorSpec {
+        step: Step::Submit,
+        name: "email_input",
+        page: FlowPage::Contact,
+        selector: Some(r#"input[name="email"]"#),
+        multiple: false,
+        optional: false,
+    },
+    SelectorSpec {
+        step: Step::Submit,
+        name: "reception_consent",
+        page: FlowPage::Contact,
+        selector: Some(r#"input[name="reception_consent"]"#),
+        multiple: false,
+        optional: true,
+    },
+    SelectorSpec {
+        step: Step::Submit,
+        name: "submit_button",
+        page: FlowPage::Contact,
+        selector: None,
+        multiple: false,
+        optional: false,
+    },
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectorStatus {
+    Ok,
+    // 요소 없음 (텍스트 조건이 있으면 텍스트가 맞는 요소 없음)
+    Missing,
+    // 여러 요소와 일치 - 자동화는 첫 번째 요소만 사용하므로 다른 버튼을 누를 수 있음
+    Ambiguous,
+    // 요소는 있지만 보이지 않음 (이전 단계를 진행해야 보이는 경우도 있음)
+    Hidden,
+    Disabled,
+    // 페이지를 열 수 없어 점검하지 않음
+    Skipped,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SelectorCheck {
+    pub step: Step,
+    pub name: String,
+    pub page: FlowPage,
+    // 점검에 사용한 로케이터 (찾지 못했으면 목록의 첫 번째)
+    pub selector: String,
+    // 로케이터 목록 중 찾은 순번 (0 이 기본, 찾지 못했으면 None)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locator_index: Option<usize>,
+    pub optional: bool,
+    pub matches: u32,
+    pub visible: u32,
+    pub enabled: u32,
+    pub status: SelectorStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SelectorHealthReport {
+    // 점검한 URL 또는 HTML 파일 경로
+    pub sources: Vec<String>,
+    pub checks: Vec<SelectorCheck>,
+    // 필수 셀렉터 중 찾지 못했거나 겹치는 것이 없으면 true
+    pub healthy: bool,
+}
+
+impl SelectorHealthReport {
+    fn new(sources: Vec<String>, checks: Vec<SelectorCheck>) -> Self {
+        let healthy = !checks.iter().any(|c| {
+            !c.optional && matches!(c.status, SelectorStatus::Missing | SelectorStatus::Ambiguous)
+        });
+        Self {
+            sources,
+            checks,
+            healthy,
+        }
+    }
+}
+
+pub fn classify(spec: &SelectorSpec, counts: &LocatorCounts) -> SelectorStatus {
+    if counts.matches == 0 {
+        SelectorStatus::Missing
+    } else if !spec.multiple && counts.matches > 1 {
+        SelectorStatus::Ambiguous
+    } else if counts.visible == 0 {
+        SelectorStatus::Hidden
+    } else if counts.enabled == 0 {
+        SelectorStatus::Disabled
+    } else {
+        SelectorStatus::Ok
+    }
+}
+
+// 점검할 로케이터 목록 - 고정 셀렉터가 없으면 설정의 단계별 목록
+pub fn spec_chain(spec: &SelectorSpec, overrides: &LocatorOverrides) -> Vec<Locator> {
+    match spec.selector {
+        Some(selector) => vec![Locator::Css {
+            selector: selector.to_string(),
+        }],
+        None => locators::chain(overrides, spec.step),
+    }
+}
+
+// 자동화와 같이 화면에 보이는 요소를 찾는 첫 로케이터를 고르고, 없으면 요소가 있는 첫 로케이터
+pub fn pick_locator(counts: &[LocatorCounts]) -> Option<usize> {
+    counts
+        .iter()
+        .position(|c| c.visible > 0)
+        .or_else(|| counts.iter().position(|c| c.matches > 0))
+}
+
+fn check(spec: &SelectorSpec, chain: &[Locator], counts: &[LocatorCounts]) -> SelectorCheck {
+    let index = pick_locator(counts);
+    let counts = index.map(|i| counts[i].clone()).unwrap_or_default();
+    let status = classify(spec, &counts);
+    let note = match (status, index) {
+        (SelectorStatus::Ambiguous, _) => Some(format!(
+            "{}개 요소와 일치합니다 - 첫 번째 요소만 사용됩니다",
+            counts.matches
+        )),
+        (_, Some(i)) if i > 0 => Some(format!("기본 셀렉터 대신 대체 로케이터 #{} 로 찾았습니다", i + 1)),
+        _ => None,
+    };
+    SelectorCheck {
+        step: spec.step,
+        name: spec.name.to_string(),
+        page: spec.page,
+        selector: chain.get(index.unwrap_or(0)).map(ToString::to_string).unwrap_or_default(),
+        locator_index: index,
+        optional: spec.optional,
+        matches: counts.matches,
+        visible: counts.visible,
+        enabled: counts.enabled,
+        status,
+        note,
+    }
+}
+
+fn skipped(spec: &SelectorSpec, overrides: &LocatorOverrides, note: String) -> SelectorCheck {
+    SelectorCheck {
+        step: spec.step,
+        name: spec.name.to_string(),
+        page: spec.page,
+        selector: spec_chain(spec, overrides)
+            .first()
+            .map(ToString::to_string)
+            .unwrap_or_default(),
+        locator_index: None,
+        optional: spec.optional,
+        matches: 0,
+        visible: 0,
+        enabled: 0,
+        status: SelectorStatus::Skipped,
+        note: Some(note),
+    }
+}
+
+async fn check_page(
+    page: &Page,
+    specs: &[&SelectorSpec],
+    overrides: &LocatorOverrides,
+) -> Result<Vec<SelectorCheck>, String> {
+    let mut checks = Vec::new();
+    for spec in specs {
+        let chain = spec_chain(spec, overrides);
+        let mut counts = Vec::new();
+        for locator in &chain {
+            counts.push(
+                locators::count(page, locator)
+                    .await
+                    .map_err(|e| format!("셀렉터 점검 실패 ({}): {}", locator, e))?,
+            );
+        }
+        checks.push(check(spec, &chain, &counts));
+    }
+    Ok(checks)
+}
+
+// 점검용 헤드리스 브라우저 (자동화 실행과 별개)
+pub async fn with_browser<T>(
+    task: impl for<'a> FnOnce(&'a Page) -> futures::future::BoxFuture<'a, Result<T, String>>,
+) -> Result<T, String> {
+    let config = BrowserConfig::builder()
+        .no_sandbox()
+        .args(vec!["--disable-gpu", "--no-first-run"])
+        .build()
+        .map_err(|e| format!("{:?}", e))?;
+    let (mut browser, mut handler) = Browser::launch(config)
+        .await
+        .map_err(|e| format!("브라우저를 시작할 수 없습니다: {}", e))?;
+    let handler_task = tokio::spawn(async move { while handler.next().await.is_some() {} });
+
+    let result = match browser.new_page("about:blank").await {
+        Ok(page) => task(&page).await,
+        Err(e) => Err(format!("새 페이지 생성 실패: {}", e)),
+    };
+
+    if let Err(e) = browser.close().await {
+        warn!("⚠️ 점검 브라우저 종료 실패: {}", e);
+    }
+    handler_task.abort();
+    result
+}
+
+// authUrl / reserveUrl 을 차례로 열어 해당 페이지의 셀렉터를 점검
+pub async fn check_urls(
+    auth_url: &str,
+    reserve_url: &str,
+    overrides: &LocatorOverrides,
+) -> Result<SelectorHealthReport, String> {
+    let overrides = overrides.clone();
+    let auth_url = auth_url.to_string();
+    let reserve_url = reserve_url.to_string();
+    let sources = vec![auth_url.clone(), reserve_url.clone()];
+
+    let checks = with_browser(move |page| {
+        Box::pin(async move {
+            let mut checks = Vec::new();
+            for (flow_page, url) in [(FlowPage::Landing, &auth_url), (FlowPage::Appointment, &reserve_url)] {
+                let specs: Vec<&SelectorSpec> = FLOW_SELECTORS.iter().filter(|s| s.page == flow_page).collect();
+                info!("🔎 셀렉터 점검: {}", url);
+                let loaded = match tokio::time::timeout(Duration::from_secs(20), page.goto(url.as_str())).await {
+                    Ok(Ok(_)) => Ok(()),
+                    Ok(Err(e)) => Err(format!("페이지를 열 수 없습니다: {}", e)),
+                    Err(_) => Err("페이지 로딩 시간 초과".to_string()),
+                };
+                match loaded {
+                    Ok(()) => {
+                        tokio::time::sleep(Duration::from_secs(3)).await;
+                        checks.extend(check_page(page, &specs, &overrides).await?);
+                    }
+                    Err(e) => {
+                        warn!("⚠️ {} ({})", e, url);
+                        checks.extend(specs.iter().map(|spec| skipped(spec, &overrides, e.clone())));
+                    }
+                }
+            }
+            checks.extend(
+                FLOW_SELECTORS
+                    .iter()
+                    .filter(|s| s.page == FlowPage::Contact)
+                    .map(|spec| {
+                        skipped(spec, &overrides, "PASS 인증 후 페이지 - 저장된 HTML 로 점검하세요".to_string())
+                    }),
+            );
+            Ok(checks)
+        })
+    })
+    .await?;
+
+    Ok(SelectorHealthReport::new(sources, checks))
+}
+
+// 저장된 HTML 스냅샷 하나에 대해 모든 셀렉터를 점검
+pub async fn check_snapshot(
+    path: &std::path::Path,
+    overrides: &LocatorOverrides,
+) -> Result<SelectorHealthReport, String> {
+    let overrides = overrides.clone();
+    let html = std::fs::read_to_string(path)
+        .map_err(|e| format!("HTML 파일을 읽을 수 없습니다 ({}): {}", path.display(), e))?;
+
+    let checks = with_browser(move |page| {
+        Box::pin(async move {
+            page.set_content(html)
+                .await
+                .map_err(|e| format!("HTML 을 불러올 수 없습니다: {}", e))?;
+            let specs: Vec<&SelectorSpec> = FLOW_SELECTORS.iter().collect();
+            check_page(page, &specs, &overrides).await
+        })
+    })
+    .await?;
+
+    Ok(SelectorHealthReport::new(vec![path.display().to_string()], checks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(name: &str) -> &'static SelectorSpec {
+        FLOW_SELECTORS.iter().find(|s| s.name == name).unwrap()
+    }
+
+    fn counts(matches: u32, visible: u32, enabled: u32) -> LocatorCounts {
+        LocatorCounts {
+            matches,
+            visible,
+            enabled,
+        }
+    }
+
+    #[test]
+    fn button_steps_follow_configured_locators() {
+        let custom = vec![Locator::Css {
+            selector: "#agree-new".to_string(),
+        }];
+        let overrides = LocatorOverrides::from([(Step::Agree, custom.clone())]);
+
+        assert_eq!(spec_chain(spec("agree_button"), &overrides), custom);
+        assert_eq!(
+            spec_chain(spec("next_button"), &overrides),
+            locators::default_chain(Step::Next)
+        );
+        // 날짜 목록처럼 로케이터 목록이 없는 항목은 고정 셀렉터
+        assert_eq!(
+            spec_chain(spec("date_list"), &overrides),
+            vec![Locator::Css {
+                selector: "#appointment .datetime-form .date-list ul > li[data-date]".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn first_visible_locator_is_reported() {
+        let spec = spec("agree_button");
+        let chain = locators::default_chain(Step::Agree);
+
+        let fallback = check(spec, &chain, &[counts(0, 0, 0), counts(1, 0, 0), counts(1, 1, 1)]);
+        assert_eq!(fallback.locator_index, Some(2));
+        assert_eq!(fallback.selector, chain[2].to_string());
+        assert_eq!(fallback.status, SelectorStatus::Ok);
+        assert!(fallback.note.is_some());
+
+        let hidden = check(spec, &chain, &[counts(0, 0, 0), counts(1, 0, 0), counts(0, 0, 0)]);
+        assert_eq!(hidden.locator_index, Some(1));
+        assert_eq!(hidden.status, SelectorStatus::Hidden);
+
+        let missing = check(spec, &chain, &[counts(0, 0, 0), counts(0, 0, 0), counts(0, 0, 0)]);
+        assert_eq!(missing.locator_index, None);
+        assert_eq!(missing.selector, chain[0].to_string());
+        assert_eq!(missing.status, SelectorStatus::Missing);
+    }
+
+    #[test]
+    fn status_depends_on_counts() {
+        let button = spec("next_button");
+        let dates = spec("date_list");
+        assert_eq!(classify(button, &counts(2, 2, 2)), SelectorStatus::Ambiguous);
+        assert_eq!(classify(dates, &counts(7, 7, 3)), SelectorStatus::Ok);
+        assert_eq!(classify(dates, &counts(7, 7, 0)), SelectorStatus::Disabled);
+    }
+}
@@ -0,0 +1,353 @@
+// 실행 이벤트 전달 대상 (로컬 명령 실행, JSON Lines 파일, HTTP 웹훅)
+// 설정 파일의 notificationSinks 로 프로필마다 지정하며, 데스크톱 알림과 같은 이벤트를 받음
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tracing::warn;
+
+use crate::events::{RunEvent, RunEventKind};
+
+// 명령/웹훅이 응답하지 않을 때 기본 대기 시간
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SinkConfig {
+    #[serde(flatten)]
+    pub kind: SinkKind,
+    // 비어 있으면 모든 이벤트 전달
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<RunEventKind>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SinkKind {
+    // 셸을 거치지 않고 실행 - 이벤트 JSON 은 표준 입력으로 전달
+    Command {
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(rename = "timeoutSecs", default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
+    },
+    // 이벤트마다 JSON 한 줄씩 추가
+    JsonLines { path: String },
+    // 이벤트 JSON 을 POST
+    Webhook {
+        url: String,
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        headers: HashMap<String, String>,
+        #[serde(rename = "timeoutSecs", default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
+    },
+}
+
+pub trait NotificationSink: Send + Sync {
+    fn name(&self) -> String;
+    fn accepts(&self, kind: RunEventKind) -> bool;
+    fn deliver<'a>(&'a self, event: &'a RunEvent) -> BoxFuture<'a, Result<(), String>>;
+}
+
+// 설정 검증 - 잘못된 항목마다 (필드, 메시지)
+pub fn validate(config: &SinkConfig) -> Vec<(&'static str, String)> {
+    let mut errors = Vec::new();
+    match &config.kind {
+        SinkKind::Command { program, .. } => {
+            if program.trim().is_empty() {
+                errors.push(("program", "실행할 명령이 비어 있습니다".to_string()));
+            }
+        }
+        SinkKind::JsonLines { path } => {
+            if path.trim().is_empty() {
+                errors.push(("path", "파일 경로가 비어 있습니다".to_string()));
+            }
+        }
+        SinkKind::Webhook { url, .. } => match url::Url::parse(url) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+            _ => errors.push(("url", format!("잘못된 웹훅 URL: {}", url))),
+        },
+    }
+    errors
+}
+
+pub fn build(config: &SinkConfig) -> Box<dyn NotificationSink> {
+    let events = config.events.clone();
+    match &config.kind {
+        SinkKind::Command {
+            program,
+            args,
+            timeout_secs,
+        } => Box::new(CommandSink {
+            program: program.clone(),
+            args: args.clone(),
+            timeout: Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS)),
+            events,
+        }),
+        SinkKind::JsonLines { path } => Box::new(JsonLinesSink {
+            path: PathBuf::from(path),
+            events,
+        }),
+        SinkKind::Webhook {
+            url,
+            headers,
+            timeout_secs,
+        } => Box::new(WebhookSink {
+            url: url.clone(),
+            headers: headers.clone(),
+            // 이벤트마다 연결 풀을 새로 만들지 않도록 대상마다 한 번만 생성
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS)))
+                .build()
+                .map_err(|e| format!("웹훅 클라이언트를 만들 수 없습니다: {}", e)),
+            events,
+        }),
+    }
+}
+
+pub fn build_all(configs: &[SinkConfig]) -> Vec<Box<dyn NotificationSink>> {
+    configs.iter().map(build).collect()
+}
+
+// 모든 대상에 전달하고 대상별 결과를 돌려줌 (실패는 경고만 남기고 실행에는 영향 없음)
+pub async fn deliver_all(sinks: &[Box<dyn NotificationSink>], event: &RunEvent) -> Vec<(String, Result<(), String>)> {
+    let mut results = Vec::new();
+    for sink in sinks.iter().filter(|s| s.accepts(event.kind)) {
+        let result = sink.deliver(event).await;
+        if let Err(e) = &result {
+            warn!("⚠️ 알림 전달 실패 ({}): {}", sink.name(), e);
+        }
+        results.push((sink.name(), result));
+    }
+    results
+}
+
+fn accepts(events: &[RunEventKind], kind: RunEventKind) -> bool {
+    events.is_empty() || events.contains(&kind)
+}
+
+pub struct CommandSink {
+    program: String,
+    args: Vec<String>,
+    timeout: Duration,
+    events: Vec<RunEventKind>,
+}
+
+impl NotificationSink for CommandSink {
+    fn name(&self) -> String {
+        format!("command:{}", self.program)
+    }
+
+    fn accepts(&self, kind: RunEventKind) -> bool {
+        accepts(&self.events, kind)
+    }
+
+    fn deliver<'a>(&'a self, event: &'a RunEvent) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let payload = serde_json::to_vec(event).map_err(|e| e.to_string())?;
+            let mut child = tokio::process::Command::new(&self.program)
+                .args(&self.args)
+                .env("ROLEX_EVENT_KIND", event.kind.as_str())
+                .env("ROLEX_RUN_ID", &event.run_id)
+                .env("ROLEX_STORE_NAME", &event.store_name)
+                .stdin(std::process::Stdio::piped())
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .kill_on_drop(true)
+                .spawn()
+                .map_err(|e| format!("명령을 실행할 수 없습니다: {}", e))?;
+
+            if let Some(mut stdin) = child.stdin.take() {
+                let _ = stdin.write_all(&payload).await;
+            }
+
+            match tokio::time::timeout(self.timeout, child.wait()).await {
+                Ok(Ok(status)) if status.success() => Ok(()),
+                Ok(Ok(status)) => Err(format!("명령이 실패했습니다: {}", status)),
+                Ok(Err(e)) => Err(format!("명령 실행 오류: {}", e)),
+                Err(_) => Err(format!("명령이 {}초 안에 끝나지 않았습니다", self.timeout.as_secs())),
+            }
+        })
+    }
+}
+
+pub struct JsonLinesSink {
+    path: PathBuf,
+    events: Vec<RunEventKind>,
+}
+
+impl NotificationSink for JsonLinesSink {
+    fn name(&self) -> String {
+        format!("json_lines:{}", self.path.display())
+    }
+
+    fn accepts(&self, kind: RunEventKind) -> bool {
+        accepts(&self.events, kind)
+    }
+
+    fn deliver<'a>(&'a self, event: &'a RunEvent) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let line = serde_json::to_string(event).map_err(|e| e.to_string())?;
+            let path = self.path.clone();
+            // 한 줄을 한 번의 write 로 추가해 동시 실행 중에도 줄이 섞이지 않도록 함
+            tokio::task::spawn_blocking(move || {
+                if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                    std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                }
+                let mut file = std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+                    .map_err(|e| format!("파일을 열 수 없습니다 ({}): {}", path.display(), e))?;
+                file.write_all(format!("{}\n", line).as_bytes())
+                    .map_err(|e| format!("파일에 쓸 수 없습니다 ({}): {}", path.display(), e))
+            })
+            .await
+            .map_err(|e| e.to_string())?
+        })
+    }
+}
+
+pub struct WebhookSink {
+    url: String,
+    headers: HashMap<String, String>,
+    client: Result<reqwest::Client, String>,
+    events: Vec<RunEventKind>,
+}
+
+impl NotificationSink for WebhookSink {
+    fn name(&self) -> String {
+        // URL 쿼리에 토큰이 있을 수 있으므로 호스트만 표시
+        let host = url::Url::parse(&self.url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_default();
+        format!("webhook:{}", host)
+    }
+
+    fn accepts(&self, kind: RunEventKind) -> bool {
+        accepts(&self.events, kind)
+    }
+
+    fn deliver<'a>(&'a self, event: &'a RunEvent) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let client = self.client.as_ref().map_err(Clone::clone)?;
+            let mut request = client.post(&self.url).json(event);
+            for (name, value) in &self.headers {
+                request = request.header(name, value);
+            }
+            let response = request
+                .send()
+                .await
+                .map_err(|e| format!("웹훅 요청 실패: {}", e))?;
+            if !response.status().is_success() {
+                return Err(format!("웹훅 응답 오류: {}", response.status()));
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    fn event() -> RunEvent {
+        RunEvent {
+            run_id: "20250601-093000-0001".to_string(),
+            store_name: "테스트 매장".to_string(),
+            kind: RunEventKind::Succeeded,
+            message: "예약 완료".to_string(),
+            timestamp: "2025-06-01 09:30:00".to_string(),
+            window: None,
+        }
+    }
+
+    // 요청 하나를 받아 (헤더, 본문) 을 돌려주고 주어진 상태 코드로 응답하는 서버
+    async fn serve_once(status: &'static str) -> (String, tokio::task::JoinHandle<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook?token=secret", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut received = Vec::new();
+            let mut buf = [0u8; 4096];
+            let (head, body_start, content_length) = loop {
+                let n = stream.read(&mut buf).await.unwrap();
+                assert!(n > 0, "요청이 끝나기 전에 연결이 닫힘");
+                received.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&received).to_string();
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let head = text[..end].to_string();
+                    let content_length = head
+                        .lines()
+                        .find_map(|l| l.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().to_string()))
+                        .and_then(|v| v.parse::<usize>().ok())
+                        .unwrap_or(0);
+                    break (head, end + 4, content_length);
+                }
+            };
+            while received.len() < body_start + content_length {
+                let n = stream.read(&mut buf).await.unwrap();
+                assert!(n > 0, "본문을 다 받기 전에 연결이 닫힘");
+                received.extend_from_slice(&buf[..n]);
+            }
+            let body = String::from_utf8(received[body_start..body_start + content_length].to_vec()).unwrap();
+            let response = format!("HTTP/1.1 {}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n", status);
+            stream.write_all(response.as_bytes()).await.unwrap();
+            (head, body)
+        });
+        (url, handle)
+    }
+
+    fn webhook(url: String) -> Box<dyn NotificationSink> {
+        build(&SinkConfig {
+            kind: SinkKind::Webhook {
+                url,
+                headers: HashMap::from([("X-Api-Key".to_string(), "key-123".to_string())]),
+                timeout_secs: Some(5),
+            },
+            events: Vec::new(),
+        })
+    }
+
+    #[tokio::test]
+    async fn webhook_posts_event_json() {
+        let (url, server) = serve_once("204 No Content").await;
+        let sink = webhook(url);
+        assert_eq!(sink.name(), "webhook:127.0.0.1");
+
+        sink.deliver(&event()).await.unwrap();
+        let (head, body) = server.await.unwrap();
+        let head = head.to_ascii_lowercase();
+        assert!(head.starts_with("post /hook?token=secret http/1.1"), "{}", head);
+        assert!(head.contains("\r\ncontent-type: application/json"), "{}", head);
+        assert!(head.contains("\r\nx-api-key: key-123"), "{}", head);
+
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body, serde_json::to_value(event()).unwrap());
+        assert_eq!(body["kind"], "succeeded");
+    }
+
+    #[tokio::test]
+    async fn webhook_error_status_is_reported() {
+        let (url, server) = serve_once("500 Internal Server Error").await;
+        let error = webhook(url).deliver(&event()).await.unwrap_err();
+        server.await.unwrap();
+        assert!(error.contains("500"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn unreachable_webhook_is_reported() {
+        // 바로 닫아 아무도 받지 않는 포트
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        drop(listener);
+        let error = webhook(url).deliver(&event()).await.unwrap_err();
+        assert!(error.starts_with("웹훅 요청 실패"), "{}", error);
+    }
+}
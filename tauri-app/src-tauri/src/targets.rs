@@ -0,0 +1,349 @@
+// 브라우저 창(CDP target)과 iframe 추적 - PASS 인증 팝업이 어디에 떠 있는지 확인
+// window.opener 등 페이지 안 JS 로는 별도 target 으로 열린 팝업을 볼 수 없으므로 CDP 이벤트를 사용
+use chromiumoxide::browser::Browser;
+use chromiumoxide::cdp::browser_protocol::dom::GetFrameOwnerParams;
+use chromiumoxide::cdp::browser_protocol::page::{EventFrameDetached, EventFrameNavigated};
+use chromiumoxide::cdp::browser_protocol::target::{
+    EventTargetCreated, EventTargetDestroyed, EventTargetInfoChanged, TargetInfo,
+};
+use chromiumoxide::Page;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::locators;
+
+// 인증 iframe 으로 볼 URL - 호스트/경로를 영숫자 단위로 나눈 조각 중 하나가 정확히 일치해야 함
+// ("oauth", "author", "passive" 등은 해당하지 않음)
+const AUTH_FRAME_HINTS: [&str; 3] = ["pass", "auth", "okname"];
+
+// 기존 iframe 확인과 같은 기준 (display: none 이 아니면 보이는 것으로 봄)
+const FRAME_VISIBLE_FN: &str = "function() { return window.getComputedStyle(this).display !== 'none'; }";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TrackedWindow {
+    pub target_id: String,
+    pub url: String,
+    pub title: String,
+    pub opener_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TrackedFrame {
+    pub frame_id: String,
+    pub url: String,
+}
+
+// 사용자가 인증을 진행해야 하는 위치
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "window", rename_all = "snake_case")]
+pub enum ActionWindow {
+    Popup {
+        target_id: String,
+        url: String,
+        title: String,
+    },
+    Iframe {
+        frame_id: String,
+        url: String,
+    },
+    MainPage {
+        url: Option<String>,
+    },
+}
+
+impl ActionWindow {
+    // 창을 앞으로 가져올 때 사용할 target id (팝업만 해당)
+    pub fn target_id(&self) -> Option<&str> {
+        match self {
+            ActionWindow::Popup { target_id, .. } => Some(target_id),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ActionWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ActionWindow::Popup { url, title, .. } if title.is_empty() => write!(f, "팝업 창 ({})", url),
+            ActionWindow::Popup { url, title, .. } => write!(f, "팝업 창 '{}' ({})", title, url),
+            ActionWindow::Iframe { url, .. } => write!(f, "예약 페이지 안의 인증 프레임 ({})", url),
+            ActionWindow::MainPage { url: Some(url) } => write!(f, "예약 페이지 ({})", url),
+            ActionWindow::MainPage { url: None } => write!(f, "예약 페이지"),
+        }
+    }
+}
+
+#[derive(Default)]
+struct TrackerState {
+    // 열린 순서대로 유지 (마지막이 가장 최근 팝업)
+    popups: Vec<TrackedWindow>,
+    frames: Vec<TrackedFrame>,
+}
+
+pub struct TargetTracker {
+    state: Arc<Mutex<TrackerState>>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl TargetTracker {
+    // 리스너 등록에 실패해도 실행은 계속 (그 경우 예약 페이지 기준으로만 판단)
+    pub async fn start(browser: &Browser, page: &Page) -> Self {
+        let main_target = page.target_id().as_ref().to_string();
+        let state = Arc::new(Mutex::new(TrackerState::default()));
+        let mut tasks = Vec::new();
+
+        match browser.event_listener::<EventTargetCreated>().await {
+            Ok(mut events) => {
+                let state = state.clone();
+                let main_target = main_target.clone();
+                tasks.push(tokio::spawn(async move {
+                    while let Some(event) = events.next().await {
+                        let target = &event.target_info;
+                        if target.r#type != "page" || target.target_id.as_ref() == main_target {
+                            continue;
+                        }
+                        if let Ok(mut state) = state.lock() {
+                            let opener = target.opener_id.as_ref().map(|id| id.as_ref());
+                            if !opened_from(opener, &main_target, &state.popups) {
+                                debug!("예약 페이지와 관계없는 창은 무시: {}", target.url);
+                                continue;
+                            }
+                            info!("🪟 새 창 열림: {}", target.url);
+                            state.popups.push(tracked_window(target));
+                        }
+                    }
+                }));
+            }
+            Err(e) => warn!("⚠️ 창 열림 이벤트를 구독할 수 없습니다: {}", e),
+        }
+
+        match browser.event_listener::<EventTargetInfoChanged>().await {
+            Ok(mut events) => {
+                let state = state.clone();
+                tasks.push(tokio::spawn(async move {
+                    while let Some(event) = events.next().await {
+                        let target = &event.target_info;
+                        if let Ok(mut state) = state.lock() {
+                            if let Some(window) = state
+                                .popups
+                                .iter_mut()
+                                .find(|w| w.target_id == target.target_id.as_ref())
+                            {
+                                *window = tracked_window(target);
+                            }
+                        }
+                    }
+                }));
+            }
+            Err(e) => warn!("⚠️ 창 정보 이벤트를 구독할 수 없습니다: {}", e),
+        }
+
+        match browser.event_listener::<EventTargetDestroyed>().await {
+            Ok(mut events) => {
+                let state = state.clone();
+                tasks.push(tokio::spawn(async move {
+                    while let Some(event) = events.next().await {
+                        if let Ok(mut state) = state.lock() {
+                            if let Some(index) = state
+                                .popups
+                                .iter()
+                                .position(|w| w.target_id == event.target_id.as_ref())
+                            {
+                                let window = state.popups.remove(index);
+                                info!("🪟 창 닫힘: {}", window.url);
+                            }
+                        }
+                    }
+                }));
+            }
+            Err(e) => warn!("⚠️ 창 닫힘 이벤트를 구독할 수 없습니다: {}", e),
+        }
+
+        match page.event_listener::<EventFrameNavigated>().await {
+            Ok(mut events) => {
+                let state = state.clone();
+                tasks.push(tokio::spawn(async move {
+                    while let Some(event) = events.next().await {
+                        // 최상위 프레임(예약 페이지 자체)은 제외
+                        if event.frame.parent_id.is_none() {
+                            continue;
+                        }
+                        let frame_id = event.frame.id.as_ref().to_string();
+                        let url = event.frame.url.clone();
+                        if let Ok(mut state) = state.lock() {
+                            match state.frames.iter_mut().find(|f| f.frame_id == frame_id) {
+                                Some(frame) => frame.url = url,
+                                None => state.frames.push(TrackedFrame { frame_id, url }),
+                            }
+                        }
+                    }
+                }));
+            }
+            Err(e) => warn!("⚠️ 프레임 이동 이벤트를 구독할 수 없습니다: {}", e),
+        }
+
+        match page.event_listener::<EventFrameDetached>().await {
+            Ok(mut events) => {
+                let state = state.clone();
+                tasks.push(tokio::spawn(async move {
+                    while let Some(event) = events.next().await {
+                        if let Ok(mut state) = state.lock() {
+                            state.frames.retain(|f| f.frame_id != event.frame_id.as_ref());
+                        }
+                    }
+                }));
+            }
+            Err(e) => warn!("⚠️ 프레임 제거 이벤트를 구독할 수 없습니다: {}", e),
+        }
+
+        Self {
+            state,
+            tasks,
+        }
+    }
+
+    pub fn open_popups(&self) -> Vec<TrackedWindow> {
+        self.state.lock().map(|s| s.popups.clone()).unwrap_or_default()
+    }
+
+    // 인증 URL 이면서 예약 페이지에 보이는 iframe
+    pub async fn auth_frames(&self, page: &Page) -> Vec<TrackedFrame> {
+        let frames: Vec<TrackedFrame> = self
+            .state
+            .lock()
+            .map(|s| s.frames.iter().filter(|f| is_auth_url(&f.url)).cloned().collect())
+            .unwrap_or_default();
+
+        let mut visible = Vec::new();
+        for frame in frames {
+            if is_frame_visible(page, &frame.frame_id).await {
+                visible.push(frame);
+            }
+        }
+        visible
+    }
+
+    // 인증 팝업 또는 보이는 인증 iframe 이 남아 있으면 true
+    pub async fn auth_open(&self, page: &Page) -> bool {
+        !self.open_popups().is_empty() || !self.auth_frames(page).await.is_empty()
+    }
+
+    // 가장 최근에 열린 팝업 > 인증 iframe > 예약 페이지 순으로 사용자가 조작할 위치를 고름
+    pub async fn action_window(&self, page: &Page) -> ActionWindow {
+        if let Some(popup) = self.open_popups().pop() {
+            return ActionWindow::Popup {
+                target_id: popup.target_id,
+                url: popup.url,
+                title: popup.title,
+            };
+        }
+        if let Some(frame) = self.auth_frames(page).await.pop() {
+            return ActionWindow::Iframe {
+                frame_id: frame.frame_id,
+                url: frame.url,
+            };
+        }
+        ActionWindow::MainPage {
+            url: page.url().await.ok().flatten(),
+        }
+    }
+}
+
+impl Drop for TargetTracker {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+fn tracked_window(target: &TargetInfo) -> TrackedWindow {
+    TrackedWindow {
+        target_id: target.target_id.as_ref().to_string(),
+        url: target.url.clone(),
+        title: target.title.clone(),
+        opener_id: target.opener_id.as_ref().map(|id| id.as_ref().to_string()),
+    }
+}
+
+// 예약 페이지가 연 창이거나, 이미 추적 중인 인증 팝업이 다시 연 창 (관계없는 탭은 제외)
+fn opened_from(opener: Option<&str>, main_target: &str, popups: &[TrackedWindow]) -> bool {
+    match opener {
+        Some(opener) => opener == main_target || popups.iter().any(|w| w.target_id == opener),
+        None => false,
+    }
+}
+
+fn is_auth_url(url: &str) -> bool {
+    let Ok(url) = url::Url::parse(url) else {
+        return false;
+    };
+    let host = url.host_str().unwrap_or_default().to_lowercase();
+    let path = url.path().to_lowercase();
+    host.split(|c: char| !c.is_ascii_alphanumeric())
+        .chain(path.split(|c: char| !c.is_ascii_alphanumeric()))
+        .any(|token| AUTH_FRAME_HINTS.contains(&token))
+}
+
+// iframe 요소(프레임 소유 노드)가 화면에 보이는지 확인 - 요소를 찾을 수 없으면 보이지 않는 것으로 봄
+async fn is_frame_visible(page: &Page, frame_id: &str) -> bool {
+    let owner = match page.execute(GetFrameOwnerParams::new(frame_id.to_string())).await {
+        Ok(owner) => owner.result.backend_node_id,
+        Err(e) => {
+            debug!("프레임 {} 의 iframe 요소를 찾을 수 없습니다: {}", frame_id, e);
+            return false;
+        }
+    };
+    match locators::call_on_node(page, owner, FRAME_VISIBLE_FN).await {
+        Ok(Some(visible)) => visible.as_bool().unwrap_or(false),
+        Ok(None) => false,
+        Err(e) => {
+            debug!("프레임 {} 표시 여부 확인 실패: {}", frame_id, e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(target_id: &str) -> TrackedWindow {
+        TrackedWindow {
+            target_id: target_id.to_string(),
+            url: "https://pass.example.com/".to_string(),
+            title: String::new(),
+            opener_id: Some("main".to_string()),
+        }
+    }
+
+    #[test]
+    fn only_windows_opened_from_the_booking_page_are_popups() {
+        let popups = vec![window("popup")];
+
+        assert!(opened_from(Some("main"), "main", &[]));
+        // 인증 팝업이 다시 연 창
+        assert!(opened_from(Some("popup"), "main", &popups));
+        // 다른 탭에서 열었거나 사용자가 직접 연 탭
+        assert!(!opened_from(Some("other"), "main", &popups));
+        assert!(!opened_from(None, "main", &popups));
+    }
+
+    #[test]
+    fn auth_urls_match_whole_host_or_path_tokens() {
+        assert!(is_auth_url("https://pass.example.co.kr/cert"));
+        assert!(is_auth_url("https://nice.example.com/auth/start?x=1"));
+        assert!(is_auth_url("https://safe.okname.co.kr/"));
+        assert!(is_auth_url("https://cert.example.com/check-pass/index"));
+
+        assert!(!is_auth_url("https://accounts.example.com/oauth/callback"));
+        assert!(!is_auth_url("https://www.example.com/author/rolex"));
+        assert!(!is_auth_url("https://ads.example.com/passive-frame"));
+        // 쿼리 문자열은 보지 않음
+        assert!(!is_auth_url("https://ads.example.com/frame?from=pass"));
+        assert!(!is_auth_url("about:blank"));
+    }
+}